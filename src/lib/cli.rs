@@ -16,7 +16,7 @@ use crate::logger::LoggerOptions;
 use crate::profile;
 use crate::recursion_level;
 use crate::runner;
-use crate::types::{CliArgs, GlobalConfig};
+use crate::types::{CliArgs, GlobalConfig, LoadOptions};
 use crate::version;
 use clap::{App, Arg, ArgMatches, SubCommand};
 
@@ -24,6 +24,7 @@ static VERSION: &str = env!("CARGO_PKG_VERSION");
 static AUTHOR: &str = env!("CARGO_PKG_AUTHORS");
 static DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
 static DEFAULT_TOML: &str = "Makefile.toml";
+static DEFAULT_TOML_FILE_NAMES: &[&str] = &["Makefile.toml", ".cargo-make.toml"];
 static DEFAULT_LOG_LEVEL: &str = "info";
 static DEFAULT_TASK_NAME: &str = "default";
 static DEFAULT_OUTPUT_FORMAT: &str = "default";
@@ -75,38 +76,46 @@ fn run(cli_args: CliArgs, global_config: &GlobalConfig) {
     let home = environment::setup_cwd(cwd);
 
     let force_makefile = cli_args.build_file.is_some();
-    let build_file = &cli_args
-        .build_file
-        .clone()
-        .unwrap_or(DEFAULT_TOML.to_string());
+    let build_file = &cli_args.build_file.clone().unwrap_or_else(|| {
+        descriptor::find_descriptor_file(".", DEFAULT_TOML_FILE_NAMES)
+            .unwrap_or(DEFAULT_TOML.to_string())
+    });
     let task = &cli_args.task;
-    let profile_name = &cli_args
-        .profile
-        .clone()
-        .unwrap_or(profile::DEFAULT_PROFILE.to_string());
-    let normalized_profile_name = profile::set(&profile_name);
+    // the makefile default profile is only used if no profile was provided
+    match cli_args.profile {
+        Some(ref profile_name) => {
+            profile::set(&profile_name);
+        }
+        None => profile::unset(),
+    };
 
     environment::load_env_file(cli_args.env_file.clone());
 
     let env = cli_args.env.clone();
 
     let experimental = cli_args.experimental;
-    let descriptor_load_result = descriptor::load(&build_file, force_makefile, env, experimental);
+    let descriptor_load_result = descriptor::load(
+        &build_file,
+        force_makefile,
+        env,
+        experimental,
+        &LoadOptions {
+            strict: cli_args.strict,
+            strict_env: cli_args.strict_env,
+            ..LoadOptions::new()
+        },
+    );
 
     let config = match descriptor_load_result {
         Ok(config) => config,
-        Err(ref min_version) => {
-            error!(
-                "{} version: {} does not meet minimum required version: {}",
-                &cli_args.command, &VERSION, &min_version
-            );
-            panic!(
-                "{} version: {} does not meet minimum required version: {}",
-                &cli_args.command, &VERSION, &min_version
-            );
+        Err(ref error) => {
+            error!("{} {}", &cli_args.command, &error);
+            panic!("{} {}", &cli_args.command, &error);
         }
     };
 
+    let normalized_profile_name = profile::set(&profile::get());
+
     match config.config.additional_profiles {
         Some(ref profiles) => profile::set_additional(profiles),
         None => profile::set_additional(&vec![]),
@@ -128,7 +137,14 @@ fn run(cli_args: CliArgs, global_config: &GlobalConfig) {
     if cli_args.list_all_steps {
         cli_commands::list_steps::run(&config, &cli_args.output_format, &cli_args.output_file);
     } else if cli_args.diff_execution_plan {
-        let default_config = descriptor::load_internal_descriptors(true, experimental, None);
+        let default_config =
+            match descriptor::load_internal_descriptors(true, experimental, None, &vec![]) {
+                Ok(default_config) => default_config,
+                Err(ref error) => {
+                    error!("{} {}", &cli_args.command, &error);
+                    panic!("{} {}", &cli_args.command, &error);
+                }
+            };
         cli_commands::diff_steps::run(&default_config, &config, &task, &cli_args);
     } else if cli_args.print_only {
         cli_commands::print_steps::print(
@@ -224,13 +240,19 @@ fn run_for_args(
         None => None,
     };
 
-    let profile_name = cmd_matches
-        .value_of("profile".to_string())
-        .unwrap_or(profile::DEFAULT_PROFILE);
-    cli_args.profile = Some(profile_name.to_string());
+    cli_args.profile = if cmd_matches.occurrences_of("profile") > 0 {
+        match cmd_matches.value_of("profile") {
+            Some(value) => Some(value.to_string()),
+            None => None,
+        }
+    } else {
+        None
+    };
 
     cli_args.disable_check_for_updates = cmd_matches.is_present("disable-check-for-updates");
     cli_args.experimental = cmd_matches.is_present("experimental");
+    cli_args.strict = cmd_matches.is_present("strict");
+    cli_args.strict_env = cmd_matches.is_present("strict-env");
     cli_args.print_only = cmd_matches.is_present("print-steps");
     cli_args.disable_workspace = cmd_matches.is_present("no-workspace");
     cli_args.disable_on_error = cmd_matches.is_present("no-on-error");
@@ -380,6 +402,16 @@ fn create_cli<'a, 'b>(
                 .long("--experimental")
                 .help("Allows access unsupported experimental predefined tasks."),
         )
+        .arg(
+            Arg::with_name("strict")
+                .long("--strict")
+                .help("Fails in case the makefiles contain unknown keys or duplicate tasks, are empty, override a final task, reference a missing env file or their path can not be resolved"),
+        )
+        .arg(
+            Arg::with_name("strict-env")
+                .long("--strict-env")
+                .help("Fails in case an --env key is not defined in the makefiles env"),
+        )
         .arg(
             Arg::with_name("disable-check-for-updates")
                 .long("--disable-check-for-updates")
@@ -33,6 +33,8 @@ fn run_makefile_not_found() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -67,6 +69,8 @@ fn run_empty_task() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -101,6 +105,8 @@ fn print_empty_task() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -135,6 +141,8 @@ fn list_empty_task() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -169,6 +177,8 @@ fn run_file_and_task() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -206,6 +216,8 @@ fn run_cwd_with_file() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -241,6 +253,8 @@ fn run_file_not_go_to_project_root() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -276,6 +290,8 @@ fn run_cwd_go_to_project_root_current_dir() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -314,6 +330,8 @@ fn run_cwd_go_to_project_root_child_dir() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -352,6 +370,8 @@ fn run_cwd_task_not_found() {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         },
         &global_config,
     );
@@ -0,0 +1,108 @@
+//! # diff
+//!
+//! Compares two fully merged configs.<br>
+//! Useful to see the impact of changing an extended (base) makefile on the final config.
+//!
+
+#[cfg(test)]
+#[path = "./diff_test.rs"]
+mod diff_test;
+
+use crate::types::{Config, ConfigDiff, Task};
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json;
+
+fn to_json_value<T: Serialize>(value: &T) -> serde_json::Value {
+    match serde_json::to_value(value) {
+        Ok(json_value) => json_value,
+        Err(_) => serde_json::Value::Null,
+    }
+}
+
+fn get_task_json_value(task: &Task) -> serde_json::Value {
+    let mut task_value = to_json_value(task);
+
+    // the task location info env vars are not part of the task definition
+    if let Some(env_value) = task_value.get_mut("env") {
+        if let serde_json::Value::Object(ref mut env) = env_value {
+            env.retain(|key, _| !key.starts_with("CARGO_MAKE_CURRENT_TASK_"));
+
+            if env.is_empty() {
+                *env_value = serde_json::Value::Null;
+            }
+        }
+    }
+
+    task_value
+}
+
+fn diff_maps<T, F>(
+    old: &IndexMap<String, T>,
+    new: &IndexMap<String, T>,
+    to_value: F,
+) -> (Vec<String>, Vec<String>, Vec<String>)
+where
+    F: Fn(&T) -> serde_json::Value,
+{
+    let mut added = vec![];
+    let mut removed = vec![];
+    let mut changed = vec![];
+
+    for (key, old_value) in old.iter() {
+        match new.get(key) {
+            Some(new_value) => {
+                if to_value(old_value) != to_value(new_value) {
+                    changed.push(key.to_string());
+                }
+            }
+            None => removed.push(key.to_string()),
+        }
+    }
+
+    for key in new.keys() {
+        if !old.contains_key(key) {
+            added.push(key.to_string());
+        }
+    }
+
+    (added, removed, changed)
+}
+
+fn diff_config_sections(old: &Config, new: &Config) -> Vec<String> {
+    let old_value = to_json_value(&old.config);
+    let new_value = to_json_value(&new.config);
+
+    let mut changed = vec![];
+    if let (Some(old_fields), Some(new_fields)) = (old_value.as_object(), new_value.as_object()) {
+        for (field, old_field_value) in old_fields.iter() {
+            let new_field_value = new_fields.get(field).unwrap_or(&serde_json::Value::Null);
+
+            if old_field_value != new_field_value {
+                changed.push(field.to_string());
+            }
+        }
+    }
+
+    changed
+}
+
+/// Returns the added/removed/changed tasks, env keys and config section fields between
+/// the old and new configs.
+pub fn diff_configs(old: &Config, new: &Config) -> ConfigDiff {
+    let mut config_diff = ConfigDiff::new();
+
+    let (added, removed, changed) = diff_maps(&old.tasks, &new.tasks, get_task_json_value);
+    config_diff.added_tasks = added;
+    config_diff.removed_tasks = removed;
+    config_diff.changed_tasks = changed;
+
+    let (added, removed, changed) = diff_maps(&old.env, &new.env, to_json_value);
+    config_diff.added_env = added;
+    config_diff.removed_env = removed;
+    config_diff.changed_env = changed;
+
+    config_diff.changed_config = diff_config_sections(old, new);
+
+    config_diff
+}
@@ -0,0 +1,105 @@
+use super::*;
+use crate::types::{ConfigSection, EnvValue};
+
+fn create_config() -> Config {
+    let mut env = IndexMap::new();
+    env.insert("ENV1".to_string(), EnvValue::Value("value1".to_string()));
+    env.insert("ENV2".to_string(), EnvValue::Value("value2".to_string()));
+
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    let mut tasks = IndexMap::new();
+    tasks.insert("task1".to_string(), task);
+
+    Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env,
+        env_scripts: vec![],
+        tasks,
+    }
+}
+
+#[test]
+fn diff_configs_same() {
+    let config_diff = diff_configs(&create_config(), &create_config());
+
+    assert!(config_diff.is_empty());
+}
+
+#[test]
+fn diff_configs_added_task() {
+    let old = create_config();
+    let mut new = create_config();
+    new.tasks.insert("task2".to_string(), Task::new());
+
+    let config_diff = diff_configs(&old, &new);
+
+    assert_eq!(config_diff.added_tasks, vec!["task2"]);
+    assert!(config_diff.removed_tasks.is_empty());
+    assert!(config_diff.changed_tasks.is_empty());
+    assert!(config_diff.added_env.is_empty());
+    assert!(config_diff.removed_env.is_empty());
+    assert!(config_diff.changed_env.is_empty());
+    assert!(config_diff.changed_config.is_empty());
+}
+
+#[test]
+fn diff_configs_changed_task() {
+    let old = create_config();
+    let mut new = create_config();
+    new.tasks.get_mut("task1").unwrap().command = Some("other".to_string());
+
+    let config_diff = diff_configs(&old, &new);
+
+    assert_eq!(config_diff.changed_tasks, vec!["task1"]);
+    assert!(config_diff.added_tasks.is_empty());
+    assert!(config_diff.removed_tasks.is_empty());
+}
+
+#[test]
+fn diff_configs_task_location_info_ignored() {
+    let old = create_config();
+    let mut new = create_config();
+    let mut env = IndexMap::new();
+    env.insert(
+        "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE".to_string(),
+        EnvValue::Value("./Makefile.toml".to_string()),
+    );
+    new.tasks.get_mut("task1").unwrap().env = Some(env);
+
+    let config_diff = diff_configs(&old, &new);
+
+    assert!(config_diff.is_empty());
+}
+
+#[test]
+fn diff_configs_removed_env() {
+    let old = create_config();
+    let mut new = create_config();
+    new.env.remove("ENV2");
+    new.env
+        .insert("ENV1".to_string(), EnvValue::Value("other".to_string()));
+
+    let config_diff = diff_configs(&old, &new);
+
+    assert_eq!(config_diff.removed_env, vec!["ENV2"]);
+    assert_eq!(config_diff.changed_env, vec!["ENV1"]);
+    assert!(config_diff.added_env.is_empty());
+    assert!(config_diff.added_tasks.is_empty());
+    assert!(config_diff.removed_tasks.is_empty());
+    assert!(config_diff.changed_tasks.is_empty());
+}
+
+#[test]
+fn diff_configs_changed_config_field() {
+    let old = create_config();
+    let mut new = create_config();
+    new.config.min_version = Some("0.30.0".to_string());
+
+    let config_diff = diff_configs(&old, &new);
+
+    assert_eq!(config_diff.changed_config, vec!["min_version"]);
+    assert!(config_diff.added_tasks.is_empty());
+    assert!(config_diff.changed_env.is_empty());
+}
@@ -18,3 +18,15 @@ pub(crate) static STABLE: &str = concat!(
     include_str!("deprecated.toml")
 );
 pub(crate) static BETA: &str = include_str!("beta.toml");
+
+static PRESET_RUST_QUALITY: &str = include_str!("presets/rust-quality.toml");
+static PRESET_RUST_DOCS: &str = include_str!("presets/rust-docs.toml");
+
+/// Returns the built-in preset makefile text for the provided preset name.
+pub(crate) fn get_preset(name: &str) -> Option<&'static str> {
+    match name {
+        "rust-quality" => Some(PRESET_RUST_QUALITY),
+        "rust-docs" => Some(PRESET_RUST_DOCS),
+        _ => None,
+    }
+}
@@ -4,7 +4,9 @@ use crate::runner;
 use crate::scriptengine;
 use crate::scriptengine::EngineType;
 use crate::test;
-use crate::types::{Config, CrateInfo, EnvInfo, FlowInfo, FlowState, RunTaskInfo, Step, Task};
+use crate::types::{
+    Config, CrateInfo, EnvInfo, ExternalConfig, FlowInfo, FlowState, RunTaskInfo, Step, Task,
+};
 use ci_info;
 use envmnt;
 use fsio;
@@ -12,7 +14,7 @@ use git_info::types::GitInfo;
 use rust_info::types::RustInfo;
 
 fn load_descriptor() -> Config {
-    descriptor::load_internal_descriptors(true, false, None)
+    descriptor::load_internal_descriptors(true, false, None, &vec![]).unwrap()
 }
 
 fn get_task(name: &str, config: &Config) -> Task {
@@ -246,3 +248,18 @@ fn makefile_build_file_increment_panic_invalid_data_test() {
 
     runner::run_task(&flow_info, &mut FlowState::new(), &step);
 }
+
+#[test]
+fn get_preset_valid() {
+    for name in ["rust-quality", "rust-docs"].iter() {
+        let preset = super::get_preset(name).unwrap();
+
+        let config: ExternalConfig = toml::from_str(preset).unwrap();
+        assert!(!config.tasks.unwrap().is_empty());
+    }
+}
+
+#[test]
+fn get_preset_unknown() {
+    assert!(super::get_preset("unknown-preset").is_none());
+}
@@ -10,27 +10,157 @@
 #[path = "./mod_test.rs"]
 mod mod_test;
 
+mod diff;
 mod makefiles;
+mod remote;
 
+pub use self::diff::diff_configs;
+pub use crate::environment::{dump_env, effective_env};
+
+use crate::command;
+use crate::environment;
 use crate::io;
+use crate::profile;
 use crate::scriptengine;
 use crate::types::{
-    Config, ConfigSection, EnvFile, EnvFileInfo, EnvValue, Extend, ExternalConfig, ModifyConfig,
-    ScriptValue, Task,
+    Config, ConfigSection, DeprecationInfo, DescriptorError, EnvFile, EnvFileInfo, EnvScript,
+    EnvValue, EnvValueAppend, EnvValueDocumented, Extend, ExtendMode, ExtendOptions,
+    ExtendRelativeTo, ExternalConfig, LoadOptions, LoadOutput, MergeEvent, MergeSection,
+    ModifyConfig, PlatformOverrideTask, ScriptValue, Task, VersionCheck,
 };
 use crate::version;
 use envmnt;
 use fsio::path::as_path::AsPath;
 use fsio::path::canonicalize_or;
 use fsio::path::from_path::FromPath;
+use glob::{glob, Pattern};
 use indexmap::IndexMap;
+use log::Level;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::Serialize;
+use serde_json;
+use serde_yaml;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use toml;
 
+static STRING_DESCRIPTOR_PATH: &'static str = "<string>";
+static STDIN_DESCRIPTOR_FILE_NAME: &'static str = "-";
+static STDIN_DESCRIPTOR_PATH: &'static str = "<stdin>";
+static DEFAULT_MAX_EXTEND_DEPTH: usize = 50;
+static DEFAULT_TASK_NAME_PATTERN: &'static str = "^[a-z0-9-]+(::[a-z0-9-]+)*$";
+
+// internal descriptors are parsed once and cloned on every load
+static BASE_CONFIG: Lazy<Config> = Lazy::new(|| parse_internal_descriptor(makefiles::BASE, "base"));
+static STABLE_CONFIG: Lazy<Config> =
+    Lazy::new(|| parse_internal_descriptor(makefiles::STABLE, "stable"));
+static EXPERIMENTAL_CONFIG: Lazy<Config> =
+    Lazy::new(|| parse_internal_descriptor(makefiles::BETA, "experimental"));
+
+thread_local! {
+    // merge events are only collected while loading the makefiles via load_with_events
+    static MERGE_EVENTS: RefCell<Option<Vec<MergeEvent>>> = RefCell::new(None);
+}
+
+fn record_merge_event(
+    section: MergeSection,
+    key: &str,
+    previous_source: Option<String>,
+    new_source: Option<String>,
+) {
+    MERGE_EVENTS.with(|events| {
+        if let Some(ref mut events) = *events.borrow_mut() {
+            events.push(MergeEvent {
+                section,
+                key: key.to_string(),
+                previous_source,
+                new_source,
+            });
+        }
+    });
+}
+
+/// Records the env keys of the base env which are overridden by the extended env.
+fn record_env_merge_events(
+    base: &IndexMap<String, EnvValue>,
+    extended: &IndexMap<String, EnvValue>,
+    base_config: Option<&ConfigSection>,
+    extended_config: Option<&ConfigSection>,
+) {
+    for key in extended.keys() {
+        if base.contains_key(key) {
+            record_merge_event(
+                MergeSection::Env,
+                key,
+                base_config.and_then(|config| config.get_env_source(key)),
+                extended_config.and_then(|config| config.get_env_source(key)),
+            );
+        }
+    }
+}
+
 fn merge_env(
     base: &mut IndexMap<String, EnvValue>,
     extended: &mut IndexMap<String, EnvValue>,
+) -> IndexMap<String, EnvValue> {
+    merge_env_with_path_keys(base, extended, &vec![], get_path_separator())
+}
+
+fn get_path_separator() -> &'static str {
+    if cfg!(windows) {
+        ";"
+    } else {
+        ":"
+    }
+}
+
+fn get_path_env_keys(config_section: &Option<ConfigSection>) -> Vec<String> {
+    match config_section {
+        Some(ref config_section) => config_section.path_env_keys.clone().unwrap_or(vec![]),
+        None => vec![],
+    }
+}
+
+/// Returns the empty_env_is_unset value of the extending config section (or the extended config
+/// section if not defined).
+fn get_empty_env_is_unset(
+    config_section: Option<&ConfigSection>,
+    parent_config_section: Option<&ConfigSection>,
+) -> bool {
+    config_section
+        .and_then(|config_section| config_section.empty_env_is_unset)
+        .or_else(|| {
+            parent_config_section.and_then(|config_section| config_section.empty_env_is_unset)
+        })
+        .unwrap_or(false)
+}
+
+/// Merges the env blocks same as merge_env, however values of the provided path keys which are
+/// defined in both blocks are joined (extended value first) using the provided separator.
+fn merge_env_with_path_keys(
+    base: &mut IndexMap<String, EnvValue>,
+    extended: &mut IndexMap<String, EnvValue>,
+    path_env_keys: &Vec<String>,
+    separator: &str,
+) -> IndexMap<String, EnvValue> {
+    merge_env_with_options(base, extended, path_env_keys, separator, false)
+}
+
+/// Merges the env blocks same as merge_env_with_path_keys, however in case empty_is_unset is
+/// true, extended empty values remove the key defined in the base env (including in the profile
+/// env blocks) instead of overriding it with an empty value.
+fn merge_env_with_options(
+    base: &mut IndexMap<String, EnvValue>,
+    extended: &mut IndexMap<String, EnvValue>,
+    path_env_keys: &Vec<String>,
+    separator: &str,
+    empty_is_unset: bool,
 ) -> IndexMap<String, EnvValue> {
     let mut merged = IndexMap::<String, EnvValue>::new();
 
@@ -57,11 +187,68 @@ fn merge_env(
                         let mut base_profile_env_mut = base_profile_env.clone();
                         let mut extended_profile_env_mut = extended_profile_env.clone();
 
-                        let merged_sub_env =
-                            merge_env(&mut base_profile_env_mut, &mut extended_profile_env_mut);
+                        let merged_sub_env = merge_env_with_options(
+                            &mut base_profile_env_mut,
+                            &mut extended_profile_env_mut,
+                            path_env_keys,
+                            separator,
+                            empty_is_unset,
+                        );
 
                         merged.insert(key_str, EnvValue::Profile(merged_sub_env));
                     }
+                    (_, EnvValue::Value(ref extended_string))
+                        if empty_is_unset && extended_string.is_empty() =>
+                    {
+                        // the extended makefile clears the key defined in the base makefile
+                        ()
+                    }
+                    (EnvValue::Value(ref base_string), EnvValue::Value(ref extended_string))
+                        if path_env_keys.contains(&key_str) =>
+                    {
+                        let joined = format!("{}{}{}", extended_string, separator, base_string);
+
+                        merged.insert(key_str, EnvValue::Value(joined));
+                    }
+                    (
+                        EnvValue::Documented(ref base_documented_value),
+                        EnvValue::Value(ref extended_string),
+                    ) => {
+                        // plain values keep the description of the overridden value
+                        merged.insert(
+                            key_str,
+                            EnvValue::Documented(EnvValueDocumented {
+                                value: extended_string.to_string(),
+                                description: base_documented_value.description.clone(),
+                            }),
+                        );
+                    }
+                    (_, EnvValue::Unset(ref unset_info)) if unset_info.unset => {
+                        // the extended makefile removes the key defined in the base makefile
+                        ()
+                    }
+                    (EnvValue::List(ref base_list), EnvValue::Append(ref append_info)) => {
+                        let merged_list = [&base_list[..], &append_info.append[..]].concat();
+
+                        merged.insert(key_str, EnvValue::List(merged_list));
+                    }
+                    (EnvValue::Value(ref base_string), EnvValue::Append(ref append_info)) => {
+                        let merged_list =
+                            [&vec![base_string.to_string()][..], &append_info.append[..]].concat();
+
+                        merged.insert(key_str, EnvValue::List(merged_list));
+                    }
+                    (EnvValue::Append(ref base_append_info), EnvValue::Append(ref append_info)) => {
+                        let merged_list =
+                            [&base_append_info.append[..], &append_info.append[..]].concat();
+
+                        merged.insert(
+                            key_str,
+                            EnvValue::Append(EnvValueAppend {
+                                append: merged_list,
+                            }),
+                        );
+                    }
                     _ => {
                         merged.insert(key_str, value_clone);
                         ()
@@ -76,116 +263,410 @@ fn merge_env(
     merged
 }
 
+/// Returns the strict_env_types value of the extending config section (or the extended config
+/// section if not defined).
+fn get_strict_env_types(
+    config_section: Option<&ConfigSection>,
+    parent_config_section: Option<&ConfigSection>,
+) -> bool {
+    config_section
+        .and_then(|config_section| config_section.strict_env_types)
+        .or_else(|| {
+            parent_config_section.and_then(|config_section| config_section.strict_env_types)
+        })
+        .unwrap_or(false)
+}
+
+/// Returns the env value type used to detect type changes between merged env blocks.<br>
+/// All values which resolve to a single string are of the value type, unset values have no type.
+fn get_env_value_type(value: &EnvValue) -> Option<&'static str> {
+    match value {
+        EnvValue::Unset(ref unset_info) if unset_info.unset => None,
+        EnvValue::List(_) | EnvValue::Append(_) => Some("list"),
+        EnvValue::Profile(_) => Some("profile"),
+        _ => Some("value"),
+    }
+}
+
+/// Validates that the env keys defined in both env blocks keep their value type.<br>
+/// Appending to a plain value and removing a key (unset or empty value in case empty_is_unset
+/// is true) are explicit and therefore allowed. Profile env blocks are validated recursively.
+fn validate_env_types(
+    base: &IndexMap<String, EnvValue>,
+    extended: &IndexMap<String, EnvValue>,
+    empty_is_unset: bool,
+    key_prefix: &str,
+) -> Result<(), DescriptorError> {
+    for (key, extended_value) in extended.iter() {
+        let base_value = match base.get(key) {
+            Some(base_value) => base_value,
+            None => continue,
+        };
+
+        match (base_value, extended_value) {
+            (
+                EnvValue::Profile(ref base_profile_env),
+                EnvValue::Profile(ref extended_profile_env),
+            ) => validate_env_types(
+                base_profile_env,
+                extended_profile_env,
+                empty_is_unset,
+                &format!("{}{}.", key_prefix, key),
+            )?,
+            (_, EnvValue::Value(ref extended_string))
+                if empty_is_unset && extended_string.is_empty() =>
+            {
+                ()
+            }
+            (EnvValue::Value(_), EnvValue::Append(_)) => (),
+            _ => {
+                if let (Some(base_type), Some(extended_type)) = (
+                    get_env_value_type(base_value),
+                    get_env_value_type(extended_value),
+                ) {
+                    if base_type != extended_type {
+                        return Err(DescriptorError::EnvTypeMismatch {
+                            key: format!("{}{}", key_prefix, key),
+                            base_type: base_type.to_string(),
+                            extended_type: extended_type.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn is_final_task(task: Option<&Task>) -> bool {
+    task.and_then(|task| task.is_final).unwrap_or(false)
+}
+
+/// Returns the first task of the extending tasks which overrides a final task of the base tasks.
+fn get_final_task_override(
+    base: Option<&IndexMap<String, Task>>,
+    extended: Option<&IndexMap<String, Task>>,
+) -> Option<String> {
+    match (base, extended) {
+        (Some(base), Some(extended)) => extended
+            .keys()
+            .find(|key| is_final_task(base.get(*key)))
+            .cloned(),
+        _ => None,
+    }
+}
+
+/// Validates the env value types of the two configs (in case strict_env_types is enabled) and
+/// the final tasks overrides (in strict mode) and merges them same as merge_external_configs.
+fn merge_external_configs_checked(
+    config: ExternalConfig,
+    parent_config: ExternalConfig,
+    strict: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    if strict {
+        if let Some(task) =
+            get_final_task_override(parent_config.tasks.as_ref(), config.tasks.as_ref())
+        {
+            return Err(DescriptorError::FinalTaskOverride(task));
+        }
+    }
+
+    if get_strict_env_types(config.config.as_ref(), parent_config.config.as_ref()) {
+        if let (Some(ref parent_env), Some(ref env)) = (&parent_config.env, &config.env) {
+            validate_env_types(
+                parent_env,
+                env,
+                get_empty_env_is_unset(config.config.as_ref(), parent_config.config.as_ref()),
+                "",
+            )?;
+        }
+    }
+
+    Ok(merge_external_configs(config, parent_config))
+}
+
+fn get_env_file_key(env_file: &EnvFile) -> (String, Option<String>) {
+    let (path, base_path, profile) = match env_file {
+        EnvFile::Path(ref path) => (path, None, None),
+        EnvFile::Info(ref info) => (&info.path, info.base_path.clone(), info.profile.clone()),
+    };
+
+    let file_path = if path.starts_with(".") {
+        Path::new(&base_path.unwrap_or(".".to_string())).join(path)
+    } else {
+        PathBuf::from(path)
+    };
+    let file_path_string: String = FromPath::from_path(&file_path);
+
+    (canonicalize_or(&file_path, &file_path_string), profile)
+}
+
 fn merge_env_files(base: &mut Vec<EnvFile>, extended: &mut Vec<EnvFile>) -> Vec<EnvFile> {
-    [&extended[..], &base[..]].concat()
+    let mut merged = vec![];
+    let mut keys = vec![];
+
+    // same files (by resolved path and profile) are loaded once, first occurrence takes precedence
+    for env_file in extended.iter().chain(base.iter()) {
+        let key = get_env_file_key(env_file);
+
+        if !keys.contains(&key) {
+            keys.push(key);
+            merged.push(env_file.clone());
+        }
+    }
+
+    merged
+}
+
+fn merge_env_scripts(
+    base: &mut Vec<EnvScript>,
+    extended: &mut Vec<EnvScript>,
+    dedup: bool,
+) -> Vec<EnvScript> {
+    let mut merged = [&extended[..], &base[..]].concat();
+
+    // stable sort so scripts with the same order (including plain string scripts) keep
+    // the extended scripts first
+    merged.sort_by_key(|env_script| env_script.get_order());
+
+    if dedup {
+        merged = dedup_env_scripts(merged);
+    }
+
+    merged
 }
 
-fn merge_env_scripts(base: &mut Vec<String>, extended: &mut Vec<String>) -> Vec<String> {
-    [&extended[..], &base[..]].concat()
+/// Removes env scripts identical (ignoring surrounding whitespace) to a previous env script
+/// with the same script runner, keeping the first occurrence.
+fn dedup_env_scripts(env_scripts: Vec<EnvScript>) -> Vec<EnvScript> {
+    let mut unique_env_scripts: Vec<EnvScript> = vec![];
+
+    for env_script in env_scripts {
+        let script = env_script.get_script();
+        let duplicate = unique_env_scripts.iter().any(|unique_env_script| {
+            unique_env_script.get_script().trim() == script.trim()
+                && unique_env_script.get_script_runner() == env_script.get_script_runner()
+                && unique_env_script.get_script_runner_args() == env_script.get_script_runner_args()
+        });
+
+        if duplicate {
+            debug!("Skipping duplicate env script: {}", &script);
+        } else {
+            unique_env_scripts.push(env_script);
+        }
+    }
+
+    unique_env_scripts
 }
 
+/// Merges the extended tasks into the base tasks.<br>
+/// Both maps are consumed, so tasks are only cloned in case they are defined in both maps.<br>
+/// The merged order is stable: the base tasks keep their order (also when overridden) and
+/// the tasks introduced by the extended map are appended in their order. Since every extend
+/// is merged this way, core tasks come first followed by the tasks in the order they were
+/// first encountered across the extend chain.
 fn merge_tasks(
-    base: &mut IndexMap<String, Task>,
-    extended: &mut IndexMap<String, Task>,
+    mut base: IndexMap<String, Task>,
+    extended: IndexMap<String, Task>,
     merge_task_env: bool,
 ) -> IndexMap<String, Task> {
-    let mut merged = IndexMap::<String, Task>::new();
-
-    for (key, value) in base.iter() {
-        let key_str = key.to_string();
-        merged.insert(key_str, value.clone());
-    }
+    for (key, task) in extended {
+        if is_final_task(base.get(&key)) {
+            warn!(
+                "Task: {} is final and can not be overridden, ignoring the override.",
+                &key
+            );
+            continue;
+        }
 
-    for (key, value) in extended.iter() {
-        let key_str = key.to_string();
-        let mut task = value.clone();
+        if let Some(value) = base.get(&key) {
+            record_merge_event(
+                MergeSection::Task,
+                &key,
+                value.get_source_file(),
+                task.get_source_file(),
+            );
+        }
 
-        task = match base.get(key) {
-            Some(ref value) => {
+        let task = match base.get(&key) {
+            // cleared tasks replace the base task and do not inherit any of its values
+            Some(_) if task.clear.unwrap_or(false) => task,
+            Some(value) => {
                 let mut merged_task = Task::new();
 
                 merged_task.extend(value);
                 merged_task.extend(&task);
 
+                // extended task env files are loaded before the base task env files
+                if let (Some(ref base_env_files), Some(ref extended_env_files)) =
+                    (&value.env_files, &task.env_files)
+                {
+                    merged_task.env_files = Some(merge_env_files(
+                        &mut base_env_files.clone(),
+                        &mut extended_env_files.clone(),
+                    ));
+                }
+
                 if merge_task_env && value.env.is_some() && task.env.is_some() {
-                    let extended_env = task.env.clone().unwrap();
-                    if extended_env.len() == 2
-                        && extended_env.contains_key("CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE")
-                        && extended_env
-                            .contains_key("CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY")
-                    {
-                        let base_env = value.env.clone().unwrap();
-                        merged_task.env = Some(base_env);
-                    }
+                    let mut base_env = value.env.clone().unwrap();
+                    let mut extended_env = task.env.unwrap();
+
+                    merged_task.env = Some(merge_env(&mut base_env, &mut extended_env));
                 }
 
                 merged_task
             }
-            _ => task,
+            None => task,
         };
 
-        merged.insert(key_str, task);
+        // overridden tasks keep their base position while new tasks are added at the end
+        base.insert(key, task);
     }
 
-    merged
+    base
+}
+
+/// Sets the base path of the env files which do not define one
+fn add_env_files_base_path(env_files: Vec<EnvFile>, base_directory: &str) -> Vec<EnvFile> {
+    let mut modified_env_files = vec![];
+
+    for env_file in env_files {
+        match env_file {
+            EnvFile::Path(path) => {
+                let mut info = EnvFileInfo::new(path);
+                info.base_path = Some(base_directory.to_string());
+
+                modified_env_files.push(EnvFile::Info(info));
+            }
+            EnvFile::Info(mut info) => {
+                if info.base_path.is_none() {
+                    info.base_path = Some(base_directory.to_string());
+                }
+
+                modified_env_files.push(EnvFile::Info(info));
+            }
+        }
+    }
+
+    modified_env_files
+}
+
+/// Sets the base path of the file env values which do not define one
+fn add_env_values_base_path(env: &mut IndexMap<String, EnvValue>, base_directory: &str) {
+    for (_, value) in env.iter_mut() {
+        match value {
+            EnvValue::File(ref mut file) => {
+                if file.base_path.is_none() {
+                    file.base_path = Some(base_directory.to_string());
+                }
+            }
+            EnvValue::Profile(ref mut sub_env) => add_env_values_base_path(sub_env, base_directory),
+            _ => (),
+        }
+    }
+}
+
+/// Records the provided source for all the fields defined in the config section.
+fn set_config_field_sources(config_section: &mut ConfigSection, source: &str) {
+    if let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(&*config_section) {
+        for (field, value) in fields.iter() {
+            if !value.is_null() {
+                config_section.set_field_source(field, source);
+            }
+        }
+    }
+}
+
+/// Appends the effective (merged) config env_file to the env_files list.
+fn add_config_env_file(external_config: &mut ExternalConfig) {
+    let (env_file, env_file_directory) = match external_config.config {
+        Some(ref config_section) => (
+            config_section.env_file.clone(),
+            config_section.env_file_directory.clone(),
+        ),
+        None => (None, None),
+    };
+
+    if let Some(env_file) = env_file {
+        let mut info = EnvFileInfo::new(env_file);
+        info.base_path = env_file_directory;
+
+        external_config
+            .env_files
+            .get_or_insert_with(Vec::new)
+            .push(EnvFile::Info(info));
+    }
+}
+
+/// Returns the directory from which the descriptor relative paths are resolved.<br>
+/// Makefiles loaded from a string have no location, so the provided parent path is used.
+fn get_descriptor_directory(descriptor_path: &str, parent_path: &str) -> String {
+    if descriptor_path == STRING_DESCRIPTOR_PATH {
+        return parent_path.to_string();
+    }
+
+    match descriptor_path.as_path().parent() {
+        Some(directory) => FromPath::from_path(directory),
+        None => "".to_string(),
+    }
 }
 
 fn add_file_location_info(
     mut external_config: ExternalConfig,
     file_path_string: &str,
+    base_directory: &str,
 ) -> ExternalConfig {
-    let file_path = file_path_string.as_path();
-    let base_directory = match file_path.parent() {
-        Some(directory) => FromPath::from_path(directory),
-        None => "".to_string(),
-    };
+    // the env_file is only added once the config sections are merged
+    if let Some(ref mut config_section) = external_config.config {
+        if config_section.env_file.is_some() {
+            config_section.env_file_directory = Some(base_directory.to_string());
+        }
+    }
 
     match external_config.env_files {
         Some(env_files) => {
-            let mut modified_env_files = vec![];
-
-            for env_file in env_files {
-                match env_file {
-                    EnvFile::Path(path) => {
-                        let mut info = EnvFileInfo::new(path);
-                        info.base_path = Some(base_directory.clone());
+            external_config.env_files = Some(add_env_files_base_path(env_files, &base_directory));
+        }
+        None => (),
+    };
 
-                        modified_env_files.push(EnvFile::Info(info));
-                    }
-                    EnvFile::Info(mut info) => {
-                        if info.base_path.is_none() {
-                            info.base_path = Some(base_directory.clone());
-                        }
+    if let Some(ref mut env_blocks) = external_config.env_blocks {
+        for env in env_blocks.values_mut() {
+            add_env_values_base_path(env, &base_directory);
+        }
+    }
 
-                        modified_env_files.push(EnvFile::Info(info));
-                    }
-                }
-            }
+    if let Some(ref mut env) = external_config.env {
+        add_env_values_base_path(env, &base_directory);
 
-            external_config.env_files = Some(modified_env_files);
+        let config_section = external_config
+            .config
+            .get_or_insert_with(ConfigSection::new);
+        for key in env.keys() {
+            config_section.set_env_source(key, file_path_string);
         }
-        None => (),
-    };
+    }
+
+    if let Some(ref mut config_section) = external_config.config {
+        set_config_field_sources(config_section, file_path_string);
+    }
 
     let mut tasks_map = IndexMap::new();
     if let Some(tasks) = external_config.tasks.clone() {
         for (task_name, task) in tasks {
-            let mut env = match task.env.clone() {
-                Some(env) => env,
-                None => IndexMap::new(),
-            };
-
-            env.insert(
-                "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE".to_string(),
-                EnvValue::Value(file_path_string.to_string()),
-            );
-            env.insert(
-                "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY".to_string(),
-                EnvValue::Value(base_directory.to_string()),
-            );
-
             let mut updated_task = task.clone();
-            updated_task.env = Some(env);
+            updated_task.initial_makefile = Some(file_path_string.to_string());
+            updated_task.initial_makefile_dir = Some(base_directory.to_string());
+            updated_task.set_source_file(file_path_string);
+
+            // task env files are relative to the makefile which defined them
+            if let Some(env_files) = task.env_files.clone() {
+                updated_task.env_files = Some(add_env_files_base_path(env_files, &base_directory));
+            }
+            if let Some(ref mut env) = updated_task.env {
+                add_env_values_base_path(env, &base_directory);
+            }
             tasks_map.insert(task_name, updated_task);
         }
 
@@ -195,6 +676,80 @@ fn add_file_location_info(
     external_config
 }
 
+/// Runs the load script (as an OS script) and merges its output (a makefile) into the provided
+/// config. In case the script has no output, the config is returned as is.
+fn merge_load_script_output(
+    external_config: ExternalConfig,
+    descriptor_path: &str,
+    base_directory: &str,
+) -> Result<ExternalConfig, DescriptorError> {
+    let load_script = match external_config.config {
+        Some(ref config) => config.get_load_script(),
+        None => None,
+    };
+
+    let script = match load_script {
+        Some(script) => script,
+        None => {
+            debug!("No load script defined.");
+            return Ok(external_config);
+        }
+    };
+
+    let output = match command::run_script_get_output(&script, None, &vec![], true, Some(false)) {
+        Ok((0, output, _)) => output,
+        Ok((code, _, error)) => {
+            return Err(DescriptorError::LoadScript(format!(
+                "Load script of descriptor: {} failed with exit code: {}, {}",
+                &descriptor_path, code, error
+            )))
+        }
+        Err(error) => {
+            return Err(DescriptorError::LoadScript(format!(
+                "Unable to run load script of descriptor: {}, {}",
+                &descriptor_path, error
+            )))
+        }
+    };
+
+    if output.trim().is_empty() {
+        return Ok(external_config);
+    }
+
+    // the output is parsed with the same format as the owning descriptor
+    let output_path = format!("{} (load script output)", &descriptor_path);
+    let mut script_config: ExternalConfig = if is_yaml_descriptor(descriptor_path) {
+        match serde_yaml::from_str(&output) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(DescriptorError::ParseYaml {
+                    file: output_path,
+                    message: error.to_string(),
+                });
+            }
+        }
+    } else {
+        match toml::from_str(&output) {
+            Ok(value) => value,
+            Err(error) => {
+                return Err(DescriptorError::Parse {
+                    file: output_path,
+                    source: error,
+                });
+            }
+        }
+    };
+    debug!("Loaded load script config: {:#?}", &script_config);
+
+    script_config = add_file_location_info(script_config, &descriptor_path, base_directory);
+
+    let extend = external_config.extend.clone();
+    let mut merged_config = merge_external_configs(script_config, external_config);
+    merged_config.extend = extend;
+
+    Ok(merged_config)
+}
+
 fn run_load_script(external_config: &ExternalConfig) -> bool {
     match external_config.config {
         Some(ref config) => {
@@ -228,7 +783,83 @@ fn run_load_script(external_config: &ExternalConfig) -> bool {
     }
 }
 
+fn get_skip_core_tasks(external_config: &ExternalConfig) -> Option<bool> {
+    match external_config.config {
+        Some(ref config) => config.skip_core_tasks,
+        None => None,
+    }
+}
+
+/// Merges the extended env blocks into the base env blocks.<br>
+/// Env blocks defined in both are merged, in which case the extended env block values win.
+fn merge_env_blocks(
+    base: Option<IndexMap<String, IndexMap<String, EnvValue>>>,
+    extended: Option<IndexMap<String, IndexMap<String, EnvValue>>>,
+) -> Option<IndexMap<String, IndexMap<String, EnvValue>>> {
+    match (base, extended) {
+        (Some(mut base_env_blocks), Some(extended_env_blocks)) => {
+            for (name, mut extended_env) in extended_env_blocks {
+                let env = match base_env_blocks.get_mut(&name) {
+                    Some(base_env) => merge_env(base_env, &mut extended_env),
+                    None => extended_env,
+                };
+
+                base_env_blocks.insert(name, env);
+            }
+
+            Some(base_env_blocks)
+        }
+        (None, extended) => extended,
+        (base, None) => base,
+    }
+}
+
+/// Merges the env block referenced by each task into the task env (the task env values win).
+fn resolve_task_env_blocks(external_config: &mut ExternalConfig) -> Result<(), DescriptorError> {
+    let tasks = match external_config.tasks {
+        Some(ref mut tasks) => tasks,
+        None => return Ok(()),
+    };
+
+    for (task_name, task) in tasks.iter_mut() {
+        if let Some(ref env_block) = task.env_block {
+            let block_env = match external_config.env_blocks {
+                Some(ref env_blocks) => env_blocks.get(env_block),
+                None => None,
+            };
+
+            match block_env {
+                Some(block_env) => {
+                    let mut env = block_env.clone();
+                    let mut task_env = task.env.take().unwrap_or_else(IndexMap::new);
+
+                    task.env = Some(merge_env(&mut env, &mut task_env));
+                }
+                None => {
+                    return Err(DescriptorError::EnvBlockNotFound {
+                        task: task_name.to_string(),
+                        block: env_block.to_string(),
+                    })
+                }
+            };
+        }
+    }
+
+    Ok(())
+}
+
 fn merge_external_configs(config: ExternalConfig, parent_config: ExternalConfig) -> ExternalConfig {
+    let parent_skip_core_tasks = get_skip_core_tasks(&parent_config);
+    let skip_core_tasks = get_skip_core_tasks(&config);
+    if let (Some(parent_value), Some(value)) = (parent_skip_core_tasks, skip_core_tasks) {
+        if parent_value != value {
+            warn!(
+                "Conflicting skip_core_tasks values found in extended makefiles, using: {}",
+                value
+            );
+        }
+    }
+
     // merge env files
     let mut parent_env_files = match parent_config.env_files {
         Some(env_files) => env_files,
@@ -249,7 +880,23 @@ fn merge_external_configs(config: ExternalConfig, parent_config: ExternalConfig)
         Some(env) => env,
         None => IndexMap::new(),
     };
-    let all_env = merge_env(&mut parent_env, &mut extended_env);
+    record_env_merge_events(
+        &parent_env,
+        &extended_env,
+        parent_config.config.as_ref(),
+        config.config.as_ref(),
+    );
+    let mut path_env_keys = get_path_env_keys(&config.config);
+    if path_env_keys.is_empty() {
+        path_env_keys = get_path_env_keys(&parent_config.config);
+    }
+    let all_env = merge_env_with_options(
+        &mut parent_env,
+        &mut extended_env,
+        &path_env_keys,
+        get_path_separator(),
+        get_empty_env_is_unset(config.config.as_ref(), parent_config.config.as_ref()),
+    );
 
     // merge env scripts
     let mut parent_env_scripts = match parent_config.env_scripts {
@@ -260,18 +907,16 @@ fn merge_external_configs(config: ExternalConfig, parent_config: ExternalConfig)
         Some(env_scripts) => env_scripts,
         None => vec![],
     };
-    let all_env_scripts = merge_env_scripts(&mut parent_env_scripts, &mut extended_env_scripts);
 
     // merge tasks
-    let mut parent_tasks = match parent_config.tasks {
+    let parent_tasks = match parent_config.tasks {
         Some(tasks) => tasks,
         None => IndexMap::new(),
     };
-    let mut extended_tasks = match config.tasks {
+    let extended_tasks = match config.tasks {
         Some(tasks) => tasks,
         None => IndexMap::new(),
     };
-    let all_tasks = merge_tasks(&mut parent_tasks, &mut extended_tasks, false);
 
     let mut config_section = ConfigSection::new();
     if parent_config.config.is_some() {
@@ -285,328 +930,2132 @@ fn merge_external_configs(config: ExternalConfig, parent_config: ExternalConfig)
         config_section.extend(&mut config_section_data);
     }
 
+    let dedup_env_scripts = config_section.dedup_env_scripts.unwrap_or(false);
+    let all_env_scripts = merge_env_scripts(
+        &mut parent_env_scripts,
+        &mut extended_env_scripts,
+        dedup_env_scripts,
+    );
+
+    let all_env_blocks = merge_env_blocks(parent_config.env_blocks, config.env_blocks);
+
+    let merge_task_env = config_section.merge_task_env.unwrap_or(false);
+    let all_tasks = merge_tasks(parent_tasks, extended_tasks, merge_task_env);
+
     ExternalConfig {
         extend: None,
         config: Some(config_section),
         env_files: Some(all_env_files),
         env: Some(all_env),
         env_scripts: Some(all_env_scripts),
+        env_blocks: all_env_blocks,
         tasks: Some(all_tasks),
     }
 }
 
-fn load_descriptor_extended_makefiles(
-    parent_path: &str,
-    extend_struct: &Extend,
-) -> Result<ExternalConfig, String> {
-    match extend_struct {
-        Extend::Path(base_file) => load_external_descriptor(parent_path, &base_file, true, false),
-        Extend::Options(extend_options) => {
-            let force = !extend_options.optional.unwrap_or(false);
-            load_external_descriptor(parent_path, &extend_options.path, force, false)
-        }
-        Extend::List(extend_list) => {
-            let mut ordered_list_config = ExternalConfig::new();
+/// Merges the overlay external config into the base external config and returns the merged
+/// config, same as when a makefile extends another makefile (the overlay being the extending
+/// makefile).<br>
+/// The overlay values take precedence over the base values:
+///
+/// * **env** - overlay env vars replace base env vars with the same key, profile env blocks are merged per key
+/// * **env_files** - overlay env files are loaded first, followed by the base env files (duplicates are removed)
+/// * **env_scripts** - overlay env scripts are invoked first, followed by the base env scripts (both sorted by their order)
+/// * **tasks** - tasks with the same name are merged attribute by attribute, unless the overlay task is cleared in which case it replaces the base task
+/// * **config** - every attribute defined in the overlay config section replaces the base value
+///
+/// The extend attribute of both configs is not followed and is not part of the merged config.
+pub fn merge(base: ExternalConfig, overlay: ExternalConfig) -> ExternalConfig {
+    merge_external_configs(overlay, base)
+}
 
-            for entry in extend_list.iter() {
-                let extend_options = entry.clone();
-                let entry_config = load_descriptor_extended_makefiles(
-                    parent_path,
-                    &Extend::Options(extend_options),
-                )?;
+fn is_glob_pattern(path: &str) -> bool {
+    path.contains('*') || path.contains('?') || path.contains('[')
+}
 
-                // merge configs
-                ordered_list_config = merge_external_configs(entry_config, ordered_list_config);
-            }
+/// Returns the log level of the message printed when an optional extended makefile is not found
+/// (None in case the extended makefile is not skipped).<br>
+/// The message is printed as info in case the CARGO_MAKE_REPORT_MISSING_OPTIONAL_EXTEND env var is
+/// set to true, otherwise it is only printed in debug mode.
+fn get_skipped_extend_log_level(force: bool, file_exists: bool) -> Option<Level> {
+    if force || file_exists {
+        None
+    } else if envmnt::is("CARGO_MAKE_REPORT_MISSING_OPTIONAL_EXTEND") {
+        Some(Level::Info)
+    } else {
+        Some(Level::Debug)
+    }
+}
 
-            Ok(ordered_list_config)
+/// Returns the directory of the (first) workspace makefile or the current working directory in
+/// case no workspace makefile is defined.
+fn get_workspace_makefile_directory() -> String {
+    let workspace_makefiles = envmnt::get_or("CARGO_MAKE_WORKSPACE_MAKEFILE", "");
+
+    match env::split_paths(&workspace_makefiles).next() {
+        Some(ref file_path) if !workspace_makefiles.is_empty() => match file_path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => FromPath::from_path(directory),
+            _ => ".".to_string(),
+        },
+        _ => {
+            debug!("Workspace makefile not defined, resolving extend from current directory.");
+            ".".to_string()
         }
     }
 }
 
-/// Ensure the Makefile's min_version, if present, is older than cargo-make's
-/// currently running version.
-fn check_makefile_min_version(external_descriptor: &str) -> Result<(), String> {
-    let value: toml::Value = match toml::from_str(&external_descriptor) {
-        Ok(value) => value,
-        // If there's an error parsing the file, let the caller function figure
-        // it out
-        Err(_) => return Ok(()),
+/// Returns the directory from which the extend path is resolved and the extend path.<br>
+/// Paths resolved from the workspace or current working directory may start with a path
+/// separator, which is removed (the path is still relative to the selected directory).
+fn get_extend_location(
+    parent_path: &str,
+    path: &str,
+    relative_to: Option<ExtendRelativeTo>,
+) -> (String, String) {
+    let base_path = match relative_to {
+        Some(ExtendRelativeTo::Workspace) => get_workspace_makefile_directory(),
+        Some(ExtendRelativeTo::Cwd) => ".".to_string(),
+        Some(ExtendRelativeTo::File) | None => return (parent_path.to_string(), path.to_string()),
     };
 
-    let min_version = value
-        .get("config")
-        .and_then(|config| config.get("min_version"))
-        .and_then(|min_ver| min_ver.as_str());
-
-    if let Some(ref min_version) = min_version {
-        if version::is_newer_found(&min_version) {
-            return Err(min_version.to_string());
-        }
-    }
+    let relative_path = path.trim_start_matches(|character| character == '/' || character == '\\');
 
-    Ok(())
+    (base_path, relative_path.to_string())
 }
 
-fn load_external_descriptor(
-    base_path: &str,
-    file_name: &str,
+fn load_descriptor_extended_makefile(
+    parent_path: &str,
+    path: &str,
     force: bool,
-    set_env: bool,
-) -> Result<ExternalConfig, String> {
-    debug!(
-        "Loading tasks from file: {} base directory: {}",
-        &file_name, &base_path
-    );
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    profile: &str,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    let expanded_path = environment::expand_value(path);
+    let path = if expanded_path.contains("${") {
+        if force {
+            return Err(DescriptorError::UnresolvedExtendPath(path.to_string()));
+        }
 
-    let file_path = Path::new(base_path).join(file_name);
+        debug!("Unable to resolve extend path: {}", &path);
+        path
+    } else {
+        &expanded_path
+    };
 
-    if file_path.exists() && file_path.is_file() {
-        let file_path_string: String = FromPath::from_path(&file_path);
-        let absolute_file_path = canonicalize_or(&file_path, &file_path_string);
+    let is_remote = remote::get_url(parent_path, path).is_some();
+    if !is_glob_pattern(path) || is_remote {
+        let file_exists = is_remote || Path::new(parent_path).join(path).is_file();
+        if let Some(level) = get_skipped_extend_log_level(force, file_exists) {
+            log!(
+                level,
+                "Optional extended makefile: {} not found, skipping.",
+                &path
+            );
 
-        if set_env {
-            envmnt::set("CARGO_MAKE_MAKEFILE_PATH", &absolute_file_path);
+            return Ok(ExternalConfig::new());
         }
 
-        let external_descriptor = io::read_text_file(&file_path);
+        return load_external_descriptor(
+            parent_path,
+            path,
+            force,
+            false,
+            extend_chain,
+            strict,
+            run_scripts,
+            Some(profile),
+            load_trace,
+        );
+    }
+
+    let pattern_path = Path::new(parent_path).join(path);
+    let pattern: String = FromPath::from_path(&pattern_path);
+    debug!("Searching for extended makefiles using glob: {}", &pattern);
+
+    let mut file_paths: Vec<PathBuf> = match glob(&pattern) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.is_file())
+            .collect(),
+        Err(error) => {
+            return Err(DescriptorError::ExtendPattern(format!(
+                "Invalid extend glob pattern: {}, {}",
+                &pattern, error
+            )))
+        }
+    };
+    file_paths.sort();
+
+    if file_paths.is_empty() {
+        if force {
+            return Err(DescriptorError::ExtendPattern(format!(
+                "Extend glob pattern: {} did not match any file.",
+                &pattern
+            )));
+        }
 
-        check_makefile_min_version(&external_descriptor)?;
+        debug!("Extend glob pattern did not match any file, skipping.");
+    }
 
-        let mut file_config: ExternalConfig = match toml::from_str(&external_descriptor) {
-            Ok(value) => value,
-            Err(error) => panic!("Unable to parse external descriptor, {}", error),
+    let mut ordered_list_config = ExternalConfig::new();
+    let mut min_version_error = None;
+
+    for file_path in file_paths {
+        let file_path_string: String = FromPath::from_path(&file_path);
+        let entry_config = match load_external_descriptor(
+            ".",
+            &file_path_string,
+            true,
+            false,
+            extend_chain,
+            strict,
+            run_scripts,
+            Some(profile),
+            load_trace,
+        ) {
+            Ok(entry_config) => entry_config,
+            Err(error) => {
+                collect_min_version_error(&mut min_version_error, error)?;
+                continue;
+            }
         };
-        debug!("Loaded external config: {:#?}", &file_config);
 
-        file_config = add_file_location_info(file_config, &absolute_file_path);
+        // merge configs
+        ordered_list_config =
+            merge_external_configs_checked(entry_config, ordered_list_config, strict)?;
+    }
 
-        run_load_script(&file_config);
+    match min_version_error {
+        Some(error) => Err(error),
+        None => Ok(ordered_list_config),
+    }
+}
 
-        match file_config.extend {
-            Some(ref extend_struct) => {
-                let parent_path_buf = Path::new(base_path).join(file_name).join("..");
-                let parent_path = file_path
-                    .parent()
-                    .unwrap_or(&parent_path_buf)
-                    .to_str()
-                    .unwrap_or(".");
-                debug!("External config parent path: {}", &parent_path);
+/// Removes the sections which should not be imported from the extended config.
+fn remove_extended_sections(extended_config: &mut ExternalConfig, extend_options: &ExtendOptions) {
+    if !extend_options.import_env.unwrap_or(true) {
+        extended_config.env = None;
+    }
 
-                let base_file_config =
-                    load_descriptor_extended_makefiles(&parent_path, extend_struct)?;
+    if !extend_options.import_config.unwrap_or(true) {
+        // the config section also holds the env sources which are still required
+        extended_config.config = match extended_config.config {
+            Some(ref config_section) => {
+                let mut env_config_section = ConfigSection::new();
+                if let Some(ref env) = extended_config.env {
+                    for key in env.keys() {
+                        if let Some(source) = config_section.get_env_source(key) {
+                            env_config_section.set_env_source(key, &source);
+                        }
+                    }
+                }
 
-                Ok(merge_external_configs(
-                    file_config.clone(),
-                    base_file_config,
-                ))
+                Some(env_config_section)
             }
-            None => Ok(file_config),
-        }
-    } else if force {
-        error!("Descriptor file: {:#?} not found.", &file_path);
-        panic!("Descriptor file: {:#?} not found.", &file_path);
-    } else {
-        debug!("External file not found or is not a file, skipping.");
+            None => None,
+        };
+    }
 
-        Ok(ExternalConfig::new())
+    if !extend_options.import_env_files.unwrap_or(true) {
+        extended_config.env_files = None;
+    }
+
+    if !extend_options.import_env_scripts.unwrap_or(true) {
+        extended_config.env_scripts = None;
     }
 }
 
-pub(crate) fn load_internal_descriptors(
-    stable: bool,
-    experimental: bool,
-    modify_config: Option<ModifyConfig>,
-) -> Config {
-    debug!("Loading base tasks.");
+/// Keeps only the requested tasks (and optionally their dependencies) from the extended config.
+fn filter_extended_tasks(
+    mut external_config: ExternalConfig,
+    path: &str,
+    task_names: &Vec<String>,
+    include_dependencies: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    let mut tasks = external_config.tasks.unwrap_or(IndexMap::new());
+
+    for task_name in task_names {
+        if !tasks.contains_key(task_name) {
+            return Err(DescriptorError::ExtendTaskNotFound {
+                path: path.to_string(),
+                task: task_name.to_string(),
+            });
+        }
+    }
 
-    let base_descriptor = if stable {
-        makefiles::STABLE
-    } else {
-        makefiles::BASE
-    };
+    let mut selected_task_names = task_names.clone();
+    if include_dependencies {
+        let mut index = 0;
+        while index < selected_task_names.len() {
+            if let Some(task) = tasks.get(&selected_task_names[index]) {
+                let mut references = get_task_references(task);
+                if let Some(ref extend) = task.extend {
+                    references.push(extend.to_string());
+                }
 
-    let mut base_config: Config = match toml::from_str(base_descriptor) {
-        Ok(value) => value,
-        Err(error) => panic!("Unable to parse base descriptor, {}", error),
-    };
-    debug!("Loaded base config: {:#?}", &base_config);
+                for reference in references {
+                    if tasks.contains_key(&reference) && !selected_task_names.contains(&reference) {
+                        selected_task_names.push(reference);
+                    }
+                }
+            }
 
-    if experimental {
-        debug!("Loading experimental tasks.");
-        let experimental_descriptor = makefiles::BETA;
+            index = index + 1;
+        }
+    }
 
-        let experimental_config: Config = match toml::from_str(experimental_descriptor) {
-            Ok(value) => value,
-            Err(error) => panic!("Unable to parse experimental descriptor, {}", error),
-        };
-        debug!("Loaded experimental config: {:#?}", &experimental_config);
+    tasks.retain(|name, _| selected_task_names.contains(name));
+    external_config.tasks = Some(tasks);
 
-        let mut base_tasks = base_config.tasks;
-        let mut experimental_tasks = experimental_config.tasks;
-        let all_tasks = merge_tasks(&mut base_tasks, &mut experimental_tasks, false);
+    Ok(external_config)
+}
 
-        base_config.tasks = all_tasks;
+/// Parses the built-in preset makefile with the provided name.<br>
+/// Presets go through the same validations as any other makefile (min version, strict mode).<br>
+/// Preset tasks are not bound to any makefile location, same as the internal core tasks.
+fn load_preset_descriptor(name: &str, strict: bool) -> Result<ExternalConfig, DescriptorError> {
+    debug!("Loading extend preset: {}", name);
+
+    match makefiles::get_preset(name) {
+        Some(preset_descriptor) => {
+            deserialize_toml_descriptor(preset_descriptor, &format!("preset: {}", name), strict)
+        }
+        None => Err(DescriptorError::UnknownPreset(name.to_string())),
     }
+}
 
-    // reset
-    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE", "");
-    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX", "");
+fn load_descriptor_extended_makefiles(
+    parent_path: &str,
+    extend_struct: &Extend,
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    profile: &str,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    let (base_config, overlay_config) = load_descriptor_extended_makefiles_by_mode(
+        parent_path,
+        extend_struct,
+        extend_chain,
+        strict,
+        run_scripts,
+        profile,
+        load_trace,
+    )?;
+
+    match overlay_config {
+        Some(overlay_config) => merge_external_configs_checked(overlay_config, base_config, strict),
+        None => Ok(base_config),
+    }
+}
 
-    match modify_config {
-        Some(props) => {
-            base_config.apply(&props);
+/// Loads the extended makefiles and returns them split by their extend mode.<br>
+/// The first value holds the makefiles the current makefile overrides and the second value
+/// holds the makefiles (if any) which override the current makefile.
+fn load_descriptor_extended_makefiles_by_mode(
+    parent_path: &str,
+    extend_struct: &Extend,
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    profile: &str,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<(ExternalConfig, Option<ExternalConfig>), DescriptorError> {
+    match extend_struct {
+        Extend::Path(base_file) => {
+            let base_config = load_descriptor_extended_makefile(
+                parent_path,
+                &base_file,
+                true,
+                extend_chain,
+                strict,
+                run_scripts,
+                profile,
+                load_trace,
+            )?;
+
+            Ok((base_config, None))
+        }
+        Extend::Options(extend_options) => {
+            let force = !extend_options.optional.unwrap_or(false);
+            let extend_name = match extend_options.preset {
+                Some(ref preset) => format!("preset: {}", preset),
+                None => extend_options.path.to_string(),
+            };
 
-            match props.namespace {
-                Some(ref namespace) => {
-                    let prefix = props.get_namespace_prefix();
+            if let Some(ref profiles) = extend_options.profiles {
+                let profile_name = profile.to_string();
+
+                if !profiles.contains(&profile_name) {
+                    if force {
+                        info!(
+                            "Skipping extend: {}, profile: {} is not one of: {}",
+                            &extend_name,
+                            &profile_name,
+                            profiles.join(", ")
+                        );
+                    } else {
+                        debug!(
+                            "Skipping optional extend: {}, profile: {} is not one of: {}",
+                            &extend_name,
+                            &profile_name,
+                            profiles.join(", ")
+                        );
+                    }
 
-                    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE", &namespace);
-                    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX", &prefix);
+                    return Ok((ExternalConfig::new(), None));
+                }
+            }
+
+            let extended_config = match extend_options.preset {
+                Some(ref preset) => load_preset_descriptor(preset, strict)?,
+                None => {
+                    let (extend_parent_path, extend_path) = get_extend_location(
+                        parent_path,
+                        &extend_options.path,
+                        extend_options.relative_to,
+                    );
+
+                    load_descriptor_extended_makefile(
+                        &extend_parent_path,
+                        &extend_path,
+                        force,
+                        extend_chain,
+                        strict,
+                        run_scripts,
+                        profile,
+                        load_trace,
+                    )?
                 }
-                None => (),
             };
+
+            let mut extended_config = match extend_options.tasks {
+                // optional extended makefile was not found
+                Some(_) if !force && extended_config.tasks.is_none() => extended_config,
+                Some(ref task_names) => filter_extended_tasks(
+                    extended_config,
+                    &extend_name,
+                    task_names,
+                    extend_options.include_dependencies.unwrap_or(false),
+                )?,
+                None => extended_config,
+            };
+
+            remove_extended_sections(&mut extended_config, extend_options);
+
+            // record the decision in the config section, core tasks are only merged
+            // at the end of the loading process
+            if extend_options.skip_core_tasks.is_some() {
+                let mut config_section = extended_config
+                    .config
+                    .unwrap_or_else(|| ConfigSection::new());
+                config_section.skip_core_tasks = extend_options.skip_core_tasks;
+                extended_config.config = Some(config_section);
+            }
+
+            match extend_options.mode {
+                Some(ExtendMode::Overlay) => Ok((ExternalConfig::new(), Some(extended_config))),
+                _ => Ok((extended_config, None)),
+            }
         }
-        None => (),
-    };
+        Extend::List(extend_list) => {
+            let mut ordered_list_config = ExternalConfig::new();
+            let mut ordered_overlay_config: Option<ExternalConfig> = None;
+            let mut min_version_error = None;
+
+            for entry in extend_list.iter() {
+                let extend_options = entry.clone();
+                let (entry_config, entry_overlay_config) =
+                    match load_descriptor_extended_makefiles_by_mode(
+                        parent_path,
+                        &Extend::Options(extend_options),
+                        extend_chain,
+                        strict,
+                        run_scripts,
+                        profile,
+                        load_trace,
+                    ) {
+                        Ok(entry_configs) => entry_configs,
+                        Err(error) => {
+                            collect_min_version_error(&mut min_version_error, error)?;
+                            continue;
+                        }
+                    };
+
+                // merge configs (later entries override earlier entries)
+                ordered_list_config =
+                    merge_external_configs_checked(entry_config, ordered_list_config, strict)?;
+                if let Some(entry_overlay_config) = entry_overlay_config {
+                    ordered_overlay_config = Some(match ordered_overlay_config {
+                        Some(overlay_config) => merge_external_configs_checked(
+                            entry_overlay_config,
+                            overlay_config,
+                            strict,
+                        )?,
+                        None => entry_overlay_config,
+                    });
+                }
+            }
 
-    base_config
+            match min_version_error {
+                Some(error) => Err(error),
+                None => Ok((ordered_list_config, ordered_overlay_config)),
+            }
+        }
+    }
 }
 
-fn merge_base_config_and_external_config(
-    base_config: Config,
-    external_config: ExternalConfig,
-    env_map: Option<Vec<String>>,
-    late_merge: bool,
-) -> Config {
-    let mut external_tasks = match external_config.tasks {
-        Some(tasks) => tasks,
-        None => IndexMap::new(),
-    };
-    let mut base_tasks = base_config.tasks;
+fn get_known_keys<T: Serialize>(value: &T) -> Vec<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::Object(map)) => map.keys().map(|key| key.to_string()).collect(),
+        _ => vec![],
+    }
+}
 
-    let env_files = match external_config.env_files {
-        Some(env_files) => env_files,
-        None => vec![],
-    };
+fn add_unknown_keys(
+    value: &toml::Value,
+    known_keys: &Vec<String>,
+    location: &str,
+    unknown_keys: &mut Vec<String>,
+) {
+    if let Some(table) = value.as_table() {
+        for key in table.keys() {
+            if !known_keys.contains(key) {
+                if location.is_empty() {
+                    unknown_keys.push(key.to_string());
+                } else {
+                    unknown_keys.push(format!("{}.{}", location, key));
+                }
+            }
+        }
+    }
+}
 
-    let env_scripts = match external_config.env_scripts {
-        Some(env_scripts) => env_scripts,
-        None => vec![],
+/// Ensure the Makefile does not contain any key which is not supported by cargo-make
+/// in the root, config section, tasks and platform overrides.
+fn validate_known_keys(external_descriptor: &str, file_path: &str) -> Result<(), DescriptorError> {
+    let value: toml::Value = match toml::from_str(&external_descriptor) {
+        Ok(value) => value,
+        // parsing errors are handled by the caller function
+        Err(_) => return Ok(()),
     };
 
-    let mut external_env = match external_config.env {
-        Some(env) => env,
-        None => IndexMap::new(),
-    };
-    let mut base_env = base_config.env;
+    validate_known_keys_value(&value, file_path)
+}
 
-    // merge env
-    let mut all_env = merge_env(&mut base_env, &mut external_env);
-    all_env = match env_map {
-        Some(values) => {
-            let mut cli_env = IndexMap::new();
+fn validate_known_keys_value(value: &toml::Value, file_path: &str) -> Result<(), DescriptorError> {
+    let mut unknown_keys = vec![];
 
-            for env_pair in &values {
-                let env_part: Vec<&str> = env_pair.split('=').collect();
-                debug!("Checking env pair: {}", &env_pair);
+    add_unknown_keys(
+        value,
+        &get_known_keys(&ExternalConfig::new()),
+        "",
+        &mut unknown_keys,
+    );
 
-                if env_part.len() == 2 {
-                    cli_env.insert(
-                        env_part[0].to_string(),
-                        EnvValue::Value(env_part[1].to_string()),
+    if let Some(config) = value.get("config") {
+        add_unknown_keys(
+            config,
+            &get_known_keys(&ConfigSection::new()),
+            "config",
+            &mut unknown_keys,
+        );
+    }
+
+    if let Some(tasks) = value.get("tasks").and_then(|tasks| tasks.as_table()) {
+        let task_keys = get_known_keys(&Task::new());
+        let platform_task_keys = get_known_keys(&PlatformOverrideTask::default());
+
+        for (task_name, task) in tasks {
+            let location = format!("tasks.{}", task_name);
+            add_unknown_keys(task, &task_keys, &location, &mut unknown_keys);
+
+            for platform_name in &["linux", "windows", "mac"] {
+                if let Some(platform_task) = task.get(platform_name) {
+                    add_unknown_keys(
+                        platform_task,
+                        &platform_task_keys,
+                        &format!("{}.{}", location, platform_name),
+                        &mut unknown_keys,
                     );
                 }
             }
+        }
+    }
+
+    if unknown_keys.is_empty() {
+        Ok(())
+    } else {
+        Err(DescriptorError::UnknownKeys {
+            file: file_path.to_string(),
+            keys: unknown_keys,
+        })
+    }
+}
 
-            merge_env(&mut all_env, &mut cli_env)
+/// Ensure the task min_version, if present, is older than cargo-make's currently
+/// running version.<br>
+/// Unlike the makefile min_version, this is only checked for tasks which are invoked.
+pub fn check_task_min_version(task: &Task) -> Result<(), DescriptorError> {
+    if let Some(ref min_version) = task.min_version {
+        if version::is_newer_found(&min_version).map_err(DescriptorError::InvalidVersion)? {
+            return Err(DescriptorError::MinVersion {
+                required: min_version.to_string(),
+                file: None,
+            });
         }
-        None => all_env,
+    }
+
+    Ok(())
+}
+
+/// Ensure the Makefile's min_version, if present, is older than cargo-make's
+/// currently running version and the max_version, if present, is not older than
+/// cargo-make's currently running version.
+fn check_makefile_min_version(external_descriptor: &str) -> Result<(), DescriptorError> {
+    let value: toml::Value = match toml::from_str(&external_descriptor) {
+        Ok(value) => value,
+        // If there's an error parsing the file, let the caller function figure
+        // it out
+        Err(_) => return Ok(()),
     };
 
-    let all_tasks = merge_tasks(&mut base_tasks, &mut external_tasks, late_merge);
+    check_makefile_min_version_value(&value)
+}
 
-    let mut config_section = base_config.config.clone();
-    config_section.extend(&mut external_config.config.unwrap_or(ConfigSection::new()));
+fn compare_min_version_value(value: &toml::Value) -> VersionCheck {
+    let min_version = value
+        .get("config")
+        .and_then(|config| config.get("min_version"))
+        .and_then(|min_ver| min_ver.as_str());
 
-    Config {
-        config: config_section,
-        env_files,
-        env: all_env,
-        env_scripts,
-        tasks: all_tasks,
+    match min_version {
+        Some(min_version) => match version::is_newer_found(&min_version) {
+            Ok(true) => VersionCheck::TooOld {
+                required: min_version.to_string(),
+                running: version::get_running_version(),
+            },
+            Ok(false) => VersionCheck::Ok,
+            Err(error) => VersionCheck::Unparseable(error),
+        },
+        None => VersionCheck::Ok,
     }
 }
 
-/// Loads the tasks descriptor.<br>
-/// It will first load the default descriptor which is defined in cargo-make internally and
-/// afterwards tries to find the external descriptor and load it as well.<br>
-/// If an extenal descriptor exists, it will be loaded and extend the default descriptor.
-/// If one of the descriptor requires a newer version of cargo-make, returns an error with the
-/// minimum version required by the descriptor.
-fn load_descriptors(
-    file_name: &str,
-    force: bool,
-    env_map: Option<Vec<String>>,
-    stable: bool,
-    experimental: bool,
-    modify_core_tasks: Option<ModifyConfig>,
-) -> Result<Config, String> {
-    let default_config = load_internal_descriptors(stable, experimental, modify_core_tasks);
+fn check_makefile_min_version_value(value: &toml::Value) -> Result<(), DescriptorError> {
+    match compare_min_version_value(value) {
+        VersionCheck::TooOld { required, .. } => {
+            return Err(DescriptorError::MinVersion {
+                required,
+                file: None,
+            })
+        }
+        VersionCheck::Unparseable(error) => return Err(DescriptorError::InvalidVersion(error)),
+        VersionCheck::Ok => (),
+    };
 
-    let mut external_config = load_external_descriptor(".", file_name, force, true)?;
+    let config = value.get("config");
+    let max_version = config
+        .and_then(|config| config.get("max_version"))
+        .and_then(|max_ver| max_ver.as_str());
 
-    external_config = match env::var("CARGO_MAKE_WORKSPACE_MAKEFILE") {
-        Ok(workspace_makefile) => {
-            let mut pathbuf = PathBuf::from(workspace_makefile);
-            match pathbuf.clone().file_name() {
-                Some(workspace_file_name) => match workspace_file_name.to_str() {
-                    Some(workspace_file_name_str) => {
-                        pathbuf.pop();
-
-                        match pathbuf.to_str() {
-                            Some(directory) => {
-                                let workspace_config = load_external_descriptor(
-                                    directory,
-                                    workspace_file_name_str,
-                                    false,
-                                    false,
-                                )?;
-                                merge_external_configs(external_config, workspace_config)
-                            }
-                            _ => external_config,
-                        }
-                    }
-                    _ => external_config,
-                },
-                _ => external_config,
-            }
+    if let Some(ref max_version) = max_version {
+        if version::is_older_found(&max_version).map_err(DescriptorError::InvalidVersion)? {
+            return Err(DescriptorError::MaxVersion {
+                maximum: max_version.to_string(),
+            });
         }
-        _ => external_config,
-    };
+    }
 
-    let config =
-        merge_base_config_and_external_config(default_config, external_config, env_map, false);
+    Ok(())
+}
 
-    debug!("Loaded merged config: {:#?}", &config);
+/// Compares the makefile (content) min_version with the running cargo-make version.<br>
+/// Unlike loading the makefile, the max_version and the extended makefiles are not checked.
+pub fn compare_min_version(descriptor: &str) -> VersionCheck {
+    match toml::from_str(&descriptor) {
+        Ok(value) => compare_min_version_value(&value),
+        Err(error) => VersionCheck::Unparseable(error.to_string()),
+    }
+}
 
-    Ok(config)
+/// Returns the min version error which requires the higher cargo-make version.
+fn get_higher_min_version_error(
+    error: DescriptorError,
+    other_error: DescriptorError,
+) -> DescriptorError {
+    match (&error, &other_error) {
+        (
+            DescriptorError::MinVersion { ref required, .. },
+            DescriptorError::MinVersion {
+                required: ref other_required,
+                ..
+            },
+        ) if version::is_newer_semver(required, other_required).unwrap_or(false) => other_error,
+        _ => error,
+    }
 }
 
-/// Loads the tasks descriptor.<br>
-/// It will first load the default descriptor which is defined in cargo-make internally and
-/// afterwards tries to find the external descriptor and load it as well.<br>
-/// If an extenal descriptor exists, it will be loaded and extend the default descriptor. <br>
-/// If one of the descriptor requires a newer version of cargo-make, returns an error with the
-/// minimum version required by the descriptor.
-pub(crate) fn load(
+/// Accumulates the min version errors of the extended makefiles, so the highest required
+/// version is reported only after all makefiles were checked.<br>
+/// Other errors are returned, unless a min version error was already found in which case
+/// they are ignored as they may be caused by the newer makefile format.
+fn collect_min_version_error(
+    min_version_error: &mut Option<DescriptorError>,
+    error: DescriptorError,
+) -> Result<(), DescriptorError> {
+    match error {
+        DescriptorError::MinVersion { .. } => {
+            *min_version_error = Some(match min_version_error.take() {
+                Some(current_error) => get_higher_min_version_error(current_error, error),
+                None => error,
+            });
+
+            Ok(())
+        }
+        _ if min_version_error.is_some() => Ok(()),
+        _ => Err(error),
+    }
+}
+
+/// Returns the extend attribute of the makefile without deserializing the entire makefile.
+fn get_descriptor_extend(external_descriptor: &str, descriptor_path: &str) -> Option<Extend> {
+    let value: Option<toml::Value> = if is_yaml_descriptor(descriptor_path) {
+        serde_yaml::from_str::<serde_yaml::Value>(external_descriptor)
+            .ok()
+            .and_then(|value| toml::Value::try_from(&value).ok())
+    } else {
+        toml::from_str(external_descriptor).ok()
+    };
+
+    value
+        .and_then(|value| value.get("extend").cloned())
+        .and_then(|extend| extend.try_into().ok())
+}
+
+/// Returns the maximum extend depth, which can be overridden by the CARGO_MAKE_MAX_EXTEND_DEPTH env var.
+fn get_max_extend_depth() -> usize {
+    let value = envmnt::get_or("CARGO_MAKE_MAX_EXTEND_DEPTH", "");
+
+    if value.is_empty() {
+        DEFAULT_MAX_EXTEND_DEPTH
+    } else {
+        match value.parse() {
+            Ok(max_depth) => max_depth,
+            Err(_) => {
+                warn!(
+                    "Invalid CARGO_MAKE_MAX_EXTEND_DEPTH value: {}, using default: {}",
+                    &value, DEFAULT_MAX_EXTEND_DEPTH
+                );
+                DEFAULT_MAX_EXTEND_DEPTH
+            }
+        }
+    }
+}
+
+fn get_extend_chain_with_max_depth(
+    extend_chain: &Vec<String>,
+    descriptor_path: &str,
+    max_depth: usize,
+) -> Result<Vec<String>, DescriptorError> {
+    let mut current_extend_chain = extend_chain.clone();
+    current_extend_chain.push(descriptor_path.to_string());
+
+    if extend_chain.contains(&descriptor_path.to_string()) {
+        Err(DescriptorError::CircularExtend(current_extend_chain))
+    } else if extend_chain.len() > max_depth {
+        // the extend chain holds all the makefiles which (directly or indirectly) extend this makefile
+        Err(DescriptorError::ExtendDepthLimit {
+            file: descriptor_path.to_string(),
+            limit: max_depth,
+        })
+    } else {
+        Ok(current_extend_chain)
+    }
+}
+
+fn get_extend_chain(
+    extend_chain: &Vec<String>,
+    descriptor_path: &str,
+) -> Result<Vec<String>, DescriptorError> {
+    get_extend_chain_with_max_depth(extend_chain, descriptor_path, get_max_extend_depth())
+}
+
+fn is_yaml_descriptor(descriptor_path: &str) -> bool {
+    let lowercase_path = descriptor_path.to_lowercase();
+
+    lowercase_path.ends_with(".yml") || lowercase_path.ends_with(".yaml")
+}
+
+/// Holds the task names in the order presented by the makefile parser (including duplicates).
+#[derive(Deserialize, Default)]
+struct DescriptorTaskKeys {
+    #[serde(default)]
+    tasks: TaskKeys,
+}
+
+#[derive(Default)]
+struct TaskKeys(Vec<String>);
+
+impl<'de> Deserialize<'de> for TaskKeys {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TaskKeysVisitor;
+        impl<'de> Visitor<'de> for TaskKeysVisitor {
+            type Value = TaskKeys;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("A tasks table")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut keys = vec![];
+                while let Some(key) = map.next_key::<String>()? {
+                    map.next_value::<IgnoredAny>()?;
+                    keys.push(key);
+                }
+
+                Ok(TaskKeys(keys))
+            }
+        }
+        deserializer.deserialize_map(TaskKeysVisitor)
+    }
+}
+
+/// Returns the task names as presented by the toml parser (including duplicates which the
+/// tasks map deserialization silently merges).
+fn get_toml_task_keys(external_descriptor: &str) -> Vec<String> {
+    toml::from_str::<DescriptorTaskKeys>(external_descriptor)
+        .unwrap_or_default()
+        .tasks
+        .0
+}
+
+/// Returns the task names as presented by the yaml parser (including duplicates which the
+/// tasks map deserialization silently overrides).
+fn get_yaml_task_keys(external_descriptor: &str) -> Vec<String> {
+    serde_yaml::from_str::<DescriptorTaskKeys>(external_descriptor)
+        .unwrap_or_default()
+        .tasks
+        .0
+}
+
+/// Returns the keys which are defined more than once (each key only once).
+fn get_duplicate_keys(keys: Vec<String>) -> Vec<String> {
+    let mut unique_keys = vec![];
+    let mut duplicate_keys = vec![];
+
+    for key in keys {
+        if !unique_keys.contains(&key) {
+            unique_keys.push(key);
+        } else if !duplicate_keys.contains(&key) {
+            duplicate_keys.push(key);
+        }
+    }
+
+    duplicate_keys
+}
+
+/// Ensures the makefile does not define the same task more than once, in which case the
+/// deserialization silently keeps only one of the definitions.<br>
+/// Duplicates are reported as a warning, or as an error in strict mode.
+fn check_duplicate_task_keys(
+    task_keys: Vec<String>,
+    file_path: &str,
+    strict: bool,
+) -> Result<(), DescriptorError> {
+    let duplicate_keys = get_duplicate_keys(task_keys);
+
+    if duplicate_keys.is_empty() {
+        Ok(())
+    } else if strict {
+        Err(DescriptorError::DuplicateTaskKeys {
+            file: file_path.to_string(),
+            keys: duplicate_keys,
+        })
+    } else {
+        warn!(
+            "Duplicate task keys found in descriptor: {}, {}",
+            file_path,
+            duplicate_keys.join(", ")
+        );
+
+        Ok(())
+    }
+}
+
+fn deserialize_toml_descriptor(
+    external_descriptor: &str,
+    descriptor_path: &str,
+    strict: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    check_makefile_min_version(&external_descriptor)?;
+
+    let file_config: ExternalConfig = match toml::from_str(&external_descriptor) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(DescriptorError::Parse {
+                file: descriptor_path.to_string(),
+                source: error,
+            });
+        }
+    };
+
+    if strict {
+        validate_known_keys(&external_descriptor, &descriptor_path)?;
+    }
+
+    check_duplicate_task_keys(
+        get_toml_task_keys(&external_descriptor),
+        &descriptor_path,
+        strict,
+    )?;
+
+    Ok(file_config)
+}
+
+/// Deserializes a YAML makefile.<br>
+/// The version and known keys checks are done on the equivalent toml value, so both formats
+/// share the same validations.
+fn deserialize_yaml_descriptor(
+    external_descriptor: &str,
+    descriptor_path: &str,
+    strict: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    let value: serde_yaml::Value = match serde_yaml::from_str(&external_descriptor) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(DescriptorError::ParseYaml {
+                file: descriptor_path.to_string(),
+                message: error.to_string(),
+            });
+        }
+    };
+
+    // empty yaml documents are valid empty makefiles
+    if value.is_null() {
+        return Ok(ExternalConfig::new());
+    }
+
+    let toml_value = toml::Value::try_from(&value).ok();
+    if let Some(ref toml_value) = toml_value {
+        check_makefile_min_version_value(toml_value)?;
+    }
+
+    let file_config: ExternalConfig = match serde_yaml::from_value(value) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(DescriptorError::ParseYaml {
+                file: descriptor_path.to_string(),
+                message: error.to_string(),
+            });
+        }
+    };
+
+    if strict {
+        if let Some(ref toml_value) = toml_value {
+            validate_known_keys_value(toml_value, &descriptor_path)?;
+        }
+    }
+
+    check_duplicate_task_keys(
+        get_yaml_task_keys(&external_descriptor),
+        &descriptor_path,
+        strict,
+    )?;
+
+    Ok(file_config)
+}
+
+fn is_empty_descriptor(file_config: &ExternalConfig) -> bool {
+    file_config.extend.is_none()
+        && file_config.config.is_none()
+        && file_config
+            .env_files
+            .as_ref()
+            .map_or(true, |env_files| env_files.is_empty())
+        && file_config.env.as_ref().map_or(true, |env| env.is_empty())
+        && file_config
+            .env_scripts
+            .as_ref()
+            .map_or(true, |env_scripts| env_scripts.is_empty())
+        && file_config
+            .tasks
+            .as_ref()
+            .map_or(true, |tasks| tasks.is_empty())
+}
+
+/// Warns in case the makefile is effectively empty (for example only whitespace or comments),
+/// which usually means the makefile content was not saved.<br>
+/// In strict mode an error is returned instead.
+fn check_empty_descriptor(
+    file_config: &ExternalConfig,
+    descriptor_path: &str,
+    strict: bool,
+) -> Result<(), DescriptorError> {
+    if is_empty_descriptor(file_config) {
+        if strict {
+            return Err(DescriptorError::EmptyDescriptor(
+                descriptor_path.to_string(),
+            ));
+        }
+
+        warn!(
+            "Makefile: {} is empty, no tasks, env, config or extend defined.",
+            descriptor_path
+        );
+    }
+
+    Ok(())
+}
+
+fn parse_external_descriptor(
+    external_descriptor: &str,
+    descriptor_path: &str,
+    parent_path: &str,
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    profile: Option<&str>,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    let deserialize_result = if is_yaml_descriptor(descriptor_path) {
+        deserialize_yaml_descriptor(external_descriptor, descriptor_path, strict)
+    } else {
+        deserialize_toml_descriptor(external_descriptor, descriptor_path, strict)
+    };
+    let mut file_config = match deserialize_result {
+        Ok(file_config) => file_config,
+        Err(DescriptorError::MinVersion { required, .. }) => {
+            let error = DescriptorError::MinVersion {
+                required,
+                file: Some(descriptor_path.to_string()),
+            };
+
+            // the extended makefiles may require an even newer version
+            return Err(
+                match get_descriptor_extend(external_descriptor, descriptor_path) {
+                    Some(ref extend_struct) => match load_descriptor_extended_makefiles(
+                        &parent_path,
+                        extend_struct,
+                        &extend_chain,
+                        false,
+                        false,
+                        &get_active_profile(profile, None),
+                        None,
+                    ) {
+                        Ok(_) => error,
+                        Err(extended_error) => get_higher_min_version_error(error, extended_error),
+                    },
+                    None => error,
+                },
+            );
+        }
+        Err(error) => return Err(error),
+    };
+    debug!("Loaded external config: {:#?}", &file_config);
+
+    check_empty_descriptor(&file_config, &descriptor_path, strict)?;
+
+    let base_directory = get_descriptor_directory(descriptor_path, parent_path);
+    file_config = add_file_location_info(file_config, &descriptor_path, &base_directory);
+
+    if run_scripts {
+        let merge_output = match file_config.config {
+            Some(ref config) => config.merge_load_script_output.unwrap_or(false),
+            None => false,
+        };
+
+        if merge_output {
+            file_config = merge_load_script_output(file_config, &descriptor_path, &base_directory)?;
+        } else {
+            run_load_script(&file_config);
+        }
+    }
+
+    // extended makefiles use the profile resolved by the root makefile
+    let active_profile = get_active_profile(profile, file_config.config.as_ref());
+
+    match file_config.extend {
+        Some(ref extend_struct) => {
+            debug!("External config parent path: {}", &parent_path);
+
+            let (base_file_config, overlay_file_config) =
+                load_descriptor_extended_makefiles_by_mode(
+                    &parent_path,
+                    extend_struct,
+                    &extend_chain,
+                    strict,
+                    run_scripts,
+                    &active_profile,
+                    load_trace,
+                )?;
+
+            file_config = merge_external_configs_checked(file_config, base_file_config, strict)?;
+
+            // overlay makefiles override the current makefile
+            if let Some(overlay_file_config) = overlay_file_config {
+                file_config =
+                    merge_external_configs_checked(overlay_file_config, file_config, strict)?;
+            }
+        }
+        None => (),
+    };
+
+    // extended makefiles were already added so the trace reflects the merge precedence order
+    if let Some(load_trace) = load_trace {
+        load_trace.borrow_mut().push(descriptor_path.to_string());
+    }
+
+    Ok(file_config)
+}
+
+/// Returns the canonical makefile path.<br>
+/// In case the path can't be canonicalized, the path is resolved from the current directory
+/// instead (or an error is returned in strict mode).
+fn get_absolute_descriptor_path(file_path: &Path, strict: bool) -> Result<String, DescriptorError> {
+    match file_path.canonicalize() {
+        Ok(absolute_path) => Ok(FromPath::from_path(&absolute_path)),
+        Err(error) => {
+            if strict {
+                return Err(DescriptorError::Canonicalize {
+                    file: file_path.to_path_buf(),
+                    message: error.to_string(),
+                });
+            }
+
+            let absolute_path = if file_path.is_absolute() {
+                file_path.to_path_buf()
+            } else {
+                match env::current_dir() {
+                    Ok(directory) => directory.join(file_path),
+                    Err(_) => file_path.to_path_buf(),
+                }
+            };
+            let absolute_path_string: String = FromPath::from_path(&absolute_path);
+
+            debug!(
+                "Unable to canonicalize descriptor path: {:#?}, {}, using: {}",
+                file_path, error, &absolute_path_string
+            );
+
+            Ok(absolute_path_string)
+        }
+    }
+}
+
+/// Reads the makefile content from the provided reader (stdin).
+fn read_stdin_descriptor<R: Read>(mut reader: R) -> Result<String, DescriptorError> {
+    let mut content = String::new();
+    match reader.read_to_string(&mut content) {
+        Ok(_) => Ok(content),
+        Err(error) => Err(DescriptorError::Stdin(error.to_string())),
+    }
+}
+
+/// Reads the makefile content from the provided reader (stdin) in case the makefile file name
+/// is the stdin file name, otherwise returns None.<br>
+/// Stdin can only be read once while the makefile may be loaded more than once, so it is read
+/// once for every top level load and the content is passed down.
+fn read_root_stdin_descriptor<R: Read>(
+    file_name: &str,
+    reader: R,
+) -> Result<Option<String>, DescriptorError> {
+    if file_name == STDIN_DESCRIPTOR_FILE_NAME {
+        Ok(Some(read_stdin_descriptor(reader)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Loads the makefile read from stdin.<br>
+/// Relative extend and env_files paths are resolved from the current working directory.
+fn load_stdin_descriptor(
+    external_descriptor: &str,
+    set_env: bool,
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    let current_extend_chain = get_extend_chain(extend_chain, STDIN_DESCRIPTOR_PATH)?;
+
+    if set_env {
+        envmnt::set("CARGO_MAKE_MAKEFILE_PATH", STDIN_DESCRIPTOR_PATH);
+    }
+
+    parse_external_descriptor(
+        external_descriptor,
+        STDIN_DESCRIPTOR_PATH,
+        ".",
+        &current_extend_chain,
+        strict,
+        run_scripts,
+        None,
+        load_trace,
+    )
+}
+
+/// Loads the top level makefile, either from the provided stdin content or from the file system.
+fn load_root_descriptor(
     file_name: &str,
+    stdin_descriptor: &Option<String>,
     force: bool,
+    set_env: bool,
+    strict: bool,
+    run_scripts: bool,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    match stdin_descriptor {
+        Some(ref external_descriptor) => load_stdin_descriptor(
+            external_descriptor,
+            set_env,
+            &vec![],
+            strict,
+            run_scripts,
+            load_trace,
+        ),
+        None => load_external_descriptor(
+            ".",
+            file_name,
+            force,
+            set_env,
+            &vec![],
+            strict,
+            run_scripts,
+            None,
+            load_trace,
+        ),
+    }
+}
+
+fn load_external_descriptor(
+    base_path: &str,
+    file_name: &str,
+    force: bool,
+    set_env: bool,
+    extend_chain: &Vec<String>,
+    strict: bool,
+    run_scripts: bool,
+    profile: Option<&str>,
+    load_trace: Option<&RefCell<Vec<String>>>,
+) -> Result<ExternalConfig, DescriptorError> {
+    debug!(
+        "Loading tasks from file: {} base directory: {}",
+        &file_name, &base_path
+    );
+
+    if let Some(url) = remote::get_url(base_path, file_name) {
+        let current_extend_chain = get_extend_chain(extend_chain, &url)?;
+
+        return match remote::load(&url) {
+            Ok(external_descriptor) => {
+                if set_env {
+                    envmnt::set("CARGO_MAKE_MAKEFILE_PATH", &url);
+                }
+
+                parse_external_descriptor(
+                    &external_descriptor,
+                    &url,
+                    &remote::get_parent_url(&url),
+                    &current_extend_chain,
+                    strict,
+                    run_scripts,
+                    profile,
+                    load_trace,
+                )
+            }
+            Err(error) => {
+                if force {
+                    Err(DescriptorError::Remote(error))
+                } else {
+                    debug!("{}, skipping.", &error);
+
+                    Ok(ExternalConfig::new())
+                }
+            }
+        };
+    }
+
+    let file_path = Path::new(base_path).join(file_name);
+
+    if file_path.exists() && file_path.is_file() {
+        let absolute_file_path = get_absolute_descriptor_path(&file_path, strict)?;
+
+        let current_extend_chain = get_extend_chain(extend_chain, &absolute_file_path)?;
+
+        if set_env {
+            envmnt::set("CARGO_MAKE_MAKEFILE_PATH", &absolute_file_path);
+        }
+
+        let external_descriptor = io::read_text_file(&file_path);
+
+        let parent_path_buf = Path::new(base_path).join(file_name).join("..");
+        let parent_path = file_path
+            .parent()
+            .unwrap_or(&parent_path_buf)
+            .to_str()
+            .unwrap_or(".");
+
+        parse_external_descriptor(
+            &external_descriptor,
+            &absolute_file_path,
+            &parent_path,
+            &current_extend_chain,
+            strict,
+            run_scripts,
+            profile,
+            load_trace,
+        )
+    } else if force {
+        if file_path.is_dir() {
+            Err(DescriptorError::IsDirectory(file_path))
+        } else {
+            Err(DescriptorError::FileNotFound(file_path))
+        }
+    } else {
+        debug!("External file not found or is not a file, skipping.");
+
+        Ok(ExternalConfig::new())
+    }
+}
+
+/// Records the provided source for all the env keys defined in the config.
+fn set_config_env_sources(config: &mut Config, source: &str) {
+    for key in config.env.keys() {
+        config.config.set_env_source(key, source);
+    }
+}
+
+fn parse_internal_descriptor(descriptor: &str, name: &str) -> Config {
+    let mut config: Config = match toml::from_str(descriptor) {
+        Ok(value) => value,
+        Err(error) => panic!("Unable to parse {} descriptor, {}", name, error),
+    };
+
+    set_config_field_sources(&mut config.config, "internal");
+    set_config_env_sources(&mut config, "internal");
+
+    config
+}
+
+/// Loads the internal descriptor override file (used for testing custom cargo-make distributions).
+#[cfg(test)]
+fn load_internal_descriptor_override(file: &str) -> Result<Config, DescriptorError> {
+    let file_path = Path::new(file);
+    if !file_path.is_file() {
+        return Err(DescriptorError::FileNotFound(file_path.to_path_buf()));
+    }
+
+    let descriptor = io::read_text_file(&file_path.to_path_buf());
+    let mut config: Config = match toml::from_str(&descriptor) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(DescriptorError::Parse {
+                file: file.to_string(),
+                source: error,
+            });
+        }
+    };
+
+    set_config_field_sources(&mut config.config, file);
+    set_config_env_sources(&mut config, file);
+
+    Ok(config)
+}
+
+fn get_builtin_base_config(stable: bool) -> Config {
+    if stable {
+        STABLE_CONFIG.clone()
+    } else {
+        BASE_CONFIG.clone()
+    }
+}
+
+/// Returns the internal base descriptor.
+#[cfg(not(test))]
+fn get_internal_base_config(stable: bool) -> Result<Config, DescriptorError> {
+    Ok(get_builtin_base_config(stable))
+}
+
+/// Returns the internal base descriptor, which is replaced (in test builds only) by the file
+/// defined in the CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE env var (if defined).
+#[cfg(test)]
+fn get_internal_base_config(stable: bool) -> Result<Config, DescriptorError> {
+    let override_file = envmnt::get_or("CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE", "");
+
+    if !override_file.is_empty() {
+        debug!(
+            "Loading internal descriptor from: {} (CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE is defined).",
+            &override_file
+        );
+
+        load_internal_descriptor_override(&override_file)
+    } else {
+        Ok(get_builtin_base_config(stable))
+    }
+}
+
+pub(crate) fn load_internal_descriptors(
+    stable: bool,
+    experimental: bool,
+    modify_config: Option<ModifyConfig>,
+    protected_tasks: &Vec<String>,
+) -> Result<Config, DescriptorError> {
+    debug!("Loading base tasks.");
+
+    let mut base_config = get_internal_base_config(stable)?;
+    debug!("Loaded base config: {:#?}", &base_config);
+
+    if experimental {
+        debug!("Loading experimental tasks.");
+        let experimental_config = EXPERIMENTAL_CONFIG.clone();
+        debug!("Loaded experimental config: {:#?}", &experimental_config);
+
+        let base_tasks = base_config.tasks;
+        let mut experimental_tasks = experimental_config.tasks;
+
+        if !protected_tasks.is_empty() {
+            let prefix = match modify_config {
+                Some(ref props) => props.get_namespace_prefix(),
+                None => "".to_string(),
+            };
+
+            // user defined tasks are owned by the user, so beta versions are not folded in
+            experimental_tasks.retain(|name, _| {
+                let namespaced_name = format!("{}{}", &prefix, name);
+                let protected =
+                    protected_tasks.contains(name) || protected_tasks.contains(&namespaced_name);
+
+                if protected {
+                    debug!("Skipping experimental task: {} (user defined)", &name);
+                }
+
+                !protected
+            });
+        }
+
+        let all_tasks = merge_tasks(base_tasks, experimental_tasks, false);
+
+        base_config.tasks = all_tasks;
+    }
+
+    // reset
+    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE", "");
+    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX", "");
+
+    match modify_config {
+        Some(props) => {
+            base_config.apply(&props)?;
+
+            match props.namespace {
+                Some(ref namespace) => {
+                    let prefix = props.get_namespace_prefix();
+
+                    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE", &namespace);
+                    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX", &prefix);
+                }
+                None => (),
+            };
+        }
+        None => (),
+    };
+
+    Ok(base_config)
+}
+
+/// Returns true if the key is defined in the env, either directly or in one of the profiles env.
+fn is_env_key_defined(env: &IndexMap<String, EnvValue>, key: &str) -> bool {
+    env.iter().any(|(env_key, value)| {
+        env_key == key
+            || match value {
+                EnvValue::Profile(ref profile_env) => profile_env.contains_key(key),
+                _ => false,
+            }
+    })
+}
+
+/// Merges the cli env into the env.<br>
+/// The cli values take precedence over any other value, so the same keys are removed from
+/// the profile env blocks which would otherwise override them when the env is set.
+fn merge_cli_env(
+    mut env: IndexMap<String, EnvValue>,
+    cli_env: &mut IndexMap<String, EnvValue>,
+) -> IndexMap<String, EnvValue> {
+    for value in env.values_mut() {
+        if let EnvValue::Profile(ref mut profile_env) = value {
+            profile_env.retain(|key, _| !cli_env.contains_key(key));
+        }
+    }
+
+    merge_env(&mut env, cli_env)
+}
+
+fn merge_additional_profiles_env(
+    mut env: IndexMap<String, EnvValue>,
+    profile_name: &str,
+    additional_profiles: &Vec<String>,
+) -> IndexMap<String, EnvValue> {
+    let mut profile_env = IndexMap::<String, EnvValue>::new();
+
+    for additional_profile in additional_profiles {
+        if additional_profile == profile_name {
+            continue;
+        }
+
+        match env.get(additional_profile) {
+            Some(EnvValue::Profile(ref sub_env)) => {
+                let mut additional_env = sub_env.clone();
+                profile_env = merge_env(&mut profile_env, &mut additional_env);
+            }
+            _ => debug!("Additional profile: {} env not found.", &additional_profile),
+        };
+    }
+
+    if !profile_env.is_empty() {
+        if let Some(EnvValue::Profile(ref sub_env)) = env.get(profile_name) {
+            let mut active_env = sub_env.clone();
+            profile_env = merge_env(&mut profile_env, &mut active_env);
+        }
+
+        env.insert(profile_name.to_string(), EnvValue::Profile(profile_env));
+    }
+
+    env
+}
+
+fn get_core_task_names(external_config: &ExternalConfig) -> Vec<String> {
+    let (skip_core_tasks, prefix) = match external_config.config {
+        Some(ref config) => (
+            config.skip_core_tasks.unwrap_or(false),
+            match config.modify_core_tasks {
+                Some(ref modify_config) => modify_config.get_namespace_prefix(),
+                None => "".to_string(),
+            },
+        ),
+        None => (false, "".to_string()),
+    };
+
+    if skip_core_tasks {
+        vec![]
+    } else {
+        STABLE_CONFIG
+            .tasks
+            .keys()
+            .map(|key| format!("{}{}", &prefix, key))
+            .collect()
+    }
+}
+
+/// Prints a warning for every external task which overrides a core task, unless the task
+/// is marked with override_core_task.<br>
+/// Returns the names of the overriding tasks.
+fn warn_core_task_overrides(external_config: &ExternalConfig) -> Vec<String> {
+    let mut overrides = vec![];
+
+    if let Some(ref tasks) = external_config.tasks {
+        let core_task_names = get_core_task_names(external_config);
+
+        for (name, task) in tasks.iter() {
+            if core_task_names.contains(name) && !task.override_core_task.unwrap_or(false) {
+                warn!(
+                    "Task: {} overrides the core task with the same name, set override_core_task = true in case this is intentional.",
+                    &name
+                );
+
+                overrides.push(name.to_string());
+            }
+        }
+    }
+
+    overrides
+}
+
+fn merge_base_config_and_external_config(
+    base_config: Config,
+    external_config: ExternalConfig,
+    env_map: Option<Vec<String>>,
+    late_merge: bool,
+    strict_env: bool,
+) -> Result<Config, DescriptorError> {
+    let external_tasks = match external_config.tasks {
+        Some(tasks) => tasks,
+        None => IndexMap::new(),
+    };
+    let base_tasks = base_config.tasks;
+
+    let env_files = match external_config.env_files {
+        Some(env_files) => env_files,
+        None => vec![],
+    };
+
+    let env_scripts = match external_config.env_scripts {
+        Some(env_scripts) => env_scripts,
+        None => vec![],
+    };
+
+    let mut external_env = match external_config.env {
+        Some(env) => env,
+        None => IndexMap::new(),
+    };
+    let mut base_env = base_config.env;
+
+    // merge env
+    record_env_merge_events(
+        &base_env,
+        &external_env,
+        Some(&base_config.config),
+        external_config.config.as_ref(),
+    );
+    let empty_env_is_unset =
+        get_empty_env_is_unset(external_config.config.as_ref(), Some(&base_config.config));
+    if get_strict_env_types(external_config.config.as_ref(), Some(&base_config.config)) {
+        validate_env_types(&base_env, &external_env, empty_env_is_unset, "")?;
+    }
+    let path_env_keys = get_path_env_keys(&external_config.config);
+    let mut all_env = merge_env_with_options(
+        &mut base_env,
+        &mut external_env,
+        &path_env_keys,
+        get_path_separator(),
+        empty_env_is_unset,
+    );
+    all_env = match env_map {
+        Some(values) => {
+            let mut cli_env = IndexMap::new();
+
+            for env_pair in &values {
+                // only the first '=' separates the key from the value
+                let env_part: Vec<&str> = env_pair.splitn(2, '=').collect();
+                debug!("Checking env pair: {}", &env_pair);
+
+                if env_part.len() == 2 && !env_part[0].is_empty() {
+                    if strict_env && !is_env_key_defined(&all_env, env_part[0]) {
+                        return Err(DescriptorError::UndefinedCliEnv(env_part[0].to_string()));
+                    }
+
+                    cli_env.insert(
+                        env_part[0].to_string(),
+                        EnvValue::Value(env_part[1].to_string()),
+                    );
+                }
+            }
+
+            merge_cli_env(all_env, &mut cli_env)
+        }
+        None => all_env,
+    };
+
+    let mut config_section = base_config.config.clone();
+    config_section.extend(&mut external_config.config.unwrap_or(ConfigSection::new()));
+
+    // merge the additional profiles env into the active profile env (lowest precedence)
+    if !late_merge {
+        if let Some(ref additional_profiles) = config_section.additional_profiles {
+            all_env = merge_additional_profiles_env(all_env, &profile::get(), additional_profiles);
+        }
+    }
+
+    let merge_task_env = config_section.merge_task_env.unwrap_or(false);
+    let all_tasks = merge_tasks(base_tasks, external_tasks, merge_task_env);
+
+    Ok(Config {
+        config: config_section,
+        env_files,
+        env: all_env,
+        env_scripts,
+        tasks: all_tasks,
+    })
+}
+
+/// Loads the workspace makefiles (path separator delimited list) where later makefiles
+/// extend earlier makefiles. Makefiles which do not exist are skipped.
+fn load_workspace_makefiles(
+    workspace_makefiles: &str,
+    strict: bool,
+    run_scripts: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    let mut workspace_config = ExternalConfig::new();
+
+    for mut pathbuf in env::split_paths(workspace_makefiles) {
+        let workspace_file_name = match pathbuf.file_name() {
+            Some(file_name) => match file_name.to_str() {
+                Some(file_name_str) => file_name_str.to_string(),
+                None => continue,
+            },
+            None => continue,
+        };
+        pathbuf.pop();
+
+        if let Some(directory) = pathbuf.to_str() {
+            let entry_config = load_external_descriptor(
+                directory,
+                &workspace_file_name,
+                false,
+                false,
+                &vec![],
+                strict,
+                run_scripts,
+                None,
+                None,
+            )?;
+            workspace_config =
+                merge_external_configs_checked(entry_config, workspace_config, strict)?;
+        }
+    }
+
+    Ok(workspace_config)
+}
+
+/// Merges the workspace makefiles config into the member makefile config.<br>
+/// By default the member makefile extends the workspace makefiles, however in case the workspace
+/// config section enables workspace_env_override, the workspace env, env files, env scripts and
+/// config override the member values while the member tasks still override the workspace tasks.
+fn merge_workspace_config(
+    mut member_config: ExternalConfig,
+    mut workspace_config: ExternalConfig,
+) -> ExternalConfig {
+    let workspace_env_override = match workspace_config.config {
+        Some(ref config) => config.workspace_env_override.unwrap_or(false),
+        None => false,
+    };
+
+    if workspace_env_override {
+        let member_tasks = member_config.tasks.take().unwrap_or_else(IndexMap::new);
+        let workspace_tasks = workspace_config.tasks.take().unwrap_or_else(IndexMap::new);
+
+        let mut config = merge_external_configs(workspace_config, member_config);
+
+        let merge_task_env = match config.config {
+            Some(ref config_section) => config_section.merge_task_env.unwrap_or(false),
+            None => false,
+        };
+        config.tasks = Some(merge_tasks(workspace_tasks, member_tasks, merge_task_env));
+
+        config
+    } else {
+        merge_external_configs(member_config, workspace_config)
+    }
+}
+
+/// Returns the user defined task names which experimental tasks may not override.
+/// Tasks without a source makefile are core tasks and are not protected.
+fn get_protected_task_names(external_config: &ExternalConfig, experimental: bool) -> Vec<String> {
+    let additive = match external_config.config {
+        Some(ref config) => config.additive_experimental_tasks.unwrap_or(false),
+        None => false,
+    };
+
+    match external_config.tasks {
+        Some(ref tasks) if experimental && additive => tasks
+            .iter()
+            .filter(|(_, task)| task.get_source_file().is_some())
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the profile used to resolve the profile based extends.<br>
+/// The provided profile (resolved by the root makefile) is used for the extended makefiles, while
+/// the root makefile uses the active profile or its config section default profile in case no
+/// profile is active.
+fn get_active_profile(profile: Option<&str>, config_section: Option<&ConfigSection>) -> String {
+    match profile {
+        Some(profile_name) => profile_name.to_string(),
+        None => match config_section.and_then(|config| config.default_profile.clone()) {
+            Some(ref default_profile) if !profile::is_set() => profile::normalize(default_profile),
+            _ => profile::get(),
+        },
+    }
+}
+
+/// Sets the config section default profile as the active profile in case no profile is active.
+fn set_default_profile(config_section: Option<&ConfigSection>) {
+    if let Some(ref default_profile) =
+        config_section.and_then(|config| config.default_profile.clone())
+    {
+        if !profile::is_set() {
+            let profile_name = profile::set(default_profile);
+            debug!("Using makefile default profile: {}", &profile_name);
+        }
+    }
+}
+
+/// Loads the tasks descriptor.<br>
+/// It will first load the default descriptor which is defined in cargo-make internally and
+/// afterwards tries to find the external descriptor and load it as well.<br>
+/// If an extenal descriptor exists, it will be loaded and extend the default descriptor.
+/// If one of the descriptor requires a newer version of cargo-make, returns an error with the
+/// minimum version required by the descriptor.<br>
+/// If one of the descriptors is not found or is not valid, returns an error with the descriptor
+/// file path and the parsing error.
+fn load_descriptors<F>(
+    load_external_config: &F,
+    env_map: Option<Vec<String>>,
+    stable: bool,
+    experimental: bool,
+    modify_core_tasks: Option<ModifyConfig>,
+    run_scripts: bool,
+    load_options: &LoadOptions,
+) -> Result<Config, DescriptorError>
+where
+    F: Fn() -> Result<ExternalConfig, DescriptorError>,
+{
+    let mut external_config = load_external_config()?;
+
+    let protected_tasks = get_protected_task_names(&external_config, experimental);
+    let default_config =
+        load_internal_descriptors(stable, experimental, modify_core_tasks, &protected_tasks)?;
+
+    external_config = match env::var("CARGO_MAKE_WORKSPACE_MAKEFILE") {
+        Ok(workspace_makefiles) if !load_options.skip_workspace_makefile => {
+            let workspace_config =
+                load_workspace_makefiles(&workspace_makefiles, load_options.strict, run_scripts)?;
+            merge_workspace_config(external_config, workspace_config)
+        }
+        _ => external_config,
+    };
+
+    resolve_task_env_blocks(&mut external_config)?;
+
+    // only the env_file of the merged config section is loaded
+    add_config_env_file(&mut external_config);
+
+    // must be set before the profile based env is merged
+    set_default_profile(external_config.config.as_ref());
+
+    if !stable {
+        // core tasks are merged only after the initial load, so overrides are checked once
+        warn_core_task_overrides(&external_config);
+    }
+
+    let config = merge_base_config_and_external_config(
+        default_config,
+        external_config,
+        env_map,
+        false,
+        load_options.strict_env,
+    )?;
+
+    debug!("Loaded merged config: {:#?}", &config);
+
+    Ok(config)
+}
+
+fn get_task_references(task: &Task) -> Vec<String> {
+    let mut references = vec![];
+
+    if let Some(ref dependencies) = task.dependencies {
+        references.extend(dependencies.iter().cloned());
+    }
+
+    for platform_task in [&task.linux, &task.windows, &task.mac].iter() {
+        if let Some(ref platform_task) = platform_task {
+            if let Some(ref dependencies) = platform_task.dependencies {
+                references.extend(dependencies.iter().cloned());
+            }
+        }
+    }
+
+    for alias in [
+        &task.alias,
+        &task.linux_alias,
+        &task.windows_alias,
+        &task.mac_alias,
+    ]
+    .iter()
+    {
+        if let Some(ref alias) = alias {
+            references.push(alias.to_string());
+        }
+    }
+
+    references
+}
+
+/// Returns the paths of the required env files (relevant for the active profiles) which do not exist.<br>
+/// Env files which can't be resolved yet (undefined variables) are validated only when loaded.
+fn get_missing_env_files(env_files: &Vec<EnvFile>, profiles: &Vec<String>) -> Vec<PathBuf> {
+    let mut missing_env_files = vec![];
+
+    for env_file in env_files {
+        let info = match env_file {
+            EnvFile::Path(ref path) => EnvFileInfo::new(path.to_string()),
+            EnvFile::Info(ref info) => info.clone(),
+        };
+
+        let is_valid_profile = match info.profile {
+            Some(ref profile_name) => profiles.contains(profile_name),
+            None => true,
+        };
+
+        if info.optional.unwrap_or(false) || !is_valid_profile {
+            continue;
+        }
+
+        let base_path = match info.base_path {
+            Some(ref base_path) => Some(environment::expand_value(base_path)),
+            None => None,
+        };
+        let unresolved = info.path.contains("${")
+            || base_path
+                .as_ref()
+                .map_or(false, |base_path| base_path.contains("${"));
+
+        if !unresolved {
+            let file_path = environment::get_env_file_path(&info.path, base_path);
+
+            if !file_path.exists() {
+                missing_env_files.push(file_path);
+            }
+        }
+    }
+
+    missing_env_files
+}
+
+/// Checks that all required global and task env files exist.<br>
+/// Missing env files are reported as warnings, or as an error in strict mode.
+fn check_env_files(config: &Config, strict: bool) -> Result<(), DescriptorError> {
+    let mut profiles = vec![profile::get()];
+    if let Some(ref additional_profiles) = config.config.additional_profiles {
+        profiles.extend(additional_profiles.iter().cloned());
+    }
+
+    let mut missing_env_files = get_missing_env_files(&config.env_files, &profiles);
+    for task in config.tasks.values() {
+        if let Some(ref env_files) = task.env_files {
+            for file_path in get_missing_env_files(env_files, &profiles) {
+                if !missing_env_files.contains(&file_path) {
+                    missing_env_files.push(file_path);
+                }
+            }
+        }
+    }
+
+    for file_path in missing_env_files {
+        if strict {
+            return Err(DescriptorError::EnvFileNotFound(file_path));
+        }
+
+        warn!("Env file: {:#?} not found.", &file_path);
+    }
+
+    Ok(())
+}
+
+fn expand_makefile_location_value(value: &str, makefile: &str, makefile_directory: &str) -> String {
+    value
+        .replace(
+            "${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY}",
+            makefile_directory,
+        )
+        .replace("${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE}", makefile)
+}
+
+/// Expands the task initial makefile path and directory variables in the task command, args and
+/// script (other variables are expanded when the task is invoked).
+fn expand_task_makefile_location(task: &mut Task) {
+    let (makefile, makefile_directory) = match (&task.initial_makefile, &task.initial_makefile_dir)
+    {
+        (Some(makefile), Some(makefile_directory)) => {
+            (makefile.to_string(), makefile_directory.to_string())
+        }
+        _ => return,
+    };
+    let expand =
+        |value: &String| expand_makefile_location_value(value, &makefile, &makefile_directory);
+
+    if let Some(ref command) = task.command {
+        task.command = Some(expand(command));
+    }
+
+    if let Some(ref args) = task.args {
+        task.args = Some(args.iter().map(expand).collect());
+    }
+
+    task.script = match task.script.take() {
+        Some(ScriptValue::Text(lines)) => {
+            Some(ScriptValue::Text(lines.iter().map(expand).collect()))
+        }
+        Some(ScriptValue::File(mut file_script)) => {
+            file_script.file = expand(&file_script.file);
+            Some(ScriptValue::File(file_script))
+        }
+        None => None,
+    };
+}
+
+/// Removes the tasks matching the config.skip_tasks glob patterns.<br>
+/// A warning is printed for every remaining task which references a removed task.
+fn remove_skipped_tasks(config: &mut Config) {
+    let patterns = match config.config.skip_tasks {
+        Some(ref skip_tasks) if !skip_tasks.is_empty() => {
+            let mut patterns = vec![];
+            for skip_task in skip_tasks {
+                match Pattern::new(skip_task) {
+                    Ok(pattern) => patterns.push(pattern),
+                    Err(error) => warn!("Invalid skip_tasks pattern: {}, {}", &skip_task, error),
+                }
+            }
+
+            patterns
+        }
+        _ => return,
+    };
+
+    let removed_tasks: Vec<String> = config
+        .tasks
+        .keys()
+        .filter(|task_name| patterns.iter().any(|pattern| pattern.matches(task_name)))
+        .cloned()
+        .collect();
+
+    for task_name in removed_tasks.iter() {
+        debug!("Skipping task: {}", &task_name);
+        config.tasks.shift_remove(task_name);
+    }
+
+    for (task_name, task) in config.tasks.iter() {
+        for reference in get_task_references(task) {
+            if removed_tasks.contains(&reference) {
+                warn!(
+                    "Task: {} references the skipped task: {}",
+                    &task_name, &reference
+                );
+            }
+        }
+    }
+}
+
+/// Validates that all task dependencies and aliases reference tasks defined in the config.
+fn validate_task_references(config: &Config) -> Result<(), DescriptorError> {
+    let mut undefined_references = vec![];
+
+    for (task_name, task) in config.tasks.iter() {
+        for reference in get_task_references(task) {
+            if !config.tasks.contains_key(&reference) {
+                undefined_references.push(format!("{} -> {}", task_name, reference));
+            }
+        }
+    }
+
+    if undefined_references.is_empty() {
+        Ok(())
+    } else {
+        Err(DescriptorError::UndefinedDependencies(undefined_references))
+    }
+}
+
+/// Converts the task dependencies and aliases to lower case.
+fn fold_task_references_case(task: &mut Task) {
+    if let Some(ref mut dependencies) = task.dependencies {
+        for dependency in dependencies.iter_mut() {
+            *dependency = dependency.to_lowercase();
+        }
+    }
+
+    for platform_task in [&mut task.linux, &mut task.windows, &mut task.mac].iter_mut() {
+        if let Some(ref mut platform_task) = platform_task {
+            if let Some(ref mut dependencies) = platform_task.dependencies {
+                for dependency in dependencies.iter_mut() {
+                    *dependency = dependency.to_lowercase();
+                }
+            }
+        }
+    }
+
+    for alias in [
+        &mut task.alias,
+        &mut task.linux_alias,
+        &mut task.windows_alias,
+        &mut task.mac_alias,
+    ]
+    .iter_mut()
+    {
+        if let Some(ref mut alias) = alias {
+            *alias = alias.to_lowercase();
+        }
+    }
+}
+
+/// Validates the task names against the task name pattern.<br>
+/// Task names which are identical after case folding are reported as conflicts and in case
+/// case folding is enabled, the task names and their references are converted to lower case.
+fn normalize_task_names(config: &mut Config) -> Result<(), DescriptorError> {
+    let pattern = match config.config.task_name_pattern {
+        Some(ref pattern) => pattern.to_string(),
+        None => DEFAULT_TASK_NAME_PATTERN.to_string(),
+    };
+    let regex = match Regex::new(&pattern) {
+        Ok(regex) => regex,
+        Err(error) => return Err(DescriptorError::InvalidTaskNamePattern(error.to_string())),
+    };
+    let fold_case = config.config.fold_task_name_case.unwrap_or(false);
+
+    let tasks = std::mem::replace(&mut config.tasks, IndexMap::new());
+    let mut normalized_names: HashMap<String, String> = HashMap::new();
+    for (name, mut task) in tasks {
+        let normalized_name = name.to_lowercase();
+        if let Some(other) = normalized_names.get(&normalized_name) {
+            return Err(DescriptorError::TaskNameConflict {
+                task: name,
+                other: other.to_string(),
+            });
+        }
+
+        let task_name = if fold_case {
+            fold_task_references_case(&mut task);
+            normalized_name.clone()
+        } else {
+            name.clone()
+        };
+
+        if !regex.is_match(&task_name) {
+            return Err(DescriptorError::InvalidTaskName {
+                task: name,
+                pattern,
+            });
+        }
+
+        normalized_names.insert(normalized_name, name);
+        config.tasks.insert(task_name, task);
+    }
+
+    Ok(())
+}
+
+/// Loads the external descriptor using the provided loader and merges it with the internal
+/// descriptors (including core tasks modifications).
+fn load_with_external_config<F>(
+    load_external_config: &F,
     env_map: Option<Vec<String>>,
     experimental: bool,
-) -> Result<Config, String> {
+    run_scripts: bool,
+    load_options: &LoadOptions,
+) -> Result<Config, DescriptorError>
+where
+    F: Fn() -> Result<ExternalConfig, DescriptorError>,
+{
     // load extended descriptor only
-    let mut config = load_descriptors(&file_name, force, env_map.clone(), false, false, None)?;
+    let mut config = load_descriptors(
+        load_external_config,
+        env_map.clone(),
+        false,
+        false,
+        None,
+        run_scripts,
+        load_options,
+    )?;
 
     // need to load core tasks as well
     if !config.config.skip_core_tasks.unwrap_or(false) {
@@ -617,35 +3066,504 @@ pub(crate) fn load(
                 if modify_config.is_modifications_defined() {
                     // reload everything with core modifications
                     config = load_descriptors(
-                        &file_name,
-                        force,
+                        load_external_config,
                         env_map.clone(),
                         true,
                         experimental,
                         Some(modify_config),
+                        run_scripts,
+                        load_options,
                     )?;
                 }
             }
             None => {
-                let core_config = load_internal_descriptors(true, experimental, modify_core_tasks);
                 let external_config = ExternalConfig {
                     extend: None,
                     config: Some(config.config),
                     env_files: Some(config.env_files),
                     env: Some(config.env),
                     env_scripts: Some(config.env_scripts),
+                    env_blocks: None,
                     tasks: Some(config.tasks),
                 };
+                let protected_tasks = get_protected_task_names(&external_config, experimental);
+                let core_config = load_internal_descriptors(
+                    true,
+                    experimental,
+                    modify_core_tasks,
+                    &protected_tasks,
+                )?;
 
                 config = merge_base_config_and_external_config(
                     core_config,
                     external_config,
                     env_map.clone(),
                     true,
-                );
+                    load_options.strict_env,
+                )?;
             }
         };
     }
 
+    remove_skipped_tasks(&mut config);
+
+    if config.config.expand_makefile_location.unwrap_or(false) {
+        for task in config.tasks.values_mut() {
+            expand_task_makefile_location(task);
+        }
+    }
+
+    if config.config.validate_task_names.unwrap_or(false) {
+        normalize_task_names(&mut config)?;
+    }
+
+    if config.config.validate_dependencies.unwrap_or(false) {
+        validate_task_references(&config)?;
+    }
+
+    check_env_files(&config, load_options.strict)?;
+
+    Ok(config)
+}
+
+/// Loads the tasks descriptor.<br>
+/// It will first load the default descriptor which is defined in cargo-make internally and
+/// afterwards tries to find the external descriptor and load it as well.<br>
+/// If an extenal descriptor exists, it will be loaded and extend the default descriptor. <br>
+/// If one of the descriptor requires a newer version of cargo-make, returns an error with the
+/// minimum version required by the descriptor.<br>
+/// If one of the descriptors is not found or is not valid, returns an error with the descriptor
+/// file path and the parsing error.
+pub(crate) fn load(
+    file_name: &str,
+    force: bool,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+    load_options: &LoadOptions,
+) -> Result<Config, DescriptorError> {
+    let output = load_with_output(file_name, force, env_map, experimental, load_options)?;
+
+    Ok(output.config)
+}
+
+/// Loads the tasks descriptor (same as load) and also returns the paths of all the loaded
+/// makefiles in their merge precedence order (extended makefiles first) and the task and env
+/// overrides which occurred while merging the makefiles (later events override earlier events).
+pub fn load_with_output(
+    file_name: &str,
+    force: bool,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+    load_options: &LoadOptions,
+) -> Result<LoadOutput, DescriptorError> {
+    load_with_output_from_reader(
+        file_name,
+        force,
+        env_map,
+        experimental,
+        load_options,
+        std::io::stdin(),
+    )
+}
+
+/// Loads the tasks descriptor (same as load_with_output) reading the stdin makefile content
+/// from the provided reader.
+fn load_with_output_from_reader<R: Read>(
+    file_name: &str,
+    force: bool,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+    load_options: &LoadOptions,
+    stdin: R,
+) -> Result<LoadOutput, DescriptorError> {
+    let stdin_descriptor = read_root_stdin_descriptor(file_name, stdin)?;
+
+    let load_trace = RefCell::new(vec![]);
+    MERGE_EVENTS.with(|events| *events.borrow_mut() = Some(vec![]));
+
+    let config = load_with_external_config(
+        &|| {
+            // the external descriptor may be loaded more than once (core tasks modifications)
+            load_trace.borrow_mut().clear();
+            MERGE_EVENTS.with(|events| *events.borrow_mut() = Some(vec![]));
+
+            load_root_descriptor(
+                file_name,
+                &stdin_descriptor,
+                force,
+                true,
+                load_options.strict,
+                true,
+                Some(&load_trace),
+            )
+        },
+        env_map,
+        experimental,
+        true,
+        load_options,
+    );
+
+    // collection is disabled even if loading failed
+    let merge_events = MERGE_EVENTS
+        .with(|events| events.borrow_mut().take())
+        .unwrap_or(vec![]);
+    let config = config?;
+
+    let load_trace = load_trace.into_inner();
+    debug!("Makefiles load order: {:#?}", &load_trace);
+    debug!("Merge events: {:#?}", &merge_events);
+
+    Ok(LoadOutput {
+        config,
+        load_trace,
+        merge_events,
+    })
+}
+
+/// Transforms the provided env key/value, returning None removes the key from the env.
+pub type EnvTransform = Box<dyn Fn(&str, &EnvValue) -> Option<EnvValue>>;
+
+fn transform_env(
+    env: IndexMap<String, EnvValue>,
+    env_transform: &EnvTransform,
+) -> IndexMap<String, EnvValue> {
+    let mut transformed_env = IndexMap::new();
+
+    for (key, value) in env.iter() {
+        match env_transform(key, value) {
+            Some(transformed_value) => {
+                transformed_env.insert(key.to_string(), transformed_value);
+            }
+            None => debug!("Env key: {} removed by env transform.", &key),
+        }
+    }
+
+    transformed_env
+}
+
+/// Loads the tasks descriptor, same as load, and applies the optional env transform on every
+/// key/value of the final merged env.
+pub fn load_with_env_transform(
+    file_name: &str,
+    force: bool,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+    env_transform: Option<EnvTransform>,
+    load_options: &LoadOptions,
+) -> Result<Config, DescriptorError> {
+    let mut config = load(file_name, force, env_map, experimental, load_options)?;
+
+    if let Some(ref env_transform) = env_transform {
+        config.env = transform_env(config.env, env_transform);
+    }
+
+    Ok(config)
+}
+
+/// Loads the tasks descriptor, same as load, materializes its env (env files, env scripts and env
+/// values) on a copy of the process env and invokes the provided handler for every ${} variable
+/// which the env expansion left unresolved.<br>
+/// The handler is invoked once for each unresolved variable name.<br>
+/// Values which are only expanded later (for example the task env) are not checked.
+pub fn load_with_unresolved_handler(
+    file_name: &str,
+    force: bool,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+    on_unresolved: impl Fn(&str),
+    load_options: &LoadOptions,
+) -> Result<Config, DescriptorError> {
+    let config = load(file_name, force, env_map, experimental, load_options)?;
+
+    for variable in environment::get_unresolved_env_variables(&config) {
+        debug!("Unresolved env variable: {}", &variable);
+        on_unresolved(&variable);
+    }
+
     Ok(config)
 }
+
+/// Loads the tasks descriptor from the provided makefile text and merges it with the
+/// internal descriptors, same as when loading a makefile from the file system.<br>
+/// Relative env_files and extend paths are resolved from the current directory.
+pub fn load_from_str(
+    descriptor: &str,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+) -> Result<Config, DescriptorError> {
+    load_from_str_with_base_path(descriptor, ".", env_map, experimental)
+}
+
+/// Loads the tasks descriptor from the provided makefile text and merges it with the
+/// internal descriptors.<br>
+/// Relative env_files and extend paths are resolved from the provided base path, while the
+/// makefile itself is identified (task sources, extend chain) as <string>.
+pub fn load_from_str_with_base_path(
+    descriptor: &str,
+    base_path: &str,
+    env_map: Option<Vec<String>>,
+    experimental: bool,
+) -> Result<Config, DescriptorError> {
+    let base_path_string = canonicalize_or(base_path, base_path);
+
+    load_with_external_config(
+        &|| {
+            parse_external_descriptor(
+                descriptor,
+                STRING_DESCRIPTOR_PATH,
+                &base_path_string,
+                &vec![STRING_DESCRIPTOR_PATH.to_string()],
+                false,
+                true,
+                None,
+                None,
+            )
+        },
+        env_map,
+        experimental,
+        true,
+        &LoadOptions::new(),
+    )
+}
+
+fn remove_current_task_env(env: IndexMap<String, EnvValue>) -> IndexMap<String, EnvValue> {
+    env.into_iter()
+        .filter(|(key, _)| !key.starts_with("CARGO_MAKE_CURRENT_TASK_"))
+        .collect()
+}
+
+/// Serializes the config to a toml makefile with sorted keys.
+fn flatten_config(mut config: Config) -> Result<String, DescriptorError> {
+    config.env = remove_current_task_env(config.env);
+    for task in config.tasks.values_mut() {
+        if let Some(env) = task.env.take() {
+            task.env = Some(remove_current_task_env(env));
+        }
+    }
+
+    // toml values hold sorted tables, so the output is deterministic
+    match toml::Value::try_from(&config) {
+        Ok(value) => match toml::to_string(&value) {
+            Ok(descriptor) => Ok(descriptor),
+            Err(error) => Err(DescriptorError::Serialize(error.to_string())),
+        },
+        Err(error) => Err(DescriptorError::Serialize(error.to_string())),
+    }
+}
+
+/// Loads the tasks descriptor (including the extended makefiles, workspace makefiles and core
+/// tasks) and returns the merged config as a single self contained toml makefile.
+pub fn flatten(file_name: &str) -> Result<String, DescriptorError> {
+    let config = load(file_name, true, None, false, &LoadOptions::new())?;
+
+    flatten_config(config)
+}
+
+/// Returns the first of the provided makefile names (in order) which exists in the base directory.<br>
+/// Returns None in case none of the makefiles exist.
+pub fn find_descriptor_file(base_path: &str, file_names: &[&str]) -> Option<String> {
+    for file_name in file_names {
+        if Path::new(base_path).join(file_name).is_file() {
+            debug!("Found descriptor file: {}", &file_name);
+            return Some(file_name.to_string());
+        }
+    }
+
+    None
+}
+
+/// Validates the tasks descriptor by running the full loading pipeline (min version check,
+/// parsing, extending and merging) without invoking the load scripts.<br>
+/// The process env (including the active profile) is restored once the descriptor is validated.<br>
+/// Returns an error in case the descriptor (or any of the extended descriptors) is not valid.
+pub fn validate(file_name: &str, force: bool) -> Result<(), DescriptorError> {
+    let stdin_descriptor = read_root_stdin_descriptor(file_name, std::io::stdin())?;
+    let previous_env: IndexMap<String, String> = env::vars().collect();
+
+    let result = load_with_external_config(
+        &|| {
+            load_root_descriptor(
+                file_name,
+                &stdin_descriptor,
+                force,
+                false,
+                false,
+                false,
+                None,
+            )
+        },
+        None,
+        false,
+        false,
+        &LoadOptions::new(),
+    );
+
+    // restore the process env (core tasks namespace, default profile)
+    for (key, _) in env::vars() {
+        if !previous_env.contains_key(&key) {
+            envmnt::remove(&key);
+        }
+    }
+    envmnt::set_all(&previous_env);
+
+    result?;
+
+    Ok(())
+}
+
+/// Loads the tasks descriptor (same as when running cargo-make) and returns the merged tasks
+/// sorted by name.<br>
+/// Private tasks are only returned if include_private is true.
+pub fn list_tasks(
+    file_name: &str,
+    include_private: bool,
+) -> Result<Vec<(String, Task)>, DescriptorError> {
+    let config = load(file_name, true, None, false, &LoadOptions::new())?;
+
+    let mut tasks: Vec<(String, Task)> = config
+        .tasks
+        .into_iter()
+        .filter(|(_, task)| include_private || !task.private.unwrap_or(false))
+        .collect();
+    tasks.sort_by(|first, second| first.0.cmp(&second.0));
+
+    Ok(tasks)
+}
+
+/// Returns the name and deprecation message of all the deprecated tasks.<br>
+/// Tasks deprecated without a message have an empty message.
+pub fn deprecated_tasks(config: &Config) -> Vec<(String, String)> {
+    let mut deprecated = vec![];
+
+    for (task_name, task) in config.tasks.iter() {
+        match task.deprecated {
+            Some(DeprecationInfo::Boolean(true)) => {
+                deprecated.push((task_name.to_string(), "".to_string()))
+            }
+            Some(DeprecationInfo::Message(ref message)) => {
+                deprecated.push((task_name.to_string(), message.to_string()))
+            }
+            _ => (),
+        }
+    }
+
+    deprecated
+}
+
+fn get_task_problems(name: &str, task: &Task) -> Vec<String> {
+    let mut problems = vec![];
+
+    if task.command.is_some() && task.script.is_some() {
+        problems.push("Both command and script are defined.".to_string());
+    }
+    if task.run_task.is_some() && task.command.is_some() {
+        problems.push("Both run_task and command are defined.".to_string());
+    }
+    if task.run_task.is_some() && task.script.is_some() {
+        problems.push("Both run_task and script are defined.".to_string());
+    }
+
+    if let Some(ref dependencies) = task.dependencies {
+        if dependencies
+            .iter()
+            .any(|dependency| dependency.trim().is_empty())
+        {
+            problems.push("Empty dependency name defined.".to_string());
+        }
+    }
+
+    if let Some(ref extend) = task.extend {
+        if extend.trim().is_empty() {
+            problems.push("Empty extend task name defined.".to_string());
+        } else if extend == name {
+            problems.push(format!("Task extends itself: {}", extend));
+        }
+    }
+
+    problems
+}
+
+/// Validates the task in isolation (without loading any makefile) and returns all the problems
+/// found, such as conflicting command, script and run_task definitions, empty dependency names
+/// and a task which extends itself.
+pub fn validate_task(name: &str, task: &Task) -> Result<(), Vec<String>> {
+    let problems = get_task_problems(name, task);
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Returns the names of all the tasks tagged with the provided tag (in the config tasks order).
+pub fn tasks_with_tag(config: &Config, tag: &str) -> Vec<String> {
+    config
+        .tasks
+        .iter()
+        .filter(|(_, task)| match task.tags {
+            Some(ref tags) => tags.iter().any(|task_tag| task_tag == tag),
+            None => false,
+        })
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Returns the tasks defined by the internal base descriptor (the minimal core tasks which are
+/// used when the stable core tasks are not loaded).
+pub fn base_tasks() -> IndexMap<String, Task> {
+    BASE_CONFIG.tasks.clone()
+}
+
+/// Returns the tasks defined by the internal stable descriptor (the core tasks).
+pub fn stable_tasks() -> IndexMap<String, Task> {
+    STABLE_CONFIG.tasks.clone()
+}
+
+/// Returns the tasks defined by the internal experimental (beta) descriptor, which are only
+/// merged into the core tasks when running with the experimental flag.
+pub fn experimental_tasks() -> IndexMap<String, Task> {
+    EXPERIMENTAL_CONFIG.tasks.clone()
+}
+
+/// Returns the path of the makefile which provided the final definition of the task.<br>
+/// Returns None for unknown tasks and for internal core tasks.
+pub fn resolve_task_origin(config: &Config, task: &str) -> Option<String> {
+    match config.tasks.get(task) {
+        Some(task_config) => task_config.get_source_file(),
+        None => None,
+    }
+}
+
+/// Returns the config section default task name.<br>
+/// In case the core tasks are namespaced (via modify_core_tasks), the default task is resolved
+/// to the namespaced core task (falling back to the task name as is).<br>
+/// Returns None in case no default task is defined or the task does not exist.
+pub fn resolve_default_task(config: &Config) -> Option<String> {
+    let default_task = match config.config.default_task {
+        Some(ref default_task) => default_task,
+        None => return None,
+    };
+
+    let prefix = match config.config.modify_core_tasks {
+        Some(ref modify_config) => modify_config.get_namespace_prefix(),
+        None => "".to_string(),
+    };
+
+    let mut candidates = vec![];
+    if !prefix.is_empty() && !default_task.starts_with(&prefix) {
+        candidates.push(format!("{}{}", &prefix, default_task));
+    }
+    candidates.push(default_task.to_string());
+
+    match candidates
+        .into_iter()
+        .find(|name| config.tasks.contains_key(name))
+    {
+        Some(name) => Some(name),
+        None => {
+            warn!("Default task: {} not found.", default_task);
+            None
+        }
+    }
+}
@@ -1,6 +1,11 @@
 use super::*;
 
-use crate::types::{ExtendOptions, InstallCrate};
+use crate::types::{
+    DescriptorError, EnvScriptInfo, EnvSetupOrder, EnvValueConditioned, EnvValueUnset,
+    ExtendOptions, ExtendRelativeTo, FileScriptValue, InstallCrate, MergeEvent, MergeSection,
+    RunTaskInfo, TaskCondition,
+};
+use std::time::{Duration, Instant};
 
 #[test]
 fn merge_env_both_empty() {
@@ -197,835 +202,8859 @@ fn merge_env_both_skip_current_task_env() {
     };
 }
 
+#[test]
+fn merge_env_list_append() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::List(vec!["1".to_string(), "2".to_string()]),
+    );
+    map2.insert(
+        "test".to_string(),
+        EnvValue::Append(EnvValueAppend {
+            append: vec!["3".to_string()],
+        }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::List(ref value) => assert_eq!(
+            value,
+            &vec!["1".to_string(), "2".to_string(), "3".to_string()]
+        ),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_value_append() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), EnvValue::Value("1".to_string()));
+    map2.insert(
+        "test".to_string(),
+        EnvValue::Append(EnvValueAppend {
+            append: vec!["2".to_string()],
+        }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::List(ref value) => assert_eq!(value, &vec!["1".to_string(), "2".to_string()]),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_append_append() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::Append(EnvValueAppend {
+            append: vec!["1".to_string()],
+        }),
+    );
+    map2.insert(
+        "test".to_string(),
+        EnvValue::Append(EnvValueAppend {
+            append: vec!["2".to_string()],
+        }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Append(ref value) => {
+            assert_eq!(value.append, vec!["1".to_string(), "2".to_string()])
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_number_and_bool_override_value() {
+    let mut map1: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        PORT = "80"
+        DEBUG = false
+        TIMEOUT = 10
+        "#,
+    )
+    .unwrap();
+    let mut map2: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        PORT = 8080
+        DEBUG = true
+        TIMEOUT = "20"
+        "#,
+    )
+    .unwrap();
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 3);
+    match output.get("PORT").unwrap() {
+        &EnvValue::Number(value) => assert_eq!(value.to_string(), "8080"),
+        _ => panic!("wrong value type"),
+    };
+    match output.get("DEBUG").unwrap() {
+        &EnvValue::Boolean(value) => assert_eq!(value.to_string(), "true"),
+        _ => panic!("wrong value type"),
+    };
+    match output.get("TIMEOUT").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "20"),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_list_override_value() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), EnvValue::Value("1".to_string()));
+    map2.insert(
+        "test".to_string(),
+        EnvValue::List(vec!["2".to_string(), "3".to_string()]),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::List(ref value) => assert_eq!(value, &vec!["2".to_string(), "3".to_string()]),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_value_override_list() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::List(vec!["1".to_string(), "2".to_string()]),
+    );
+    map2.insert("test".to_string(), EnvValue::Value("3".to_string()));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "3"),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_list_override_list() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), EnvValue::List(vec!["1".to_string()]));
+    map2.insert("test".to_string(), EnvValue::List(vec!["2".to_string()]));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::List(ref value) => assert_eq!(value, &vec!["2".to_string()]),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_documented_overridden_by_value() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::Documented(EnvValueDocumented {
+            value: "1".to_string(),
+            description: "base description".to_string(),
+        }),
+    );
+    map2.insert("test".to_string(), EnvValue::Value("2".to_string()));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Documented(ref value) => {
+            assert_eq!(value.value, "2");
+            assert_eq!(value.description, "base description");
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_documented_overridden_by_documented() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::Documented(EnvValueDocumented {
+            value: "1".to_string(),
+            description: "base description".to_string(),
+        }),
+    );
+    map2.insert(
+        "test".to_string(),
+        EnvValue::Documented(EnvValueDocumented {
+            value: "2".to_string(),
+            description: "extended description".to_string(),
+        }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Documented(ref value) => {
+            assert_eq!(value.value, "2");
+            assert_eq!(value.description, "extended description");
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_documented_overridden_by_list() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert(
+        "test".to_string(),
+        EnvValue::Documented(EnvValueDocumented {
+            value: "1".to_string(),
+            description: "base description".to_string(),
+        }),
+    );
+    map2.insert("test".to_string(), EnvValue::List(vec!["2".to_string()]));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    let value = output.get("test").unwrap();
+    assert!(value.get_description().is_none());
+    match value {
+        &EnvValue::List(ref value) => assert_eq!(value, &vec!["2".to_string()]),
+        _ => panic!("wrong value type"),
+    };
+}
+
+fn create_conditional_env_value(value: &str) -> EnvValue {
+    let condition = TaskCondition {
+        fail_message: None,
+        profiles: None,
+        platforms: None,
+        channels: None,
+        env_set: Some(vec!["MUSL".to_string()]),
+        env_not_set: None,
+        env_true: None,
+        env_false: None,
+        env: None,
+        env_contains: None,
+        rust_version: None,
+        files_exist: None,
+        files_not_exist: None,
+    };
+
+    EnvValue::Conditional(EnvValueConditioned {
+        value: value.to_string(),
+        condition: Some(condition),
+    })
+}
+
+#[test]
+fn merge_env_conditional_not_overridden() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), create_conditional_env_value("musl"));
+    map2.insert("other".to_string(), EnvValue::Value("other".to_string()));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 2);
+    match output.get("test").unwrap() {
+        &EnvValue::Conditional(ref value) => {
+            assert_eq!(value.value, "musl");
+            assert_eq!(
+                value.condition.clone().unwrap().env_set.unwrap(),
+                vec!["MUSL".to_string()]
+            );
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_conditional_overrides_value() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), EnvValue::Value("gnu".to_string()));
+    map2.insert("test".to_string(), create_conditional_env_value("musl"));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Conditional(ref value) => assert_eq!(value.value, "musl"),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_value_overrides_conditional() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test".to_string(), create_conditional_env_value("musl"));
+    map2.insert("test".to_string(), EnvValue::Value("gnu".to_string()));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    assert_env_value(&output, "test", "gnu");
+}
+
+#[test]
+fn merge_env_unset_base_key() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test1".to_string(), EnvValue::Value("1".to_string()));
+    map1.insert("test2".to_string(), EnvValue::Value("2".to_string()));
+    map2.insert(
+        "test1".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: true }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    assert!(!output.contains_key("test1"));
+    assert!(output.contains_key("test2"));
+}
+
+#[test]
+fn merge_env_unset_not_defined_in_base() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test2".to_string(), EnvValue::Value("2".to_string()));
+    map2.insert(
+        "test1".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: true }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 2);
+    match output.get("test1").unwrap() {
+        &EnvValue::Unset(ref value) => assert!(value.unset),
+        _ => panic!("wrong value type"),
+    };
+    assert!(output.contains_key("test2"));
+}
+
+#[test]
+fn merge_env_unset_false_overrides() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("test1".to_string(), EnvValue::Value("1".to_string()));
+    map2.insert(
+        "test1".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: false }),
+    );
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test1").unwrap() {
+        &EnvValue::Unset(ref value) => assert!(!value.unset),
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_unset_profile_key() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    let mut profile1 = IndexMap::<String, EnvValue>::new();
+    profile1.insert("test1".to_string(), EnvValue::Value("1".to_string()));
+    profile1.insert("test2".to_string(), EnvValue::Value("2".to_string()));
+    map1.insert("profile".to_string(), EnvValue::Profile(profile1));
+    let mut profile2 = IndexMap::<String, EnvValue>::new();
+    profile2.insert(
+        "test1".to_string(),
+        EnvValue::Unset(EnvValueUnset { unset: true }),
+    );
+    map2.insert("profile".to_string(), EnvValue::Profile(profile2));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("profile").unwrap() {
+        &EnvValue::Profile(ref profile) => {
+            assert_eq!(profile.len(), 1);
+            assert!(profile.contains_key("test2"));
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+fn assert_env_value(env: &IndexMap<String, EnvValue>, key: &str, expected: &str) {
+    match env.get(key).unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, expected),
+        _ => panic!("wrong value type"),
+    };
+}
+
+fn merge_env_with_path_keys_test(separator: &str) {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("PATH".to_string(), EnvValue::Value("/base".to_string()));
+    map1.insert("OTHER".to_string(), EnvValue::Value("base".to_string()));
+    map1.insert(
+        "BASE_ONLY".to_string(),
+        EnvValue::Value("/base".to_string()),
+    );
+    map2.insert("PATH".to_string(), EnvValue::Value("/extended".to_string()));
+    map2.insert("OTHER".to_string(), EnvValue::Value("extended".to_string()));
+
+    let output = merge_env_with_path_keys(
+        &mut map1,
+        &mut map2,
+        &vec!["PATH".to_string(), "BASE_ONLY".to_string()],
+        separator,
+    );
+    assert_eq!(output.len(), 3);
+    assert_env_value(&output, "PATH", &format!("/extended{}/base", separator));
+    assert_env_value(&output, "OTHER", "extended");
+    assert_env_value(&output, "BASE_ONLY", "/base");
+}
+
+#[test]
+fn merge_env_with_path_keys_unix_separator() {
+    merge_env_with_path_keys_test(":");
+}
+
+#[test]
+fn merge_env_with_path_keys_windows_separator() {
+    merge_env_with_path_keys_test(";");
+}
+
+#[test]
+fn merge_env_with_path_keys_profile() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    let mut profile1 = IndexMap::<String, EnvValue>::new();
+    profile1.insert("PATH".to_string(), EnvValue::Value("/base".to_string()));
+    profile1.insert("OTHER".to_string(), EnvValue::Value("base".to_string()));
+    map1.insert("profile".to_string(), EnvValue::Profile(profile1));
+    let mut profile2 = IndexMap::<String, EnvValue>::new();
+    profile2.insert("PATH".to_string(), EnvValue::Value("/extended".to_string()));
+    profile2.insert("OTHER".to_string(), EnvValue::Value("extended".to_string()));
+    map2.insert("profile".to_string(), EnvValue::Profile(profile2));
+
+    let output = merge_env_with_path_keys(&mut map1, &mut map2, &vec!["PATH".to_string()], ";");
+    match output.get("profile").unwrap() {
+        &EnvValue::Profile(ref profile) => {
+            assert_env_value(profile, "PATH", "/extended;/base");
+            assert_env_value(profile, "OTHER", "extended");
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_path_keys_not_defined() {
+    let mut map1 = IndexMap::<String, EnvValue>::new();
+    let mut map2 = IndexMap::<String, EnvValue>::new();
+
+    map1.insert("PATH".to_string(), EnvValue::Value("/base".to_string()));
+    map2.insert("PATH".to_string(), EnvValue::Value("/extended".to_string()));
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_env_value(&output, "PATH", "/extended");
+}
+
+#[test]
+fn merge_external_configs_path_env_keys() {
+    let mut parent_env = IndexMap::<String, EnvValue>::new();
+    parent_env.insert("PATH".to_string(), EnvValue::Value("/base".to_string()));
+    parent_env.insert("OTHER".to_string(), EnvValue::Value("base".to_string()));
+    let mut parent_config = ExternalConfig::new();
+    parent_config.env = Some(parent_env);
+
+    let mut env = IndexMap::<String, EnvValue>::new();
+    env.insert("PATH".to_string(), EnvValue::Value("/extended".to_string()));
+    env.insert("OTHER".to_string(), EnvValue::Value("extended".to_string()));
+    let mut config_section = ConfigSection::new();
+    config_section.path_env_keys = Some(vec!["PATH".to_string()]);
+    let mut config = ExternalConfig::new();
+    config.config = Some(config_section);
+    config.env = Some(env);
+
+    let merged_config = merge_external_configs(config, parent_config);
+    let merged_env = merged_config.env.unwrap();
+
+    assert_env_value(
+        &merged_env,
+        "PATH",
+        &format!("/extended{}/base", get_path_separator()),
+    );
+    assert_env_value(&merged_env, "OTHER", "extended");
+}
+
+#[test]
+fn check_task_min_version_none() {
+    let result = check_task_min_version(&Task::new());
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_task_min_version_older() {
+    let mut task = Task::new();
+    task.min_version = Some("0.1.0".to_string());
+
+    let result = check_task_min_version(&task);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_task_min_version_newer() {
+    let mut task = Task::new();
+    task.min_version = Some("999.999.999".to_string());
+
+    match check_task_min_version(&task).unwrap_err() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.999.999"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn merge_tasks_min_version() {
+    let mut base = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.min_version = Some("999.999.999".to_string());
+    base.insert("test".to_string(), task);
+    let mut extended = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    extended.insert("test".to_string(), task);
+
+    let merged_tasks = merge_tasks(base, extended, false);
+
+    let task = merged_tasks.get("test").unwrap();
+    assert_eq!(task.min_version.clone().unwrap(), "999.999.999");
+    assert_eq!(task.command.clone().unwrap(), "echo");
+}
+
+#[test]
+fn merge_empty() {
+    let merged_config = merge(ExternalConfig::new(), ExternalConfig::new());
+
+    assert!(merged_config.extend.is_none());
+    assert!(merged_config.env_files.unwrap().is_empty());
+    assert!(merged_config.env.unwrap().is_empty());
+    assert!(merged_config.env_scripts.unwrap().is_empty());
+    assert!(merged_config.tasks.unwrap().is_empty());
+}
+
+#[test]
+fn merge_env_overlay_wins() {
+    let mut base_env = IndexMap::<String, EnvValue>::new();
+    base_env.insert("SHARED".to_string(), EnvValue::Value("base".to_string()));
+    base_env.insert("BASE".to_string(), EnvValue::Value("base".to_string()));
+    let mut base = ExternalConfig::new();
+    base.env = Some(base_env);
+
+    let mut overlay_env = IndexMap::<String, EnvValue>::new();
+    overlay_env.insert("SHARED".to_string(), EnvValue::Value("overlay".to_string()));
+    overlay_env.insert(
+        "OVERLAY".to_string(),
+        EnvValue::Value("overlay".to_string()),
+    );
+    let mut overlay = ExternalConfig::new();
+    overlay.env = Some(overlay_env);
+
+    let merged_env = merge(base, overlay).env.unwrap();
+
+    assert_eq!(merged_env.len(), 3);
+    assert_env_value(&merged_env, "SHARED", "overlay");
+    assert_env_value(&merged_env, "BASE", "base");
+    assert_env_value(&merged_env, "OVERLAY", "overlay");
+}
+
+#[test]
+fn merge_env_files_overlay_first() {
+    let mut base = ExternalConfig::new();
+    base.env_files = Some(vec![
+        EnvFile::Path("./src/lib/test/test_files/env.env".to_string()),
+        EnvFile::Path("./src/lib/test/test_files/profile.env".to_string()),
+    ]);
+    let mut overlay = ExternalConfig::new();
+    overlay.env_files = Some(vec![EnvFile::Path(
+        "./src/lib/test/test_files/profile.env".to_string(),
+    )]);
+
+    let merged_env_files = merge(base, overlay).env_files.unwrap();
+
+    assert_eq!(merged_env_files.len(), 2);
+    match merged_env_files[0] {
+        EnvFile::Path(ref path) => assert_eq!(path, "./src/lib/test/test_files/profile.env"),
+        _ => panic!("wrong env file type"),
+    };
+    match merged_env_files[1] {
+        EnvFile::Path(ref path) => assert_eq!(path, "./src/lib/test/test_files/env.env"),
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn merge_env_scripts_overlay_first() {
+    let mut base = ExternalConfig::new();
+    base.env_scripts = Some(vec![EnvScript::Text("base".to_string())]);
+    let mut overlay = ExternalConfig::new();
+    overlay.env_scripts = Some(vec![EnvScript::Text("overlay".to_string())]);
+
+    let merged_env_scripts = merge(base, overlay).env_scripts.unwrap();
+
+    assert_eq!(merged_env_scripts.len(), 2);
+    match merged_env_scripts[0] {
+        EnvScript::Text(ref script) => assert_eq!(script, "overlay"),
+        _ => panic!("wrong env script type"),
+    };
+    match merged_env_scripts[1] {
+        EnvScript::Text(ref script) => assert_eq!(script, "base"),
+        _ => panic!("wrong env script type"),
+    };
+}
+
+#[test]
+fn merge_tasks_overlay_wins() {
+    let mut base_tasks = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.command = Some("base".to_string());
+    task.description = Some("base description".to_string());
+    base_tasks.insert("shared".to_string(), task);
+    base_tasks.insert("base".to_string(), Task::new());
+    let mut base = ExternalConfig::new();
+    base.tasks = Some(base_tasks);
+
+    let mut overlay_tasks = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.command = Some("overlay".to_string());
+    overlay_tasks.insert("shared".to_string(), task);
+    overlay_tasks.insert("overlay".to_string(), Task::new());
+    let mut overlay = ExternalConfig::new();
+    overlay.tasks = Some(overlay_tasks);
+
+    let merged_tasks = merge(base, overlay).tasks.unwrap();
+
+    assert_eq!(merged_tasks.len(), 3);
+    assert!(merged_tasks.contains_key("base"));
+    assert!(merged_tasks.contains_key("overlay"));
+    let task = merged_tasks.get("shared").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "overlay");
+    assert_eq!(task.description.clone().unwrap(), "base description");
+}
+
+#[test]
+fn merge_config_overlay_wins() {
+    let mut base_config_section = ConfigSection::new();
+    base_config_section.init_task = Some("base_init".to_string());
+    base_config_section.end_task = Some("base_end".to_string());
+    let mut base = ExternalConfig::new();
+    base.config = Some(base_config_section);
+
+    let mut overlay_config_section = ConfigSection::new();
+    overlay_config_section.end_task = Some("overlay_end".to_string());
+    let mut overlay = ExternalConfig::new();
+    overlay.config = Some(overlay_config_section);
+
+    let merged_config_section = merge(base, overlay).config.unwrap();
+
+    assert_eq!(merged_config_section.init_task.unwrap(), "base_init");
+    assert_eq!(merged_config_section.end_task.unwrap(), "overlay_end");
+}
+
+#[test]
+fn merge_env_files_no_duplicates() {
+    let mut base = vec![EnvFile::Path(
+        "./src/lib/test/test_files/env.env".to_string(),
+    )];
+    let mut extended = vec![EnvFile::Path(
+        "./src/lib/test/test_files/profile.env".to_string(),
+    )];
+
+    let output = merge_env_files(&mut base, &mut extended);
+
+    assert_eq!(output.len(), 2);
+    match output[0] {
+        EnvFile::Path(ref path) => assert_eq!(path, "./src/lib/test/test_files/profile.env"),
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn merge_env_files_duplicate_paths() {
+    let mut base = vec![
+        EnvFile::Path("./src/lib/test/test_files/env.env".to_string()),
+        EnvFile::Path("./src/lib/test/test_files/profile.env".to_string()),
+    ];
+    let mut extended = vec![EnvFile::Path(
+        "./src/lib/test/../test/test_files/profile.env".to_string(),
+    )];
+
+    let output = merge_env_files(&mut base, &mut extended);
+
+    assert_eq!(output.len(), 2);
+    match output[0] {
+        EnvFile::Path(ref path) => {
+            assert_eq!(path, "./src/lib/test/../test/test_files/profile.env")
+        }
+        _ => panic!("wrong env file type"),
+    };
+    match output[1] {
+        EnvFile::Path(ref path) => assert_eq!(path, "./src/lib/test/test_files/env.env"),
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn merge_env_files_duplicate_path_and_info() {
+    let mut base = vec![EnvFile::Path(
+        "./src/lib/test/test_files/env.env".to_string(),
+    )];
+    let mut info = EnvFileInfo::new("./test_files/env.env".to_string());
+    info.base_path = Some("./src/lib/test".to_string());
+    let mut extended = vec![EnvFile::Info(info)];
+
+    let output = merge_env_files(&mut base, &mut extended);
+
+    assert_eq!(output.len(), 1);
+    match output[0] {
+        EnvFile::Info(ref info) => {
+            assert_eq!(info.path, "./test_files/env.env");
+            assert_eq!(info.base_path.clone().unwrap(), "./src/lib/test");
+        }
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn merge_env_files_same_path_different_profile() {
+    let mut base = vec![EnvFile::Path(
+        "./src/lib/test/test_files/env.env".to_string(),
+    )];
+    let mut info = EnvFileInfo::new("./src/lib/test/test_files/env.env".to_string());
+    info.profile = Some("production".to_string());
+    let mut extended = vec![EnvFile::Info(info)];
+
+    let output = merge_env_files(&mut base, &mut extended);
+
+    assert_eq!(output.len(), 2);
+}
+
+fn create_env_script(script: &str, order: Option<i32>) -> EnvScript {
+    match order {
+        Some(_) => EnvScript::Info(EnvScriptInfo {
+            script: script.to_string(),
+            order,
+            script_runner: None,
+            script_runner_args: None,
+        }),
+        None => EnvScript::Text(script.to_string()),
+    }
+}
+
+fn get_env_scripts_text(env_scripts: &Vec<EnvScript>) -> Vec<String> {
+    env_scripts
+        .iter()
+        .map(|env_script| env_script.get_script())
+        .collect()
+}
+
+#[test]
+fn merge_external_configs_env_scripts_script_runner() {
+    let parent_config: ExternalConfig = toml::from_str(
+        r#"
+        env_scripts = [
+            { script = "print('base')", script_runner = "python3", script_runner_args = ["-u"] },
+        ]
+        "#,
+    )
+    .unwrap();
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        env_scripts = ["echo extended"]
+        "#,
+    )
+    .unwrap();
+
+    let merged_config = merge_external_configs(config, parent_config);
+    let env_scripts = merged_config.env_scripts.unwrap();
+
+    assert_eq!(env_scripts.len(), 2);
+    assert_eq!(env_scripts[0].get_script(), "echo extended");
+    assert!(env_scripts[0].get_script_runner().is_none());
+    assert_eq!(env_scripts[1].get_script(), "print('base')");
+    assert_eq!(env_scripts[1].get_script_runner().unwrap(), "python3");
+    assert_eq!(
+        env_scripts[1].get_script_runner_args().unwrap(),
+        vec!["-u".to_string()]
+    );
+}
+
+#[test]
+fn merge_env_scripts_unordered() {
+    let mut base = vec![
+        create_env_script("base1", None),
+        create_env_script("base2", None),
+    ];
+    let mut extended = vec![
+        create_env_script("extended1", None),
+        create_env_script("extended2", None),
+    ];
+
+    let output = merge_env_scripts(&mut base, &mut extended, false);
+
+    assert_eq!(
+        get_env_scripts_text(&output),
+        vec!["extended1", "extended2", "base1", "base2"]
+    );
+}
+
+#[test]
+fn merge_env_scripts_ordered_base_first() {
+    let mut base = vec![
+        create_env_script("base1", Some(-1)),
+        create_env_script("base2", None),
+    ];
+    let mut extended = vec![
+        create_env_script("extended1", None),
+        create_env_script("extended2", Some(1)),
+    ];
+
+    let output = merge_env_scripts(&mut base, &mut extended, false);
+
+    assert_eq!(
+        get_env_scripts_text(&output),
+        vec!["base1", "extended1", "base2", "extended2"]
+    );
+}
+
+#[test]
+fn merge_env_scripts_same_order() {
+    let mut base = vec![
+        create_env_script("base1", Some(5)),
+        create_env_script("base2", Some(0)),
+    ];
+    let mut extended = vec![
+        create_env_script("extended1", Some(5)),
+        create_env_script("extended2", None),
+    ];
+
+    let output = merge_env_scripts(&mut base, &mut extended, false);
+
+    assert_eq!(
+        get_env_scripts_text(&output),
+        vec!["extended2", "base2", "extended1", "base1"]
+    );
+}
+
+#[test]
+fn merge_env_scripts_duplicates_no_dedup() {
+    let mut base = vec![EnvScript::Text("echo setup".to_string())];
+    let mut extended = vec![EnvScript::Text("echo setup".to_string())];
+
+    let output = merge_env_scripts(&mut base, &mut extended, false);
+
+    assert_eq!(output.len(), 2);
+}
+
+#[test]
+fn merge_env_scripts_duplicates_dedup() {
+    let mut base = vec![
+        EnvScript::Text("echo base".to_string()),
+        EnvScript::Text("echo setup".to_string()),
+    ];
+    let mut extended = vec![
+        EnvScript::Text("echo setup".to_string()),
+        EnvScript::Text("echo extended".to_string()),
+    ];
+
+    let output = merge_env_scripts(&mut base, &mut extended, true);
+
+    assert_eq!(
+        get_env_scripts_text(&output),
+        vec!["echo setup", "echo extended", "echo base"]
+    );
+}
+
+#[test]
+fn merge_env_scripts_whitespace_duplicates_dedup() {
+    let mut base = vec![EnvScript::Text("\n  echo setup\n".to_string())];
+    let mut extended = vec![
+        EnvScript::Text("echo setup  ".to_string()),
+        EnvScript::Text("echo  setup".to_string()),
+    ];
+
+    let output = merge_env_scripts(&mut base, &mut extended, true);
+
+    assert_eq!(
+        get_env_scripts_text(&output),
+        vec!["echo setup  ", "echo  setup"]
+    );
+}
+
+#[test]
+fn merge_env_scripts_different_runner_dedup() {
+    let mut base = vec![EnvScript::Text("echo setup".to_string())];
+    let mut extended = vec![EnvScript::Info(EnvScriptInfo {
+        script: "echo setup".to_string(),
+        order: None,
+        script_runner: Some("@shell".to_string()),
+        script_runner_args: None,
+    })];
+
+    let output = merge_env_scripts(&mut base, &mut extended, true);
+
+    assert_eq!(output.len(), 2);
+}
+
+#[test]
+fn merge_external_configs_dedup_env_scripts() {
+    let mut config_section = ConfigSection::new();
+    config_section.dedup_env_scripts = Some(true);
+    let mut base_config = ExternalConfig::new();
+    base_config.env_scripts = Some(vec![EnvScript::Text("echo setup".to_string())]);
+    let mut extended_config = ExternalConfig::new();
+    extended_config.config = Some(config_section);
+    extended_config.env_scripts = Some(vec![EnvScript::Text(" echo setup ".to_string())]);
+
+    let output = merge_external_configs(extended_config, base_config);
+
+    assert_eq!(
+        get_env_scripts_text(&output.env_scripts.unwrap()),
+        vec![" echo setup "]
+    );
+}
+
 #[test]
 fn merge_tasks_both_empty() {
+    let map1 = IndexMap::<String, Task>::new();
+    let map2 = IndexMap::<String, Task>::new();
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 0);
+}
+
+#[test]
+fn merge_tasks_first_empty() {
+    let map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
+    task.command = Some("test".to_string());
+
+    map2.insert("test".to_string(), task);
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 1);
+    let task = output.get("test").unwrap();
+    assert!(task.disabled.is_none());
+    assert!(task.alias.is_none());
+    assert!(task.linux_alias.is_none());
+    assert!(task.windows_alias.is_none());
+    assert!(task.mac_alias.is_none());
+    assert!(task.install_crate.is_some());
+    assert!(task.install_script.is_none());
+    assert!(task.command.is_some());
+    assert!(task.args.is_none());
+    assert!(task.script.is_none());
+    assert!(task.dependencies.is_none());
+    assert!(task.linux.is_none());
+    assert!(task.windows.is_none());
+    assert!(task.mac.is_none());
+}
+
+#[test]
+fn merge_tasks_second_empty() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let map2 = IndexMap::<String, Task>::new();
+
+    let mut task = Task::new();
+    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
+    task.command = Some("test".to_string());
+
+    map1.insert("test".to_string(), task);
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 1);
+    let task = output.get("test").unwrap();
+    assert!(task.disabled.is_none());
+    assert!(task.alias.is_none());
+    assert!(task.linux_alias.is_none());
+    assert!(task.windows_alias.is_none());
+    assert!(task.mac_alias.is_none());
+    assert!(task.install_crate.is_some());
+    assert!(task.install_script.is_none());
+    assert!(task.command.is_some());
+    assert!(task.args.is_none());
+    assert!(task.script.is_none());
+    assert!(task.dependencies.is_none());
+    assert!(task.linux.is_none());
+    assert!(task.windows.is_none());
+    assert!(task.mac.is_none());
+}
+
+#[test]
+fn merge_tasks_final_not_overridden() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task = Task::new();
+    task.command = Some("base".to_string());
+    task.is_final = Some(true);
+    map1.insert("final".to_string(), task);
+    let mut task = Task::new();
+    task.command = Some("base".to_string());
+    map1.insert("open".to_string(), task);
+
+    let mut task = Task::new();
+    task.command = Some("extended".to_string());
+    task.clear = Some(true);
+    map2.insert("final".to_string(), task.clone());
+    map2.insert("open".to_string(), task);
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 2);
+    assert_eq!(
+        output.get("final").unwrap().command.clone().unwrap(),
+        "base"
+    );
+    assert_eq!(
+        output.get("open").unwrap().command.clone().unwrap(),
+        "extended"
+    );
+}
+
+#[test]
+fn merge_tasks_final_extended() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task = Task::new();
+    task.command = Some("base".to_string());
+    map1.insert("test".to_string(), task);
+
+    let mut task = Task::new();
+    task.command = Some("extended".to_string());
+    task.is_final = Some(true);
+    map2.insert("test".to_string(), task);
+
+    let output = merge_tasks(map1, map2, false);
+    let task = output.get("test").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "extended");
+    assert!(task.is_final.unwrap());
+}
+
+#[test]
+fn merge_tasks_both_with_values() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
+    task1.command = Some("test".to_string());
+
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.command = Some("test".to_string());
+
+    map2.insert("test2".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 2);
+
+    let mut task = output.get("test").unwrap();
+    assert!(task.disabled.is_none());
+    assert!(task.alias.is_none());
+    assert!(task.linux_alias.is_none());
+    assert!(task.windows_alias.is_none());
+    assert!(task.mac_alias.is_none());
+    assert!(task.install_crate.is_some());
+    assert!(task.install_script.is_none());
+    assert!(task.command.is_some());
+    assert!(task.args.is_none());
+    assert!(task.script.is_none());
+    assert!(task.dependencies.is_none());
+    assert!(task.linux.is_none());
+    assert!(task.windows.is_none());
+    assert!(task.mac.is_none());
+
+    task = output.get("test2").unwrap();
+    assert!(task.disabled.is_none());
+    assert!(task.alias.is_none());
+    assert!(task.linux_alias.is_none());
+    assert!(task.windows_alias.is_none());
+    assert!(task.mac_alias.is_none());
+    assert!(task.install_crate.is_none());
+    assert!(task.install_script.is_none());
+    assert!(task.command.is_some());
+    assert!(task.args.is_none());
+    assert!(task.script.is_none());
+    assert!(task.dependencies.is_none());
+    assert!(task.linux.is_none());
+    assert!(task.windows.is_none());
+    assert!(task.mac.is_none());
+}
+
+fn create_tasks_map(names: Vec<&str>, command: &str) -> IndexMap<String, Task> {
+    let mut tasks = IndexMap::new();
+
+    for name in names {
+        let mut task = Task::new();
+        task.command = Some(command.to_string());
+        tasks.insert(name.to_string(), task);
+    }
+
+    tasks
+}
+
+fn get_task_names(tasks: &IndexMap<String, Task>) -> Vec<String> {
+    tasks.keys().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn merge_tasks_order_overridden_keep_position() {
+    let base = create_tasks_map(vec!["a", "b", "c"], "base");
+    let extended = create_tasks_map(vec!["d", "c", "a", "e"], "extended");
+
+    let output = merge_tasks(base, extended, false);
+
+    assert_eq!(get_task_names(&output), vec!["a", "b", "c", "d", "e"]);
+    assert_eq!(
+        output.get("a").unwrap().command.clone().unwrap(),
+        "extended"
+    );
+    assert_eq!(
+        output.get("c").unwrap().command.clone().unwrap(),
+        "extended"
+    );
+}
+
+#[test]
+fn merge_tasks_order_cleared_keep_position() {
+    let base = create_tasks_map(vec!["a", "b"], "base");
+    let mut extended = create_tasks_map(vec!["a"], "extended");
+    extended.get_mut("a").unwrap().clear = Some(true);
+
+    let output = merge_tasks(base, extended, false);
+
+    assert_eq!(get_task_names(&output), vec!["a", "b"]);
+}
+
+#[test]
+fn merge_tasks_order_stable_across_merge_grouping() {
+    let first = create_tasks_map(vec!["a", "b", "c"], "first");
+    let second = create_tasks_map(vec!["d", "b", "e"], "second");
+    let third = create_tasks_map(vec!["f", "a", "d"], "third");
+
+    let left_grouped = merge_tasks(
+        merge_tasks(first.clone(), second.clone(), false),
+        third.clone(),
+        false,
+    );
+    let right_grouped = merge_tasks(first, merge_tasks(second, third, false), false);
+
+    assert_eq!(
+        get_task_names(&left_grouped),
+        vec!["a", "b", "c", "d", "e", "f"]
+    );
+    assert_eq!(
+        get_task_names(&left_grouped),
+        get_task_names(&right_grouped)
+    );
+}
+
+#[test]
+fn merge_tasks_extend_task() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.disabled = Some(false);
+    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
+    task1.command = Some("test1".to_string());
+
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.disabled = Some(true);
+    task2.command = Some("test2".to_string());
+
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    assert!(task.disabled.is_some());
+    assert!(task.alias.is_none());
+    assert!(task.linux_alias.is_none());
+    assert!(task.windows_alias.is_none());
+    assert!(task.mac_alias.is_none());
+    assert!(task.install_crate.is_some());
+    assert!(task.install_script.is_none());
+    assert!(task.command.is_some());
+    assert!(task.args.is_none());
+    assert!(task.script.is_none());
+    assert!(task.dependencies.is_none());
+    assert!(task.linux.is_none());
+    assert!(task.windows.is_none());
+    assert!(task.mac.is_none());
+
+    let task_clone = task.clone();
+    assert!(task_clone.disabled.unwrap());
+    assert_eq!(
+        task_clone.install_crate.unwrap(),
+        InstallCrate::Value("my crate".to_string())
+    );
+    assert_eq!(task_clone.command.unwrap(), "test2");
+}
+
+#[test]
+fn merge_tasks_extend_task_inherits_base_values() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.description = Some("base".to_string());
+    task1.command = Some("cargo".to_string());
+    task1.args = Some(vec!["build".to_string()]);
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.script = Some(ScriptValue::Text(vec!["echo test".to_string()]));
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.description.clone().unwrap(), "base");
+    assert_eq!(task.command.clone().unwrap(), "cargo");
+    assert!(task.args.is_some());
+    assert!(task.script.is_some());
+}
+
+#[test]
+fn merge_tasks_deprecated() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.deprecated = Some(DeprecationInfo::Message("use other".to_string()));
+    map1.insert("inherited".to_string(), task1.clone());
+    map1.insert("overridden".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.command = Some("cargo".to_string());
+    map2.insert("inherited".to_string(), task2);
+    let mut task2 = Task::new();
+    task2.deprecated = Some(DeprecationInfo::Boolean(false));
+    map2.insert("overridden".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let mut task = output.get("inherited").unwrap();
+    assert_eq!(
+        task.deprecated.clone().unwrap(),
+        DeprecationInfo::Message("use other".to_string())
+    );
+    task = output.get("overridden").unwrap();
+    assert_eq!(
+        task.deprecated.clone().unwrap(),
+        DeprecationInfo::Boolean(false)
+    );
+}
+
+#[test]
+fn merge_tasks_clear_task_replaces_base_values() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.description = Some("base".to_string());
+    task1.command = Some("cargo".to_string());
+    task1.args = Some(vec!["build".to_string()]);
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.clear = Some(true);
+    task2.script = Some(ScriptValue::Text(vec!["echo test".to_string()]));
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let task = output.get("test").unwrap();
+    assert!(task.clear.unwrap());
+    assert!(task.description.is_none());
+    assert!(task.command.is_none());
+    assert!(task.args.is_none());
+    assert!(task.script.is_some());
+}
+
+#[test]
+fn merge_tasks_clear_task_env_not_merged() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    map1.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("BASE", "base"), ("BOTH", "base")]),
+    );
+    let mut task2 = create_task_with_env(vec![("BOTH", "extended")]);
+    task2.clear = Some(true);
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, true);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.env.clone().unwrap().len(), 1);
+    assert_eq!(get_task_env_value(&task, "BOTH"), "extended");
+}
+
+#[test]
+fn merge_tasks_source_file_overridden() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.command = Some("test1".to_string());
+    map1.insert("test".to_string(), task1);
+
+    let mut config1 = ExternalConfig::new();
+    config1.tasks = Some(map1);
+    let mut map1 = add_file_location_info(config1, "./base/Makefile.toml", "./base")
+        .tasks
+        .unwrap();
+
+    let mut task2 = Task::new();
+    task2.command = Some("test2".to_string());
+    map2.insert("test".to_string(), task2);
+    let mut task3 = Task::new();
+    task3.command = Some("test3".to_string());
+    map1.insert("base_only".to_string(), task3);
+    map1.get_mut("base_only")
+        .unwrap()
+        .set_source_file("./base/Makefile.toml");
+
+    let mut config2 = ExternalConfig::new();
+    config2.tasks = Some(map2);
+    let map2 = add_file_location_info(config2, "./Makefile.toml", ".")
+        .tasks
+        .unwrap();
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 2);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "test2");
+    assert_eq!(task.get_source_file().unwrap(), "./Makefile.toml");
+
+    let task = output.get("base_only").unwrap();
+    assert_eq!(task.get_source_file().unwrap(), "./base/Makefile.toml");
+}
+
+fn create_task_with_env(env: Vec<(&str, &str)>) -> Task {
+    let mut task_env = IndexMap::<String, EnvValue>::new();
+    for (key, value) in env {
+        task_env.insert(key.to_string(), EnvValue::Value(value.to_string()));
+    }
+
+    let mut task = Task::new();
+    task.env = Some(task_env);
+
+    task
+}
+
+fn get_task_env_value(task: &Task, key: &str) -> String {
+    match task.env.clone().unwrap().get(key).unwrap() {
+        EnvValue::Value(ref value) => value.to_string(),
+        _ => panic!("wrong value type"),
+    }
+}
+
+#[test]
+fn merge_tasks_task_env_no_merge() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    map1.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("BASE", "base"), ("BOTH", "base")]),
+    );
+    map2.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("EXTENDED", "extended"), ("BOTH", "extended")]),
+    );
+
+    let output = merge_tasks(map1, map2, false);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    let env = task.env.clone().unwrap();
+    assert_eq!(env.len(), 2);
+    assert!(!env.contains_key("BASE"));
+    assert_eq!(get_task_env_value(&task, "EXTENDED"), "extended");
+    assert_eq!(get_task_env_value(&task, "BOTH"), "extended");
+}
+
+#[test]
+fn merge_tasks_task_env_merge_base_only_keys() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    map1.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("BASE1", "base1"), ("BASE2", "base2")]),
+    );
+    map2.insert("test".to_string(), create_task_with_env(vec![]));
+
+    let output = merge_tasks(map1, map2, true);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.env.clone().unwrap().len(), 2);
+    assert_eq!(get_task_env_value(&task, "BASE1"), "base1");
+    assert_eq!(get_task_env_value(&task, "BASE2"), "base2");
+}
+
+#[test]
+fn merge_tasks_task_env_merge_extended_only_keys() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    map1.insert("test".to_string(), create_task_with_env(vec![]));
+    map2.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("EXTENDED1", "extended1"), ("EXTENDED2", "extended2")]),
+    );
+
+    let output = merge_tasks(map1, map2, true);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.env.clone().unwrap().len(), 2);
+    assert_eq!(get_task_env_value(&task, "EXTENDED1"), "extended1");
+    assert_eq!(get_task_env_value(&task, "EXTENDED2"), "extended2");
+}
+
+#[test]
+fn merge_tasks_task_env_merge_conflicting_keys() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    map1.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("BASE", "base"), ("BOTH", "base")]),
+    );
+    map2.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("EXTENDED", "extended"), ("BOTH", "extended")]),
+    );
+
+    let output = merge_tasks(map1, map2, true);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.env.clone().unwrap().len(), 3);
+    assert_eq!(get_task_env_value(&task, "BASE"), "base");
+    assert_eq!(get_task_env_value(&task, "EXTENDED"), "extended");
+    assert_eq!(get_task_env_value(&task, "BOTH"), "extended");
+}
+
+#[test]
+fn merge_tasks_task_env_merge_profile() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut base_profile = IndexMap::<String, EnvValue>::new();
+    base_profile.insert("BASE".to_string(), EnvValue::Value("base".to_string()));
+    base_profile.insert("BOTH".to_string(), EnvValue::Value("base".to_string()));
+    let mut base_task = create_task_with_env(vec![]);
+    base_task
+        .env
+        .as_mut()
+        .unwrap()
+        .insert("myprofile".to_string(), EnvValue::Profile(base_profile));
+    map1.insert("test".to_string(), base_task);
+
+    let mut extended_profile = IndexMap::<String, EnvValue>::new();
+    extended_profile.insert(
+        "EXTENDED".to_string(),
+        EnvValue::Value("extended".to_string()),
+    );
+    extended_profile.insert("BOTH".to_string(), EnvValue::Value("extended".to_string()));
+    let mut extended_task = create_task_with_env(vec![]);
+    extended_task
+        .env
+        .as_mut()
+        .unwrap()
+        .insert("myprofile".to_string(), EnvValue::Profile(extended_profile));
+    map2.insert("test".to_string(), extended_task);
+
+    let output = merge_tasks(map1, map2, true);
+    assert_eq!(output.len(), 1);
+
+    let task = output.get("test").unwrap();
+    let env = task.env.clone().unwrap();
+    match env.get("myprofile").unwrap() {
+        EnvValue::Profile(sub_env) => {
+            assert_eq!(sub_env.len(), 3);
+            match sub_env.get("BASE").unwrap() {
+                &EnvValue::Value(ref value_string) => assert_eq!(value_string, "base"),
+                _ => panic!("wrong value type"),
+            };
+            match sub_env.get("EXTENDED").unwrap() {
+                &EnvValue::Value(ref value_string) => assert_eq!(value_string, "extended"),
+                _ => panic!("wrong value type"),
+            };
+            match sub_env.get("BOTH").unwrap() {
+                &EnvValue::Value(ref value_string) => assert_eq!(value_string, "extended"),
+                _ => panic!("wrong value type"),
+            };
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_tasks_task_location_info_overridden() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = create_task_with_env(vec![("BASE", "base")]);
+    task1.initial_makefile = Some("./base/Makefile.toml".to_string());
+    task1.initial_makefile_dir = Some("./base".to_string());
+    map1.insert("test".to_string(), task1);
+    let mut task2 = Task::new();
+    task2.initial_makefile = Some("./Makefile.toml".to_string());
+    task2.initial_makefile_dir = Some(".".to_string());
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, true);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(get_task_env_value(&task, "BASE"), "base");
+    assert_eq!(task.env.clone().unwrap().len(), 1);
+    assert_eq!(task.initial_makefile.clone().unwrap(), "./Makefile.toml");
+    assert_eq!(task.initial_makefile_dir.clone().unwrap(), ".");
+}
+
+#[test]
+fn merge_tasks_override_without_env_preserves_base_env() {
+    let mut map1 = IndexMap::<String, Task>::new();
+
+    map1.insert(
+        "test".to_string(),
+        create_task_with_env(vec![("BASE", "base")]),
+    );
+
+    let mut external_config = ExternalConfig::new();
+    let mut tasks = IndexMap::new();
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    tasks.insert("test".to_string(), task);
+    external_config.tasks = Some(tasks);
+    let map2 = add_file_location_info(external_config, "./Makefile.toml", ".")
+        .tasks
+        .unwrap();
+
+    // location info is not stored in the env block
+    assert!(map2.get("test").unwrap().env.is_none());
+
+    let output = merge_tasks(map1, map2, false);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert_eq!(get_task_env_value(&task, "BASE"), "base");
+    assert_eq!(task.env.clone().unwrap().len(), 1);
+    assert_eq!(task.initial_makefile.clone().unwrap(), "./Makefile.toml");
+}
+
+#[test]
+#[ignore]
+fn load_descriptors_load_workspace_makefile() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./examples/workspace/Makefile.toml",
+    );
+    let config = load_descriptors(
+        &|| {
+            load_external_descriptor(
+                ".",
+                "./bad/bad.toml",
+                false,
+                true,
+                &vec![],
+                false,
+                true,
+                None,
+                None,
+            )
+        },
+        None,
+        false,
+        false,
+        None,
+        true,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    let task = config.tasks.get("workspace-echo");
+    assert!(task.is_some());
+}
+
+#[test]
+#[ignore]
+fn load_descriptors_skip_workspace_makefile() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./examples/workspace/Makefile.toml",
+    );
+    let config = load_descriptors(
+        &|| {
+            load_external_descriptor(
+                ".",
+                "./bad/bad.toml",
+                false,
+                true,
+                &vec![],
+                false,
+                true,
+                None,
+                None,
+            )
+        },
+        None,
+        false,
+        false,
+        None,
+        true,
+        &LoadOptions {
+            skip_workspace_makefile: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    assert!(config.tasks.get("workspace-echo").is_none());
+}
+
+#[test]
+#[ignore]
+fn load_descriptors_load_workspace_makefile_no_exists() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./examples/workspace/Makefile2.toml",
+    );
+    let config = load_descriptors(
+        &|| {
+            load_external_descriptor(
+                ".",
+                "./bad/bad.toml",
+                false,
+                true,
+                &vec![],
+                false,
+                true,
+                None,
+                None,
+            )
+        },
+        None,
+        false,
+        false,
+        None,
+        true,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    let task = config.tasks.get("workspace-echo");
+    assert!(task.is_none());
+}
+
+#[test]
+#[ignore]
+fn load_descriptors_no_load_workspace_makefile() {
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+    let config = load_descriptors(
+        &|| {
+            load_external_descriptor(
+                ".",
+                "./bad/bad.toml",
+                false,
+                true,
+                &vec![],
+                false,
+                true,
+                None,
+                None,
+            )
+        },
+        None,
+        false,
+        false,
+        None,
+        true,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let task = config.tasks.get("workspace-echo");
+    assert!(task.is_none());
+}
+
+#[test]
+#[ignore]
+fn load_no_stable() {
+    let config = load(
+        "./examples/skip_core_tasks.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_none());
+
+    assert!(config.env_scripts.is_empty());
+    assert!(config.env_files.is_empty());
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    task = config.tasks.get("default");
+    assert!(task.is_some());
+    task = config.tasks.get("init");
+    assert!(task.is_some());
+    task = config.tasks.get("end");
+    assert!(task.is_some());
+    task = config.tasks.get("wait");
+    assert!(task.is_none());
+    assert_eq!(config.config.init_task.unwrap(), "init");
+    assert_eq!(config.config.end_task.unwrap(), "end");
+}
+
+#[test]
+#[ignore]
+fn load_with_stable() {
+    let config = load(
+        "./examples/simple-example.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_some());
+
+    assert!(config.env_scripts.is_empty());
+    assert!(config.env_files.is_empty());
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    task = config.tasks.get("default");
+    assert!(task.is_some());
+    task = config.tasks.get("init");
+    assert!(task.is_some());
+    task = config.tasks.get("end");
+    assert!(task.is_some());
+    task = config.tasks.get("wait");
+    assert!(task.is_some());
+    assert_eq!(config.config.init_task.unwrap(), "init");
+    assert_eq!(config.config.end_task.unwrap(), "end");
+}
+
+#[test]
+#[ignore]
+fn load_with_modify() {
+    let config = load(
+        "./examples/modify_core_tasks.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_some());
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_none());
+    task = config.tasks.get("default::empty");
+    assert!(task.is_some());
+    assert!(task.clone().unwrap().private.unwrap());
+    task = config.tasks.get("default::init");
+    assert!(task.is_some());
+}
+
+#[test]
+#[should_panic]
+fn load_not_found() {
+    load(
+        "./examples/not-found.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn load_not_found_error() {
+    let result = load(
+        "./examples/not-found.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::FileNotFound(file) => assert!(file.ends_with("not-found.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_broken_makefile_error() {
+    let result = load(
+        "./src/lib/test/makefiles/broken_makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::Parse { file, .. } => assert!(file.ends_with("broken_makefile.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_internal_descriptors_no_stable() {
+    let config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    task = config.tasks.get("wait");
+    assert!(task.is_none());
+}
+
+#[test]
+fn load_internal_descriptors_with_stable() {
+    let config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    task = config.tasks.get("wait");
+    assert!(task.is_some());
+}
+
+#[test]
+fn load_internal_descriptors_default_to_workspace() {
+    let config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+
+    assert!(config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn merge_external_configs_default_to_workspace_child_overrides_parent() {
+    let mut parent_config_section = ConfigSection::new();
+    parent_config_section.default_to_workspace = Some(true);
+    let mut parent_config = ExternalConfig::new();
+    parent_config.config = Some(parent_config_section);
+
+    let mut config_section = ConfigSection::new();
+    config_section.default_to_workspace = Some(false);
+    let mut config = ExternalConfig::new();
+    config.config = Some(config_section);
+
+    let merged_config = merge_external_configs(config, parent_config);
+
+    assert!(!merged_config.config.unwrap().default_to_workspace.unwrap());
+}
+
+#[test]
+fn merge_external_configs_default_to_workspace_child_not_defined() {
+    let mut parent_config_section = ConfigSection::new();
+    parent_config_section.default_to_workspace = Some(false);
+    let mut parent_config = ExternalConfig::new();
+    parent_config.config = Some(parent_config_section);
+
+    let mut config = ExternalConfig::new();
+    config.config = Some(ConfigSection::new());
+
+    let merged_config = merge_external_configs(config, parent_config);
+
+    assert!(!merged_config.config.unwrap().default_to_workspace.unwrap());
+}
+
+#[test]
+fn merge_base_config_and_external_config_default_to_workspace_internal_default() {
+    let base_config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        ExternalConfig::new(),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn merge_base_config_and_external_config_default_to_workspace_external_overrides() {
+    let base_config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+    let mut config_section = ConfigSection::new();
+    config_section.default_to_workspace = Some(false);
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+
+    let config =
+        merge_base_config_and_external_config(base_config, external_config, None, false, false)
+            .unwrap();
+
+    assert!(!config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn load_from_str_default_to_workspace_extend_chain() {
+    let config = load_from_str(
+        r#"
+extend = "src/lib/test/makefiles/default_to_workspace.toml"
+
+[config]
+default_to_workspace = true
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn load_from_str_default_to_workspace_extended_overrides_internal() {
+    let config = load_from_str(
+        r#"
+extend = "src/lib/test/makefiles/default_to_workspace.toml"
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(!config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn load_internal_descriptor_override_valid() {
+    let config =
+        load_internal_descriptor_override("./src/lib/test/makefiles/internal_override.toml")
+            .unwrap();
+
+    assert_eq!(config.config.init_task.unwrap(), "override-init");
+    assert!(config.tasks.contains_key("override-task"));
+    assert!(!config.tasks.contains_key("empty"));
+}
+
+#[test]
+fn load_internal_descriptor_override_not_found() {
+    let result = load_internal_descriptor_override("./src/lib/test/makefiles/missing.toml");
+
+    match result.err().unwrap() {
+        DescriptorError::FileNotFound(file) => assert!(file.ends_with("missing.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_internal_descriptor_override_invalid() {
+    let result = load_internal_descriptor_override("./src/lib/test/makefiles/test1.toml");
+
+    match result.err().unwrap() {
+        DescriptorError::Parse { file, .. } => assert!(file.ends_with("test1.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+#[ignore]
+fn load_internal_descriptors_with_override() {
+    envmnt::set(
+        "CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE",
+        "./src/lib/test/makefiles/internal_override.toml",
+    );
+    let config = load_internal_descriptors(true, true, None, &vec![]);
+    envmnt::remove("CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE");
+
+    let config = config.unwrap();
+    assert!(config.tasks.contains_key("override-task"));
+    assert!(!config.tasks.contains_key("ci-flow"));
+    // experimental tasks are still merged
+    assert!(config.tasks.contains_key("coverage-lcov"));
+}
+
+#[test]
+#[ignore]
+fn load_internal_descriptors_with_empty_override() {
+    envmnt::set("CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE", "");
+    let config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+    envmnt::remove("CARGO_MAKE_INTERNAL_DESCRIPTOR_OVERRIDE");
+
+    assert!(config.tasks.contains_key("ci-flow"));
+    assert!(!config.tasks.contains_key("override-task"));
+}
+
+fn create_profile_env_config() -> ExternalConfig {
+    let mut profile_env = IndexMap::<String, EnvValue>::new();
+    profile_env.insert(
+        "CLI_TEST".to_string(),
+        EnvValue::Value("profile".to_string()),
+    );
+    profile_env.insert(
+        "PROFILE_ONLY".to_string(),
+        EnvValue::Value("profile".to_string()),
+    );
+    let mut env = IndexMap::<String, EnvValue>::new();
+    env.insert("CLI_TEST".to_string(), EnvValue::Value("base".to_string()));
+    env.insert("my_profile".to_string(), EnvValue::Profile(profile_env));
+    let mut external_config = ExternalConfig::new();
+    external_config.env = Some(env);
+
+    external_config
+}
+
+#[test]
+fn merge_base_config_and_external_config_cli_env_overrides_profile_env() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["CLI_TEST=cli".to_string()]),
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "CLI_TEST", "cli");
+    match config.env.get("my_profile").unwrap() {
+        EnvValue::Profile(ref profile_env) => {
+            assert!(!profile_env.contains_key("CLI_TEST"));
+            assert_env_value(profile_env, "PROFILE_ONLY", "profile");
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_base_config_and_external_config_cli_env_overrides_profile() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["my_profile=cli".to_string()]),
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "my_profile", "cli");
+    assert_env_value(&config.env, "CLI_TEST", "base");
+}
+
+#[test]
+fn merge_base_config_and_external_config_strict_cli_env_existing_key() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["CLI_TEST=cli".to_string()]),
+        false,
+        true,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "CLI_TEST", "cli");
+}
+
+#[test]
+fn merge_base_config_and_external_config_strict_cli_env_profile_key() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["PROFILE_ONLY=cli".to_string()]),
+        false,
+        true,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "PROFILE_ONLY", "cli");
+}
+
+#[test]
+fn merge_base_config_and_external_config_strict_cli_env_undefined_key() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let result = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["CLI_TSET=cli".to_string()]),
+        false,
+        true,
+    );
+
+    match result {
+        Err(DescriptorError::UndefinedCliEnv(key)) => assert_eq!(key, "CLI_TSET"),
+        _ => panic!("undefined cli env key not detected"),
+    };
+}
+
+#[test]
+fn merge_base_config_and_external_config_not_strict_cli_env_undefined_key() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        Some(vec!["CLI_TSET=cli".to_string()]),
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "CLI_TSET", "cli");
+}
+
+#[test]
+fn merge_base_config_and_external_config_no_cli_env_keeps_profile_env() {
+    let base_config = load_internal_descriptors(false, false, None, &vec![]).unwrap();
+
+    let config = merge_base_config_and_external_config(
+        base_config,
+        create_profile_env_config(),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_env_value(&config.env, "CLI_TEST", "base");
+    match config.env.get("my_profile").unwrap() {
+        EnvValue::Profile(ref profile_env) => {
+            assert_env_value(profile_env, "CLI_TEST", "profile");
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn load_internal_descriptors_no_experimental() {
+    let config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+
+    let mut task = config.tasks.get("ci-flow");
+    assert!(task.is_some());
+    task = config.tasks.get("coverage-lcov");
+    assert!(task.is_none());
+}
+
+#[test]
+fn load_internal_descriptors_with_experimental() {
+    let config = load_internal_descriptors(true, true, None, &vec![]).unwrap();
+
+    let mut task = config.tasks.get("ci-flow");
+    assert!(task.is_some());
+    task = config.tasks.get("coverage-lcov");
+    assert!(task.is_some());
+}
+
+#[test]
+fn load_internal_descriptors_modify_empty() {
+    let config = load_internal_descriptors(
+        true,
+        false,
+        Some(ModifyConfig {
+            private: None,
+            namespace: None,
+            rename: None,
+        }),
+        &vec![],
+    )
+    .unwrap();
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.is_none());
+    task = config.tasks.get("init");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.is_none());
+}
+
+#[test]
+fn load_internal_descriptors_modify_private() {
+    let config = load_internal_descriptors(
+        true,
+        false,
+        Some(ModifyConfig {
+            private: Some(true),
+            namespace: None,
+            rename: None,
+        }),
+        &vec![],
+    )
+    .unwrap();
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.unwrap());
+    task = config.tasks.get("init");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.unwrap());
+}
+
+#[test]
+fn load_internal_descriptors_modify_namespace() {
+    let config = load_internal_descriptors(
+        true,
+        false,
+        Some(ModifyConfig {
+            private: None,
+            namespace: Some("default".to_string()),
+            rename: None,
+        }),
+        &vec![],
+    )
+    .unwrap();
+
+    let mut task = config.tasks.get("empty");
+    assert!(task.is_none());
+    task = config.tasks.get("default::empty");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.is_none());
+    task = config.tasks.get("init");
+    assert!(task.is_none());
+    task = config.tasks.get("default::init");
+    assert!(task.is_some());
+    assert!(task.unwrap().private.is_none());
+}
+
+#[test]
+fn get_absolute_descriptor_path_valid() {
+    let file_path = Path::new("./src/lib/test/makefiles/test1.toml");
+
+    let absolute_path = get_absolute_descriptor_path(&file_path, true).unwrap();
+
+    assert!(Path::new(&absolute_path).is_absolute());
+    assert_eq!(
+        absolute_path,
+        canonicalize_or(&file_path, "./src/lib/test/makefiles/test1.toml")
+    );
+}
+
+#[test]
+fn get_absolute_descriptor_path_fallback() {
+    let file_path = Path::new("./src/lib/test/makefiles/missing.toml");
+
+    let absolute_path = get_absolute_descriptor_path(&file_path, false).unwrap();
+
+    assert!(Path::new(&absolute_path).is_absolute());
+    assert!(absolute_path.ends_with("missing.toml"));
+    let expected_path: String = FromPath::from_path(&env::current_dir().unwrap().join(&file_path));
+    assert_eq!(absolute_path, expected_path);
+}
+
+#[test]
+fn get_absolute_descriptor_path_fallback_strict() {
+    let file_path = Path::new("./src/lib/test/makefiles/missing.toml");
+
+    let error = get_absolute_descriptor_path(&file_path, true).unwrap_err();
+
+    match error {
+        DescriptorError::Canonicalize { file, .. } => assert_eq!(file, file_path.to_path_buf()),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn get_skipped_extend_log_level_forced_present() {
+    let level = get_skipped_extend_log_level(true, true);
+
+    assert!(level.is_none());
+}
+
+#[test]
+fn get_skipped_extend_log_level_optional_present() {
+    let level = get_skipped_extend_log_level(false, true);
+
+    assert!(level.is_none());
+}
+
+#[test]
+#[ignore]
+fn get_skipped_extend_log_level_optional_missing() {
+    envmnt::remove("CARGO_MAKE_REPORT_MISSING_OPTIONAL_EXTEND");
+
+    let level = get_skipped_extend_log_level(false, false);
+
+    assert_eq!(level.unwrap(), Level::Debug);
+}
+
+#[test]
+#[ignore]
+fn get_skipped_extend_log_level_optional_missing_reported() {
+    envmnt::set("CARGO_MAKE_REPORT_MISSING_OPTIONAL_EXTEND", "true");
+
+    let level = get_skipped_extend_log_level(false, false);
+
+    envmnt::remove("CARGO_MAKE_REPORT_MISSING_OPTIONAL_EXTEND");
+    assert_eq!(level.unwrap(), Level::Info);
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_no_file() {
+    let config = load_external_descriptor(
+        ".",
+        "bad_file.toml2",
+        false,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.config.is_none());
+    assert!(config.env.is_none());
+    assert!(config.tasks.is_none());
+}
+
+#[test]
+#[should_panic]
+fn load_external_descriptor_no_file_force() {
+    load_external_descriptor(
+        ".",
+        "bad_file.toml2",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic]
+fn load_external_descriptor_extended_not_found_force() {
+    load_external_descriptor(
+        ".",
+        "./examples/extends_not_found.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_simple_file() {
+    let config = load_external_descriptor(
+        ".",
+        "./examples/alias.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.config.is_none());
+    assert!(config.env.is_none());
+    assert!(config.tasks.is_some());
+
+    let tasks = config.tasks.unwrap();
+    let test_task = tasks.get("D2").unwrap();
+    let alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "D");
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_extending_file() {
+    let config = load_external_descriptor(
+        ".",
+        "examples/extending.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.config.is_some());
+    assert!(config.env.is_some());
+    assert!(config.tasks.is_some());
+
+    assert_eq!(config.env.unwrap().len(), 0);
+
+    let tasks = config.tasks.unwrap();
+    let mut test_task = tasks.get("D2").unwrap();
+    let mut alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "D");
+
+    test_task = tasks.get("extended").unwrap();
+    alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "D2");
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_extending_file_sub_folder() {
+    let config = load_external_descriptor(
+        ".",
+        "examples/files/extending.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.config.is_some());
+    assert!(config.env.is_some());
+    assert!(config.tasks.is_some());
+
+    let config_section = config.config.unwrap();
+    assert!(config_section.init_task.is_some());
+    assert!(config_section.end_task.is_none());
+    assert_eq!(config_section.init_task.unwrap(), "test_init");
+
+    assert_eq!(config.env.unwrap().len(), 0);
+
+    let tasks = config.tasks.unwrap();
+    let mut test_task = tasks.get("D2").unwrap();
+    let mut alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "D");
+
+    test_task = tasks.get("extended").unwrap();
+    alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "D2");
+
+    test_task = tasks.get("extended2").unwrap();
+    alias = test_task.alias.clone();
+    assert_eq!(alias.unwrap(), "extended");
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_set_env() {
+    envmnt::set("CARGO_MAKE_MAKEFILE_PATH", "EMPTY");
+    assert_eq!(envmnt::get_or_panic("CARGO_MAKE_MAKEFILE_PATH"), "EMPTY");
+
+    load_external_descriptor(
+        ".",
+        "./examples/alias.toml",
+        true,
+        true,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(envmnt::get_or_panic("CARGO_MAKE_MAKEFILE_PATH").ends_with("alias.toml"));
+}
+
+#[test]
+fn is_empty_descriptor_new() {
+    assert!(is_empty_descriptor(&ExternalConfig::new()));
+}
+
+#[test]
+fn is_empty_descriptor_empty_tasks() {
+    let mut config = ExternalConfig::new();
+    config.tasks = Some(IndexMap::new());
+
+    assert!(is_empty_descriptor(&config));
+}
+
+#[test]
+fn is_empty_descriptor_with_tasks() {
+    let mut config = ExternalConfig::new();
+    let mut tasks = IndexMap::new();
+    tasks.insert("test".to_string(), Task::new());
+    config.tasks = Some(tasks);
+
+    assert!(!is_empty_descriptor(&config));
+}
+
+#[test]
+fn is_empty_descriptor_with_config() {
+    let mut config = ExternalConfig::new();
+    config.config = Some(ConfigSection::new());
+
+    assert!(!is_empty_descriptor(&config));
+}
+
+#[test]
+fn load_external_descriptor_empty_file_not_strict() {
+    let config = load_external_descriptor(
+        "src/lib/test/makefiles",
+        "empty.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.is_none());
+}
+
+#[test]
+fn load_external_descriptor_empty_file_strict() {
+    let error = load_external_descriptor(
+        "src/lib/test/makefiles",
+        "empty.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::EmptyDescriptor(file) => assert!(file.ends_with("empty.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_comments_only_file_strict() {
+    let error = load_external_descriptor(
+        "src/lib/test/makefiles",
+        "comments_only.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::EmptyDescriptor(file) => assert!(file.ends_with("comments_only.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_not_empty_file_strict() {
+    let config = load_external_descriptor(
+        "src/lib/test/makefiles",
+        "test1.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(!config.tasks.unwrap().is_empty());
+}
+
+#[test]
+fn load_external_descriptor_min_version_broken_makefile_nopanic() {
+    // Ensure that we can properly get the min_version of a descriptor that
+    // doesn't match our internal data-structures.
+    let error = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/broken_makefile_minversion.toml",
+        false,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::MinVersion { required, file } => {
+            assert_eq!(required, "999.999.999");
+            assert!(file.unwrap().ends_with("broken_makefile_minversion.toml"));
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_min_version_extend_chain_highest() {
+    let error = load_external_descriptor(
+        "src/lib/test/makefiles/min_version",
+        "root.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::MinVersion { required, file } => {
+            assert_eq!(required, "999.999.999");
+            assert!(file.unwrap().ends_with("extended_higher.toml"));
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_min_version_extend_list_highest() {
+    let error = load_external_descriptor(
+        "src/lib/test/makefiles/min_version",
+        "list.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::MinVersion { required, file } => {
+            assert_eq!(required, "999.999.999");
+            assert!(file.unwrap().ends_with("extended_higher.toml"));
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn get_higher_min_version_error_other_higher() {
+    let error = get_higher_min_version_error(
+        DescriptorError::MinVersion {
+            required: "999.0.0".to_string(),
+            file: Some("a.toml".to_string()),
+        },
+        DescriptorError::MinVersion {
+            required: "999.1.0".to_string(),
+            file: Some("b.toml".to_string()),
+        },
+    );
+
+    match error {
+        DescriptorError::MinVersion { required, file } => {
+            assert_eq!(required, "999.1.0");
+            assert_eq!(file.unwrap(), "b.toml");
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn get_higher_min_version_error_other_lower() {
+    let error = get_higher_min_version_error(
+        DescriptorError::MinVersion {
+            required: "999.1.0".to_string(),
+            file: Some("a.toml".to_string()),
+        },
+        DescriptorError::MinVersion {
+            required: "999.0.0".to_string(),
+            file: Some("b.toml".to_string()),
+        },
+    );
+
+    match error {
+        DescriptorError::MinVersion { required, file } => {
+            assert_eq!(required, "999.1.0");
+            assert_eq!(file.unwrap(), "a.toml");
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn get_higher_min_version_error_other_not_min_version() {
+    let error = get_higher_min_version_error(
+        DescriptorError::MinVersion {
+            required: "999.1.0".to_string(),
+            file: None,
+        },
+        DescriptorError::UnknownPreset("test".to_string()),
+    );
+
+    match error {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.1.0"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn collect_min_version_error_other_error_first() {
+    let mut min_version_error = None;
+
+    let result = collect_min_version_error(
+        &mut min_version_error,
+        DescriptorError::UnknownPreset("test".to_string()),
+    );
+
+    assert!(result.is_err());
+    assert!(min_version_error.is_none());
+}
+
+#[test]
+fn collect_min_version_error_other_error_ignored() {
+    let mut min_version_error = None;
+
+    collect_min_version_error(
+        &mut min_version_error,
+        DescriptorError::MinVersion {
+            required: "999.0.0".to_string(),
+            file: None,
+        },
+    )
+    .unwrap();
+    collect_min_version_error(
+        &mut min_version_error,
+        DescriptorError::UnknownPreset("test".to_string()),
+    )
+    .unwrap();
+
+    match min_version_error.unwrap() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.0.0"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+#[should_panic]
+fn load_external_descriptor_broken_makefile_panic() {
+    // Ensure the descriptor we used in the test above is properly broken when
+    // it doesn't have a min_version. This is to ensure that the previous test
+    // is working properly - min_version is optional.
+    load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/broken_makefile.toml",
+        false,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn load_external_descriptor_broken_makefile_error() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/broken_makefile.toml",
+        false,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+
+    let error = result.err().unwrap();
+    match error {
+        DescriptorError::Parse { ref file, .. } => assert!(file.ends_with("broken_makefile.toml")),
+        _ => panic!("wrong error type"),
+    };
+    let message = error.to_string();
+    assert!(message.contains("broken_makefile.toml"));
+    assert!(message.contains("line: "));
+    assert!(message.contains("column: "));
+}
+
+#[test]
+fn load_external_descriptor_no_file_force_error() {
+    let result = load_external_descriptor(
+        ".",
+        "bad_file.toml2",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::FileNotFound(file) => assert!(file.ends_with("bad_file.toml2")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_directory_force_error() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/glob",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::IsDirectory(directory) => assert!(directory.ends_with("glob")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_directory_not_forced() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/glob",
+        false,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.is_none());
+}
+
+#[test]
+fn load_from_str_extend_directory_error() {
+    let result = load_from_str(
+        r#"
+extend = "src/lib/test/makefiles/glob"
+"#,
+        None,
+        false,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::IsDirectory(directory) => assert!(directory.ends_with("glob")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_from_str_extend_missing_file_error() {
+    let result = load_from_str(
+        r#"
+extend = "src/lib/test/makefiles/missing.toml"
+"#,
+        None,
+        false,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::FileNotFound(file) => assert!(file.ends_with("missing.toml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+fn load_yaml_test_descriptor(
+    file_name: &str,
+    strict: bool,
+) -> Result<ExternalConfig, DescriptorError> {
+    load_external_descriptor(
+        "./src/lib/test/makefiles/yaml",
+        file_name,
+        true,
+        false,
+        &vec![],
+        strict,
+        true,
+        None,
+        None,
+    )
+}
+
+#[test]
+fn is_yaml_descriptor_extensions() {
+    assert!(is_yaml_descriptor("./Makefile.yaml"));
+    assert!(is_yaml_descriptor("./Makefile.yml"));
+    assert!(is_yaml_descriptor("./Makefile.YML"));
+    assert!(is_yaml_descriptor("https://example.com/Makefile.yaml"));
+    assert!(!is_yaml_descriptor("./Makefile.toml"));
+    assert!(!is_yaml_descriptor("./yaml/Makefile.toml"));
+}
+
+#[test]
+fn load_external_descriptor_yaml_same_as_toml() {
+    let yaml_config = load_yaml_test_descriptor("makefile.yaml", true).unwrap();
+    let toml_config = load_yaml_test_descriptor("makefile.toml", true).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&yaml_config).unwrap(),
+        serde_json::to_value(&toml_config).unwrap()
+    );
+
+    let tasks = yaml_config.tasks.unwrap();
+    let keys: Vec<&String> = tasks.keys().collect();
+    assert_eq!(keys, vec!["yaml-task", "yaml-dependency"]);
+    let task = tasks.get("yaml-task").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert!(task.get_source_file().unwrap().ends_with("makefile.yaml"));
+
+    let env = yaml_config.env.unwrap();
+    let env_keys: Vec<&String> = env.keys().collect();
+    assert_eq!(env_keys, vec!["YAML_TEST1", "YAML_TEST2", "YAML_TEST3"]);
+}
+
+#[test]
+fn load_external_descriptor_toml_extends_yaml() {
+    let config = load_yaml_test_descriptor("extends_yaml.toml", true).unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("yml-task"));
+
+    let task = tasks.get("toml-task").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert_eq!(task.description.clone().unwrap(), "Task defined in yml");
+}
+
+#[test]
+fn load_external_descriptor_yaml_unknown_keys_strict() {
+    let result = load_yaml_test_descriptor("unknown_key.yaml", true);
+
+    match result.err().unwrap() {
+        DescriptorError::UnknownKeys { file, keys } => {
+            assert!(file.ends_with("unknown_key.yaml"));
+            assert_eq!(keys, vec!["tasks.yaml-task.unknown_task_key".to_string()]);
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_yaml_min_version() {
+    let result = load_yaml_test_descriptor("min_version.yaml", false);
+
+    match result.err().unwrap() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.999.999"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_yaml_broken() {
+    let result = load_yaml_test_descriptor("broken.yaml", false);
+
+    match result.err().unwrap() {
+        DescriptorError::ParseYaml { file, .. } => assert!(file.ends_with("broken.yaml")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_yaml_makefile_merged_with_core_tasks() {
+    let config = load(
+        "./src/lib/test/makefiles/yaml/makefile.yaml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            strict: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("yaml-task"));
+    assert!(config.tasks.contains_key("empty"));
+    assert!(!config.config.default_to_workspace.unwrap());
+}
+
+#[test]
+fn load_external_descriptor_unknown_task_key_not_strict() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/unknown_task_key.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("test"));
+}
+
+#[test]
+fn load_external_descriptor_unknown_task_key_strict() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/unknown_task_key.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::UnknownKeys { file, keys } => {
+            assert!(file.ends_with("unknown_task_key.toml"));
+            assert!(keys.contains(&"tasks.test.comand".to_string()));
+            assert!(keys.contains(&"tasks.test.depdencies".to_string()));
+            assert!(keys.contains(&"tasks.test.linux.scirpt".to_string()));
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_unknown_config_key_strict() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/unknown_config_key.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::UnknownKeys { file, keys } => {
+            assert!(file.ends_with("unknown_config_key.toml"));
+            assert_eq!(keys, vec!["config.skip_core_task".to_string()]);
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_known_keys_strict() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/test1.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("test1"));
+}
+
+#[test]
+fn load_external_descriptor_circular_extend_two_files() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_cycle2_a.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+
+    let error = result.err().unwrap();
+    assert!(error.to_string().starts_with("circular extend detected: "));
+
+    let chain = match error {
+        DescriptorError::CircularExtend(chain) => chain,
+        _ => panic!("wrong error type"),
+    };
+    assert_eq!(chain.len(), 3);
+    assert!(chain[0].ends_with("extend_cycle2_a.toml"));
+    assert!(chain[1].ends_with("extend_cycle2_b.toml"));
+    assert!(chain[2].ends_with("extend_cycle2_a.toml"));
+}
+
+#[test]
+fn load_external_descriptor_circular_extend_three_files() {
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_cycle3_a.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+
+    let error = result.err().unwrap();
+    assert!(error.to_string().starts_with("circular extend detected: "));
+
+    let chain = match error {
+        DescriptorError::CircularExtend(chain) => chain,
+        _ => panic!("wrong error type"),
+    };
+    assert_eq!(chain.len(), 4);
+    assert!(chain[0].ends_with("extend_cycle3_a.toml"));
+    assert!(chain[1].ends_with("extend_cycle3_b.toml"));
+    assert!(chain[2].ends_with("extend_cycle3_c.toml"));
+    assert!(chain[3].ends_with("extend_cycle3_a.toml"));
+}
+
+#[test]
+fn get_extend_chain_with_max_depth_within_limit() {
+    let extend_chain = vec!["a.toml".to_string(), "b.toml".to_string()];
+
+    let chain = get_extend_chain_with_max_depth(&extend_chain, "c.toml", 2).unwrap();
+
+    assert_eq!(chain.len(), 3);
+}
+
+#[test]
+fn get_extend_chain_with_max_depth_exceeded() {
+    let extend_chain = vec![
+        "a.toml".to_string(),
+        "b.toml".to_string(),
+        "c.toml".to_string(),
+    ];
+
+    let error = get_extend_chain_with_max_depth(&extend_chain, "d.toml", 2).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "extend depth limit: 2 exceeded at file: d.toml"
+    );
+    match error {
+        DescriptorError::ExtendDepthLimit { file, limit } => {
+            assert_eq!(file, "d.toml");
+            assert_eq!(limit, 2);
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_external_descriptor_extend_depth_default_limit() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_depth/depth1.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("depth4"));
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_extend_depth_limit_exceeded() {
+    envmnt::set("CARGO_MAKE_MAX_EXTEND_DEPTH", "2");
+
+    let result = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_depth/depth1.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    );
+
+    envmnt::remove("CARGO_MAKE_MAX_EXTEND_DEPTH");
+
+    match result.unwrap_err() {
+        DescriptorError::ExtendDepthLimit { file, limit } => {
+            assert!(file.ends_with("depth4.toml"));
+            assert_eq!(limit, 2);
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn run_load_script_no_config_section() {
+    let external_config = ExternalConfig::new();
+
+    let invoked = run_load_script(&external_config);
+    assert!(!invoked);
+}
+
+#[test]
+fn run_load_script_no_load_script() {
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(ConfigSection::new());
+
+    let invoked = run_load_script(&external_config);
+    assert!(!invoked);
+}
+
+#[test]
+fn run_load_script_valid_load_script() {
+    let mut config = ConfigSection::new();
+    config.load_script = Some(vec!["exit 0".to_string()]);
+
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config);
+
+    let invoked = run_load_script(&external_config);
+    assert!(invoked);
+}
+
+#[test]
+#[should_panic]
+fn run_load_script_invalid_load_script() {
+    let mut config = ConfigSection::new();
+    config.load_script = Some(vec!["exit 1".to_string()]);
+
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config);
+
+    run_load_script(&external_config);
+}
+
+#[test]
+fn run_load_script_valid_load_script_duckscript() {
+    envmnt::remove("run_load_script_valid_load_script_duckscript");
+    assert!(!envmnt::exists(
+        "run_load_script_valid_load_script_duckscript"
+    ));
+
+    let mut config = ConfigSection::new();
+    config.load_script = Some(vec![r#"#!@duckscript
+    set_env run_load_script_valid_load_script_duckscript true
+    "#
+    .to_string()]);
+
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config);
+
+    let invoked = run_load_script(&external_config);
+    assert!(invoked);
+
+    assert!(envmnt::exists(
+        "run_load_script_valid_load_script_duckscript"
+    ));
+    assert!(envmnt::is_or(
+        "run_load_script_valid_load_script_duckscript",
+        false
+    ));
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_path_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Path("src/lib/test/makefiles/test1.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn load_descriptor_extended_makefiles_path_not_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Path("src/lib/test/makefiles/bad.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_options_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn load_descriptor_extended_makefiles_options_not_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_options_exists_optional() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_options_exists_not_optional() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn load_descriptor_extended_makefiles_options_not_exists_optional() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(!tasks.contains_key("test1"));
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn load_descriptor_extended_makefiles_options_not_exists_not_optional() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_list_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test2.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+    assert!(tasks.contains_key("test2"));
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn load_descriptor_extended_makefiles_list_not_exists() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+    load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[ignore]
+fn load_descriptor_extended_makefiles_list_exists_optional() {
+    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: Some(false),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+    let descriptor = load_descriptor_extended_makefiles(
+        &parent_path,
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+    assert!(!tasks.contains_key("test2"));
+}
+
+#[test]
+fn compare_min_version_no_min_version() {
+    let result = compare_min_version("[config]\nskip_core_tasks = true");
+
+    assert_eq!(result, VersionCheck::Ok);
+}
+
+#[test]
+fn compare_min_version_smaller_min_version() {
+    let result = compare_min_version("[config]\nmin_version = \"0.0.1\"");
+
+    assert_eq!(result, VersionCheck::Ok);
+}
+
+#[test]
+fn compare_min_version_same_min_version() {
+    let toml_string = format!("[config]\nmin_version = \"{}\"", env!("CARGO_PKG_VERSION"));
+    let result = compare_min_version(&toml_string);
+
+    assert_eq!(result, VersionCheck::Ok);
+}
+
+#[test]
+fn compare_min_version_too_old() {
+    let result = compare_min_version("[config]\nmin_version = \"999.999.999\"");
+
+    assert_eq!(
+        result,
+        VersionCheck::TooOld {
+            required: "999.999.999".to_string(),
+            running: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    );
+}
+
+#[test]
+fn compare_min_version_unparseable_min_version() {
+    let result = compare_min_version("[config]\nmin_version = \"1.2\"");
+
+    match result {
+        VersionCheck::Unparseable(message) => assert!(message.contains("1.2")),
+        _ => panic!("wrong version check result"),
+    };
+}
+
+#[test]
+fn compare_min_version_unparseable_descriptor() {
+    let result = compare_min_version("123");
+
+    match result {
+        VersionCheck::Unparseable(_) => (),
+        _ => panic!("wrong version check result"),
+    };
+}
+
+#[test]
+fn compare_min_version_max_version_ignored() {
+    let result = compare_min_version("[config]\nmax_version = \"0.0.1\"");
+
+    assert_eq!(result, VersionCheck::Ok);
+}
+
+#[test]
+fn check_makefile_min_version_empty() {
+    let toml_string = "";
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_invalid_format() {
+    let toml_string = "123";
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_no_config() {
+    let toml_string = "test = true";
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_no_min_version() {
+    let toml_string = r#"
+    [config]
+    test = true
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_smaller_min_version() {
+    let toml_string = r#"
+    [config]
+    min_version = "0.0.1"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_bigger_min_version() {
+    let toml_string = r#"
+    [config]
+    min_version = "999.999.999"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_err());
+    match result.err().unwrap() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.999.999"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_same_min_version() {
+    let mut toml_string = r#"
+    [config]
+    min_version = ""#;
+    let mut buffer = toml_string.to_string();
+    buffer.push_str(env!("CARGO_PKG_VERSION"));
+    buffer.push_str("\"");
+    toml_string = buffer.as_str();
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_prerelease_of_current_version() {
+    let toml_string = format!(
+        "[config]\nmin_version = \"{}-beta.1\"",
+        env!("CARGO_PKG_VERSION")
+    );
+    let result = check_makefile_min_version(&toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_prerelease_of_newer_version() {
+    let toml_string = r#"
+    [config]
+    min_version = "999.999.999-beta.1"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    match result.err().unwrap() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.999.999-beta.1"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_invalid_min_version() {
+    let toml_string = r#"
+    [config]
+    min_version = "1.2"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    match result.err().unwrap() {
+        DescriptorError::InvalidVersion(message) => assert!(message.contains("1.2")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_invalid_max_version() {
+    let toml_string = r#"
+    [config]
+    max_version = "bad"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    match result.err().unwrap() {
+        DescriptorError::InvalidVersion(message) => assert!(message.contains("bad")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_bigger_max_version() {
+    let toml_string = r#"
+    [config]
+    max_version = "999.999.999"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_smaller_max_version() {
+    let toml_string = r#"
+    [config]
+    max_version = "0.0.1"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_err());
+    match result.err().unwrap() {
+        DescriptorError::MaxVersion { maximum } => assert_eq!(maximum, "0.0.1"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_same_max_version() {
+    let mut toml_string = r#"
+    [config]
+    max_version = ""#;
+    let mut buffer = toml_string.to_string();
+    buffer.push_str(env!("CARGO_PKG_VERSION"));
+    buffer.push_str("\"");
+    toml_string = buffer.as_str();
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_range_below() {
+    let toml_string = r#"
+    [config]
+    min_version = "999.999.998"
+    max_version = "999.999.999"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_err());
+    match result.err().unwrap() {
+        DescriptorError::MinVersion { required, .. } => assert_eq!(required, "999.999.998"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_makefile_min_version_range_in() {
+    let toml_string = r#"
+    [config]
+    min_version = "0.0.1"
+    max_version = "999.999.999"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_makefile_min_version_range_above() {
+    let toml_string = r#"
+    [config]
+    min_version = "0.0.1"
+    max_version = "0.0.2"
+    "#;
+    let result = check_makefile_min_version(toml_string);
+
+    assert!(result.is_err());
+    match result.err().unwrap() {
+        DescriptorError::MaxVersion { maximum } => assert_eq!(maximum, "0.0.2"),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_remote_not_exists_optional() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "http://127.0.0.1:1/Makefile.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_remote_not_exists_not_optional() {
+    let result = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Path("http://127.0.0.1:1/Makefile.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::Remote(message) => {
+            assert!(message.contains("http://127.0.0.1:1/Makefile.toml"))
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_glob_exists() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Path("src/lib/test/makefiles/glob/*.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("glob1"));
+    assert!(tasks.contains_key("glob2"));
+
+    let task = tasks.get("glob_both").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert_eq!(task.args.clone().unwrap(), vec!["glob2".to_string()]);
+
+    let mut task = tasks.get("glob1").unwrap();
+    assert!(task
+        .initial_makefile
+        .clone()
+        .unwrap()
+        .ends_with("glob1.toml"));
+    task = tasks.get("glob2").unwrap();
+    assert!(task
+        .initial_makefile
+        .clone()
+        .unwrap()
+        .ends_with("glob2.toml"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_glob_not_exists() {
+    let result = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Path("src/lib/test/makefiles/glob/*.toml2".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::ExtendPattern(message) => assert!(message.contains("*.toml2")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_glob_not_exists_optional() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/glob/*.toml2".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+fn load_from_str_core_and_user_tasks() {
+    let config = load_from_str(
+        r#"
+[tasks.user-task]
+command = "echo"
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("user-task"));
+    assert!(config.tasks.contains_key("empty"));
+    assert!(config.tasks.contains_key("init"));
+}
+
+#[test]
+fn load_from_str_experimental_task_merged_into_user_task() {
+    let config = load_from_str(
+        r#"
+[tasks.coverage-lcov]
+command = "echo"
+"#,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let task = config.tasks.get("coverage-lcov").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert!(task.description.is_some());
+    assert!(task.linux.is_some());
+}
+
+#[test]
+fn load_from_str_additive_experimental_tasks_user_task_wins() {
+    let config = load_from_str(
+        r#"
+[config]
+additive_experimental_tasks = true
+
+[tasks.coverage-lcov]
+command = "echo"
+"#,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let task = config.tasks.get("coverage-lcov").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert!(task.description.is_none());
+    assert!(task.linux.is_none());
+}
+
+#[test]
+fn load_internal_descriptors_experimental_protected_tasks() {
+    let config =
+        load_internal_descriptors(true, true, None, &vec!["coverage-lcov".to_string()]).unwrap();
+
+    assert!(!config.tasks.contains_key("coverage-lcov"));
+    assert!(config.tasks.contains_key("empty"));
+}
+
+#[test]
+fn load_internal_descriptors_experimental_protected_tasks_namespaced() {
+    let config = load_internal_descriptors(
+        true,
+        true,
+        Some(ModifyConfig {
+            private: None,
+            namespace: Some("core".to_string()),
+            rename: None,
+        }),
+        &vec!["core::coverage-lcov".to_string()],
+    )
+    .unwrap();
+
+    assert!(!config.tasks.contains_key("core::coverage-lcov"));
+    assert!(config.tasks.contains_key("core::empty"));
+}
+
+#[test]
+fn load_from_str_env_setup_order() {
+    let config = load_from_str(
+        r#"
+[config]
+env_setup_order = "ScriptsFirst"
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.config.env_setup_order.unwrap(),
+        EnvSetupOrder::ScriptsFirst
+    );
+}
+
+#[test]
+fn load_from_str_env_setup_order_default() {
+    let config = load_from_str("", None, false).unwrap();
+
+    assert!(config.config.env_setup_order.is_none());
+    assert_eq!(
+        config.config.get_env_setup_order(),
+        EnvSetupOrder::FilesFirst
+    );
+}
+
+#[test]
+fn load_preset_descriptor_strict() {
+    for name in &["rust-quality", "rust-docs"] {
+        let config = load_preset_descriptor(name, true).unwrap();
+
+        assert!(config.tasks.is_some());
+    }
+}
+
+#[test]
+fn load_preset_descriptor_unknown() {
+    let result = load_preset_descriptor("unknown-preset", true);
+
+    match result {
+        Err(DescriptorError::UnknownPreset(name)) => assert_eq!(name, "unknown-preset"),
+        _ => panic!("unknown preset not detected"),
+    };
+}
+
+#[test]
+fn load_from_str_extend_preset() {
+    let config = load_from_str(
+        r#"
+extend = { preset = "rust-quality" }
+
+[tasks.quality-lint]
+args = ["clippy"]
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("quality"));
+    assert!(config.tasks.contains_key("quality-format"));
+    assert!(config.tasks.contains_key("quality-audit"));
+    assert!(config.tasks.contains_key("empty"));
+
+    let task = config.tasks.get("quality-lint").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "cargo");
+    assert_eq!(task.args.clone().unwrap(), vec!["clippy".to_string()]);
+}
+
+#[test]
+fn load_from_str_extend_preset_tasks_filter() {
+    let config = load_from_str(
+        r#"
+extend = { preset = "rust-quality", tasks = ["quality-lint"] }
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("quality-lint"));
+    assert!(!config.tasks.contains_key("quality"));
+}
+
+#[test]
+fn load_from_str_extend_preset_unknown() {
+    let result = load_from_str(
+        r#"
+extend = { preset = "unknown-preset" }
+"#,
+        None,
+        false,
+    );
+
+    match result {
+        Err(DescriptorError::UnknownPreset(name)) => assert_eq!(name, "unknown-preset"),
+        _ => panic!("expected unknown preset error"),
+    }
+}
+
+#[test]
+fn load_from_str_config_field_sources() {
+    let config = load_from_str(
+        r#"
+[config]
+default_to_workspace = false
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    let source = config
+        .config
+        .get_field_source("default_to_workspace")
+        .unwrap();
+    assert_eq!(source, "<string>");
+    assert_eq!(
+        config.config.get_field_source("init_task").unwrap(),
+        "internal"
+    );
+    assert_eq!(
+        config.config.get_field_source("skip_core_tasks").unwrap(),
+        "internal"
+    );
+    assert!(config.config.get_field_source("reduce_output").is_none());
+}
+
+#[test]
+fn merge_external_configs_config_field_sources() {
+    let mut parent_config_section = ConfigSection::new();
+    parent_config_section.init_task = Some("parent_init".to_string());
+    parent_config_section.end_task = Some("parent_end".to_string());
+    set_config_field_sources(&mut parent_config_section, "parent.toml");
+    let mut parent_config = ExternalConfig::new();
+    parent_config.config = Some(parent_config_section);
+
+    let mut config_section = ConfigSection::new();
+    config_section.end_task = Some("end".to_string());
+    set_config_field_sources(&mut config_section, "child.toml");
+    let mut config = ExternalConfig::new();
+    config.config = Some(config_section);
+
+    let merged_config = merge_external_configs(config, parent_config);
+    let merged_config_section = merged_config.config.unwrap();
+
+    assert_eq!(
+        merged_config_section.get_field_source("init_task").unwrap(),
+        "parent.toml"
+    );
+    assert_eq!(
+        merged_config_section.get_field_source("end_task").unwrap(),
+        "child.toml"
+    );
+    assert_eq!(merged_config_section.get_field_sources().len(), 2);
+}
+
+fn get_config_env_value(config: &Config, key: &str) -> String {
+    match config.env.get(key).unwrap() {
+        EnvValue::Value(ref value) => value.to_string(),
+        _ => panic!("wrong value type"),
+    }
+}
+
+#[test]
+fn load_from_str_env_map_value_with_equals() {
+    let config = load_from_str(
+        "",
+        Some(vec![
+            "URL=https://example.com?a=b&c=d".to_string(),
+            "SIMPLE=value".to_string(),
+        ]),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_config_env_value(&config, "URL"),
+        "https://example.com?a=b&c=d"
+    );
+    assert_eq!(get_config_env_value(&config, "SIMPLE"), "value");
+}
+
+#[test]
+fn load_from_str_env_map_empty_value() {
+    let config = load_from_str(
+        r#"
+[env]
+EMPTY = "makefile"
+"#,
+        Some(vec!["EMPTY=".to_string()]),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(get_config_env_value(&config, "EMPTY"), "");
+}
+
+#[test]
+fn load_from_str_env_map_invalid_pairs() {
+    let config = load_from_str(
+        "",
+        Some(vec!["NO_VALUE".to_string(), "=value".to_string()]),
+        false,
+    )
+    .unwrap();
+
+    assert!(!config.env.contains_key("NO_VALUE"));
+    assert!(!config.env.contains_key(""));
+}
+
+#[test]
+fn load_from_str_rename_core_task() {
+    let config = load_from_str(
+        r#"
+[config.modify_core_tasks]
+rename = { "build" = "core-build" }
+
+[tasks.build]
+command = "echo"
+"#,
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("core-build"));
+    assert_eq!(
+        config.tasks.get("build").unwrap().command.clone().unwrap(),
+        "echo"
+    );
+    assert_eq!(
+        config
+            .tasks
+            .get("core-build")
+            .unwrap()
+            .command
+            .clone()
+            .unwrap(),
+        "cargo"
+    );
+
+    let dependencies = config
+        .tasks
+        .get("ci-flow")
+        .unwrap()
+        .dependencies
+        .clone()
+        .unwrap();
+    assert!(dependencies.contains(&"core-build".to_string()));
+    assert!(!dependencies.contains(&"build".to_string()));
+}
+
+#[test]
+fn load_from_str_rename_core_task_conflict() {
+    let result = load_from_str(
+        r#"
+[config.modify_core_tasks]
+rename = { "build" = "test" }
+"#,
+        None,
+        false,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::RenameConflict { task, name } => {
+            assert_eq!(task, "build");
+            assert_eq!(name, "test");
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_from_str_invalid() {
+    let result = load_from_str("[tasks.bad", None, false);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn load_from_str_with_base_path_relative_extend() {
+    let config = load_from_str_with_base_path(
+        r#"
+extend = "./glob/glob1.toml"
+
+[tasks.user-task]
+command = "echo"
+"#,
+        "./src/lib/test/makefiles",
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("user-task"));
+    assert!(config.tasks.contains_key("empty"));
+    assert!(config.tasks.contains_key("glob1"));
+}
+
+#[test]
+fn load_from_str_with_base_path_extend_makefile() {
+    let config = load_from_str_with_base_path(
+        r#"
+extend = "./Makefile.toml"
+
+[tasks.string-task]
+command = "echo"
+"#,
+        "./src/lib/test/makefiles/final",
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("string-task"));
+    assert!(config.tasks.contains_key("final-open"));
+    assert_eq!(
+        config
+            .tasks
+            .get("final-open")
+            .unwrap()
+            .args
+            .clone()
+            .unwrap(),
+        vec!["extended"]
+    );
+}
+
+#[test]
+fn load_from_str_with_base_path_task_origin() {
+    let config = load_from_str_with_base_path(
+        r#"
+extend = "./Makefile.toml"
+
+[tasks.string-task]
+command = "echo"
+"#,
+        "./src/lib/test/makefiles/final",
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve_task_origin(&config, "string-task").unwrap(),
+        "<string>"
+    );
+    let origin = resolve_task_origin(&config, "final-open").unwrap();
+    assert!(Path::new(&origin).ends_with("final/Makefile.toml"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_list_later_entries_override() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::List(vec![
+            ExtendOptions {
+                path: "src/lib/test/makefiles/list/list1.toml".to_string(),
+                preset: None,
+                optional: None,
+                tasks: None,
+                include_dependencies: None,
+                profiles: None,
+                skip_core_tasks: None,
+                mode: None,
+                relative_to: None,
+                import_env: None,
+                import_config: None,
+                import_env_files: None,
+                import_env_scripts: None,
+            },
+            ExtendOptions {
+                path: "src/lib/test/makefiles/list/list2.toml".to_string(),
+                preset: None,
+                optional: None,
+                tasks: None,
+                include_dependencies: None,
+                profiles: None,
+                skip_core_tasks: None,
+                mode: None,
+                relative_to: None,
+                import_env: None,
+                import_config: None,
+                import_env_files: None,
+                import_env_scripts: None,
+            },
+            ExtendOptions {
+                path: "src/lib/test/makefiles/list/list3.toml".to_string(),
+                preset: None,
+                optional: None,
+                tasks: None,
+                include_dependencies: None,
+                profiles: None,
+                skip_core_tasks: None,
+                mode: None,
+                relative_to: None,
+                import_env: None,
+                import_config: None,
+                import_env_files: None,
+                import_env_scripts: None,
+            },
+        ]),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("list1"));
+    assert!(tasks.contains_key("list2"));
+    assert!(tasks.contains_key("list3"));
+    let task = tasks.get("list_task").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["list3".to_string()]);
+}
+
+/// The previous merge_tasks implementation which cloned all the base and extended tasks
+fn merge_tasks_cloned(
+    base: &IndexMap<String, Task>,
+    extended: &IndexMap<String, Task>,
+    merge_task_env: bool,
+) -> IndexMap<String, Task> {
+    let mut merged = IndexMap::<String, Task>::new();
+
+    for (key, value) in base.iter() {
+        merged.insert(key.to_string(), value.clone());
+    }
+
+    for (key, value) in extended.iter() {
+        let task = value.clone();
+
+        let task = match base.get(key) {
+            Some(_) if task.clear.unwrap_or(false) => task,
+            Some(ref value) => {
+                let mut merged_task = Task::new();
+
+                merged_task.extend(value);
+                merged_task.extend(&task);
+
+                if let (Some(ref base_env_files), Some(ref extended_env_files)) =
+                    (&value.env_files, &task.env_files)
+                {
+                    merged_task.env_files = Some(merge_env_files(
+                        &mut base_env_files.clone(),
+                        &mut extended_env_files.clone(),
+                    ));
+                }
+
+                if merge_task_env && value.env.is_some() && task.env.is_some() {
+                    let mut base_env = value.env.clone().unwrap();
+                    let mut extended_env = task.env.clone().unwrap();
+
+                    merged_task.env = Some(merge_env(&mut base_env, &mut extended_env));
+                }
+
+                merged_task
+            }
+            _ => task,
+        };
+
+        merged.insert(key.to_string(), task);
+    }
+
+    merged
+}
+
+fn create_synthetic_tasks(prefix: &str, start: usize, count: usize) -> IndexMap<String, Task> {
+    let mut tasks = IndexMap::new();
+
+    for index in start..(start + count) {
+        let mut task = Task::new();
+        task.description = Some(format!("{} task {}", prefix, index));
+        task.command = Some("cargo".to_string());
+        task.args = Some(vec![prefix.to_string(), index.to_string()]);
+        task.dependencies = Some(vec![format!("task{}", index + 1)]);
+        let mut env = IndexMap::new();
+        env.insert(
+            format!("{}_ENV", prefix.to_uppercase()),
+            EnvValue::Value(index.to_string()),
+        );
+        task.env = Some(env);
+        if index % 10 == 0 {
+            task.clear = Some(true);
+        }
+
+        tasks.insert(format!("task{}", index), task);
+    }
+
+    tasks
+}
+
+#[test]
+fn merge_tasks_large_config_same_output() {
+    let base = create_synthetic_tasks("base", 0, 5000);
+    let extended = create_synthetic_tasks("extended", 2500, 5000);
+
+    let expected = merge_tasks_cloned(&base, &extended, true);
+    let output = merge_tasks(base, extended, true);
+
+    assert_eq!(output.len(), 7500);
+    assert_eq!(
+        serde_json::to_string(&output).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+}
+
+#[test]
+fn merge_tasks_large_config_faster_than_cloning() {
+    let base = create_synthetic_tasks("base", 0, 5000);
+    let extended = create_synthetic_tasks("extended", 2500, 5000);
+
+    let mut cloned_duration = Duration::from_secs(0);
+    let mut merge_duration = Duration::from_secs(0);
+    for _ in 0..5 {
+        let started = Instant::now();
+        let output = merge_tasks_cloned(&base, &extended, false);
+        cloned_duration += started.elapsed();
+        assert_eq!(output.len(), 7500);
+
+        // the inputs are owned by the callers, so creating them is not measured
+        let base_input = base.clone();
+        let extended_input = extended.clone();
+        let started = Instant::now();
+        let output = merge_tasks(base_input, extended_input, false);
+        merge_duration += started.elapsed();
+        assert_eq!(output.len(), 7500);
+    }
+
+    assert!(merge_duration < cloned_duration);
+}
+
+#[test]
+fn load_internal_descriptors_cached_faster_than_parsing() {
+    // warm up the cache
+    load_internal_descriptors(true, true, None, &vec![]).unwrap();
+
+    let started = Instant::now();
+    for _ in 0..50 {
+        parse_internal_descriptor(makefiles::STABLE, "base");
+        parse_internal_descriptor(makefiles::BETA, "experimental");
+    }
+    let parse_duration = started.elapsed();
+
+    let started = Instant::now();
+    for _ in 0..50 {
+        let config = load_internal_descriptors(true, true, None, &vec![]).unwrap();
+        assert!(config.tasks.contains_key("empty"));
+    }
+    let cached_duration = started.elapsed();
+
+    assert!(cached_duration < parse_duration);
+}
+
+#[test]
+fn load_internal_descriptors_cached_modify_config_not_shared() {
+    let modify_config = ModifyConfig {
+        private: None,
+        namespace: Some("cached".to_string()),
+        rename: None,
+    };
+    let config = load_internal_descriptors(true, false, Some(modify_config), &vec![]).unwrap();
+    assert!(config.tasks.contains_key("cached::empty"));
+
+    let config = load_internal_descriptors(true, false, None, &vec![]).unwrap();
+    assert!(config.tasks.contains_key("empty"));
+    assert!(!config.tasks.contains_key("cached::empty"));
+}
+
+#[test]
+fn validate_valid() {
+    let result = validate("./src/lib/test/makefiles/test1.toml", true);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn validate_not_found() {
+    let result = validate("./src/lib/test/makefiles/bad.toml", true);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn validate_broken() {
+    let result = validate("./src/lib/test/makefiles/broken_makefile.toml", true);
+
+    assert!(result.is_err());
+}
+
+#[test]
+#[ignore]
+fn validate_process_state_not_modified() {
+    let profile_name = profile::get();
+    profile::unset();
+    envmnt::remove("CARGO_MAKE_CORE_TASK_NAMESPACE");
+    envmnt::remove("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX");
+
+    let result = validate("./src/lib/test/makefiles/validate_process_state.toml", true);
+
+    let profile_set = profile::is_set();
+    profile::set(&profile_name);
+
+    assert!(result.is_ok());
+    assert!(!profile_set);
+    assert!(!envmnt::exists("CARGO_MAKE_CORE_TASK_NAMESPACE"));
+    assert!(!envmnt::exists("CARGO_MAKE_CORE_TASK_NAMESPACE_PREFIX"));
+}
+
+#[test]
+#[ignore]
+fn validate_process_state_existing_values_kept() {
+    let profile_name = profile::get();
+    profile::set("validate_active_profile_test");
+    envmnt::set("CARGO_MAKE_CORE_TASK_NAMESPACE", "existing");
+
+    let result = validate("./src/lib/test/makefiles/validate_process_state.toml", true);
+
+    let active_profile_name = profile::get();
+    profile::set(&profile_name);
+    let namespace = envmnt::get_or_panic("CARGO_MAKE_CORE_TASK_NAMESPACE");
+    envmnt::remove("CARGO_MAKE_CORE_TASK_NAMESPACE");
+
+    assert!(result.is_ok());
+    assert_eq!(active_profile_name, "validate_active_profile_test");
+    assert_eq!(namespace, "existing");
+}
+
+#[test]
+fn validate_load_script_not_invoked() {
+    envmnt::remove("CARGO_MAKE_TEST_LOAD_SCRIPT_INVOKED");
+
+    let result = validate("./src/lib/test/makefiles/load_script_set_env.toml", true);
+
+    assert!(result.is_ok());
+    assert!(!envmnt::exists("CARGO_MAKE_TEST_LOAD_SCRIPT_INVOKED"));
+
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/load_script_set_env.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("load_script_task"));
+    assert!(envmnt::is("CARGO_MAKE_TEST_LOAD_SCRIPT_INVOKED"));
+}
+
+#[test]
+fn remove_skipped_tasks_no_patterns() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    config.tasks.insert("test".to_string(), Task::new());
+
+    remove_skipped_tasks(&mut config);
+
+    assert!(config.tasks.contains_key("test"));
+}
+
+#[test]
+fn remove_skipped_tasks_removed_dependency() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    config.config.skip_tasks = Some(vec!["skip-*".to_string()]);
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["skip-me".to_string(), "other".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+    config.tasks.insert("skip-me".to_string(), Task::new());
+    config.tasks.insert("skip-me-too".to_string(), Task::new());
+    config.tasks.insert("other".to_string(), Task::new());
+
+    remove_skipped_tasks(&mut config);
+
+    assert_eq!(config.tasks.len(), 2);
+    assert!(config.tasks.contains_key("other"));
+    let task = config.tasks.get("test").unwrap();
+    assert_eq!(task.dependencies.clone().unwrap().len(), 2);
+}
+
+#[test]
+fn load_skip_tasks_core_tasks() {
+    let config = load(
+        "./src/lib/test/makefiles/skip_tasks.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(!config.tasks.contains_key("ci-flow"));
+    assert!(!config.tasks.contains_key("ci-coverage-flow"));
+    assert!(!config.tasks.contains_key("pre-ci-flow"));
+    assert!(config.tasks.contains_key("my-flow"));
+    assert!(config.tasks.contains_key("build"));
+    assert!(config.tasks.contains_key("workspace-ci-flow"));
+}
+
+#[test]
+fn expand_task_makefile_location_no_location() {
+    let mut task = Task::new();
+    task.command =
+        Some("${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY}/helper.sh".to_string());
+
+    expand_task_makefile_location(&mut task);
+
+    assert_eq!(
+        task.command.unwrap(),
+        "${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY}/helper.sh"
+    );
+}
+
+#[test]
+fn expand_task_makefile_location_file_script() {
+    let mut task = Task::new();
+    task.initial_makefile = Some("/project/Makefile.toml".to_string());
+    task.initial_makefile_dir = Some("/project".to_string());
+    task.script = Some(ScriptValue::File(FileScriptValue {
+        file: "${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY}/script.sh".to_string(),
+        absolute_path: Some(true),
+    }));
+
+    expand_task_makefile_location(&mut task);
+
+    match task.script.unwrap() {
+        ScriptValue::File(file_script) => assert_eq!(file_script.file, "/project/script.sh"),
+        _ => panic!("wrong script type"),
+    };
+}
+
+#[test]
+fn load_expand_makefile_location() {
+    let config = load(
+        "./src/lib/test/makefiles/makefile_location/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let mut task = config.tasks.get("location").unwrap();
+    let makefile = task.initial_makefile.clone().unwrap();
+    let makefile_directory = task.initial_makefile_dir.clone().unwrap();
+    assert!(makefile.ends_with("Makefile.toml"));
+    assert_eq!(
+        task.command.clone().unwrap(),
+        format!("{}/helper.sh", &makefile_directory)
+    );
+    assert_eq!(
+        task.args.clone().unwrap(),
+        vec![
+            makefile,
+            "${CARGO_MAKE_MAKEFILE_LOCATION_OTHER}".to_string()
+        ]
+    );
+
+    task = config.tasks.get("location-script").unwrap();
+    match task.script.clone().unwrap() {
+        ScriptValue::Text(lines) => {
+            assert_eq!(lines, vec![format!("{}/helper.sh", &makefile_directory)])
+        }
+        _ => panic!("wrong script type"),
+    };
+
+    // extended makefiles tasks are expanded based on their own makefile location
+    task = config.tasks.get("extended-location").unwrap();
+    assert_eq!(
+        task.command.clone().unwrap(),
+        format!("{}/helper.sh", &makefile_directory)
+    );
+}
+
+#[test]
+fn load_expand_makefile_location_disabled() {
+    let config = load(
+        "./src/lib/test/makefiles/makefile_location/disabled.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let task = config.tasks.get("extended-location").unwrap();
+    assert_eq!(
+        task.command.clone().unwrap(),
+        "${CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY}/helper.sh"
+    );
+}
+
+#[test]
+fn deprecated_tasks_none() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    config.tasks.insert("test".to_string(), Task::new());
+
+    let deprecated = deprecated_tasks(&config);
+
+    assert!(deprecated.is_empty());
+}
+
+#[test]
+fn deprecated_tasks_listed() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    let mut task = Task::new();
+    task.deprecated = Some(DeprecationInfo::Message("use other".to_string()));
+    config.tasks.insert("message".to_string(), task);
+    let mut task = Task::new();
+    task.deprecated = Some(DeprecationInfo::Boolean(true));
+    config.tasks.insert("flag".to_string(), task);
+    let mut task = Task::new();
+    task.deprecated = Some(DeprecationInfo::Boolean(false));
+    config.tasks.insert("not_deprecated".to_string(), task);
+    config.tasks.insert("other".to_string(), Task::new());
+
+    let deprecated = deprecated_tasks(&config);
+
+    assert_eq!(
+        deprecated,
+        vec![
+            ("message".to_string(), "use other".to_string()),
+            ("flag".to_string(), "".to_string())
+        ]
+    );
+}
+
+#[test]
+fn deprecated_tasks_core_tasks() {
+    let config = load_from_str("", None, false).unwrap();
+
+    let deprecated = deprecated_tasks(&config);
+
+    assert!(!deprecated.is_empty());
+    for (task_name, _) in deprecated {
+        assert!(config.tasks.contains_key(&task_name));
+    }
+}
+
+#[test]
+fn validate_task_references_valid() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["other".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+    let mut task = Task::new();
+    task.alias = Some("test".to_string());
+    config.tasks.insert("other".to_string(), task);
+
+    let result = validate_task_references(&config);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn validate_task_references_platform_dependency_missing() {
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    let mut task = Task::new();
+    let mut platform_task = PlatformOverrideTask::default();
+    platform_task.dependencies = Some(vec!["linux_only".to_string()]);
+    task.linux = Some(platform_task);
+    config.tasks.insert("test".to_string(), task);
+
+    match validate_task_references(&config).unwrap_err() {
+        DescriptorError::UndefinedDependencies(references) => {
+            assert_eq!(references, vec!["test -> linux_only".to_string()])
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn flatten_extended_makefile() {
+    let descriptor = flatten("./src/lib/test/makefiles/flatten/Makefile.toml").unwrap();
+
+    let flattened: ExternalConfig = toml::from_str(&descriptor).unwrap();
+    assert!(flattened.extend.is_none());
+    assert_eq!(flattened.config.unwrap().init_task.unwrap(), "flatten-init");
+    let env_files = flattened.env_files.unwrap();
+    assert_eq!(env_files.len(), 1);
+    let env = flattened.env.unwrap();
+    assert_env_value(&env, "FLATTEN_BASE", "base");
+    assert_env_value(&env, "FLATTEN_SHARED", "extended");
+    assert_eq!(
+        get_env_scripts_text(&flattened.env_scripts.unwrap()),
+        vec!["echo flatten"]
+    );
+    let tasks = flattened.tasks.unwrap();
+    assert!(tasks.contains_key("flatten-base"));
+    assert!(tasks.contains_key("flatten-init"));
+    assert!(tasks.contains_key("empty"));
+    let task = tasks.get("flatten-shared").unwrap();
+    assert_eq!(task.description.clone().unwrap(), "base description");
+    assert_eq!(task.args.clone().unwrap(), vec!["extended".to_string()]);
+}
+
+#[test]
+fn flatten_equivalent_to_loaded_config() {
+    let config = load(
+        "./src/lib/test/makefiles/flatten/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    let descriptor = flatten("./src/lib/test/makefiles/flatten/Makefile.toml").unwrap();
+
+    let flattened_config = load_from_str(&descriptor, None, false).unwrap();
+
+    assert_eq!(flattened_config.tasks.len(), config.tasks.len());
+    assert_env_value(&flattened_config.env, "FLATTEN_BASE", "base");
+    assert_env_value(&flattened_config.env, "FLATTEN_SHARED", "extended");
+    assert_eq!(flattened_config.env_files.len(), config.env_files.len());
+    assert_eq!(flattened_config.env_scripts.len(), config.env_scripts.len());
+    let task = flattened_config.tasks.get("flatten-shared").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["extended".to_string()]);
+}
+
+#[test]
+fn flatten_deterministic() {
+    let descriptor = flatten("./src/lib/test/makefiles/flatten/Makefile.toml").unwrap();
+    let other_descriptor = flatten("./src/lib/test/makefiles/flatten/Makefile.toml").unwrap();
+
+    assert_eq!(descriptor, other_descriptor);
+}
+
+#[test]
+fn flatten_config_strips_current_task_env() {
+    let mut env = IndexMap::new();
+    env.insert(
+        "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE".to_string(),
+        EnvValue::Value("Makefile.toml".to_string()),
+    );
+    env.insert("TEST".to_string(), EnvValue::Value("value".to_string()));
+    let mut task = Task::new();
+    task.env = Some(env.clone());
+    let mut config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env,
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+    config.tasks.insert("test".to_string(), task);
+
+    let descriptor = flatten_config(config).unwrap();
+
+    assert!(!descriptor.contains("CARGO_MAKE_CURRENT_TASK_"));
+    let flattened: ExternalConfig = toml::from_str(&descriptor).unwrap();
+    assert_eq!(flattened.env.unwrap().len(), 1);
+    let tasks = flattened.tasks.unwrap();
+    assert_eq!(tasks.get("test").unwrap().env.clone().unwrap().len(), 1);
+}
+
+#[test]
+fn flatten_not_found() {
+    let result = flatten("./src/lib/test/makefiles/flatten/missing.toml");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn load_validate_dependencies_missing() {
+    let error = load(
+        "./src/lib/test/makefiles/undefined_dependency.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::UndefinedDependencies(references) => assert_eq!(
+            references,
+            vec![
+                "invalid -> nonexistent".to_string(),
+                "invalid_alias -> nonexistent_alias".to_string()
+            ]
+        ),
+        _ => panic!("wrong error type"),
+    };
+}
+
+fn create_config_with_env_files(env_files: Vec<EnvFile>) -> Config {
+    Config {
+        config: ConfigSection::new(),
+        env_files,
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    }
+}
+
+#[test]
+fn check_env_files_existing() {
+    let config = create_config_with_env_files(vec![EnvFile::Path(
+        "./src/lib/test/test_files/env.env".to_string(),
+    )]);
+
+    let result = check_env_files(&config, true);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_env_files_missing_required_strict() {
+    let config = create_config_with_env_files(vec![EnvFile::Path(
+        "./src/lib/test/test_files/missing.env".to_string(),
+    )]);
+
+    match check_env_files(&config, true).unwrap_err() {
+        DescriptorError::EnvFileNotFound(file) => assert!(file.ends_with("missing.env")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn check_env_files_missing_required_not_strict() {
+    let config = create_config_with_env_files(vec![EnvFile::Path(
+        "./src/lib/test/test_files/missing.env".to_string(),
+    )]);
+
+    let result = check_env_files(&config, false);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_env_files_missing_optional() {
+    let mut info = EnvFileInfo::new("./src/lib/test/test_files/missing.env".to_string());
+    info.optional = Some(true);
+    let config = create_config_with_env_files(vec![EnvFile::Info(info)]);
+
+    let result = check_env_files(&config, true);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_env_files_missing_inactive_profile() {
+    let mut info = EnvFileInfo::new("./src/lib/test/test_files/missing.env".to_string());
+    info.profile = Some("check_env_files_inactive".to_string());
+    let config = create_config_with_env_files(vec![EnvFile::Info(info)]);
+
+    let result = check_env_files(&config, true);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn check_env_files_missing_task_env_file() {
+    let mut config = create_config_with_env_files(vec![]);
+    let mut task = Task::new();
+    task.env_files = Some(vec![EnvFile::Path(
+        "./src/lib/test/test_files/missing.env".to_string(),
+    )]);
+    config.tasks.insert("test".to_string(), task);
+
+    match check_env_files(&config, true).unwrap_err() {
+        DescriptorError::EnvFileNotFound(file) => assert!(file.ends_with("missing.env")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_missing_env_file_strict() {
+    let error = load(
+        "./src/lib/test/makefiles/missing_env_file.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            strict: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::EnvFileNotFound(file) => assert!(file.ends_with("missing_required.env")),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_missing_env_file_not_strict() {
+    let config = load(
+        "./src/lib/test/makefiles/missing_env_file.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert_eq!(config.env_files.len(), 3);
+}
+
+#[test]
+fn warn_core_task_overrides_no_tasks() {
+    let external_config = ExternalConfig::new();
+
+    let overrides = warn_core_task_overrides(&external_config);
+
+    assert!(overrides.is_empty());
+}
+
+#[test]
+fn warn_core_task_overrides_found() {
+    let mut tasks = IndexMap::new();
+    tasks.insert("build".to_string(), Task::new());
+    tasks.insert("my_task".to_string(), Task::new());
+    let mut task = Task::new();
+    task.override_core_task = Some(true);
+    tasks.insert("test".to_string(), task);
+    let mut external_config = ExternalConfig::new();
+    external_config.tasks = Some(tasks);
+
+    let overrides = warn_core_task_overrides(&external_config);
+
+    assert_eq!(overrides, vec!["build".to_string()]);
+}
+
+#[test]
+fn warn_core_task_overrides_skip_core_tasks() {
+    let mut tasks = IndexMap::new();
+    tasks.insert("build".to_string(), Task::new());
+    let mut config_section = ConfigSection::new();
+    config_section.skip_core_tasks = Some(true);
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+    external_config.tasks = Some(tasks);
+
+    let overrides = warn_core_task_overrides(&external_config);
+
+    assert!(overrides.is_empty());
+}
+
+#[test]
+fn warn_core_task_overrides_namespace() {
+    let mut tasks = IndexMap::new();
+    tasks.insert("build".to_string(), Task::new());
+    tasks.insert("core::test".to_string(), Task::new());
+    let mut config_section = ConfigSection::new();
+    config_section.modify_core_tasks = Some(ModifyConfig {
+        private: None,
+        namespace: Some("core".to_string()),
+        rename: None,
+    });
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+    external_config.tasks = Some(tasks);
+
+    let overrides = warn_core_task_overrides(&external_config);
+
+    assert_eq!(overrides, vec!["core::test".to_string()]);
+}
+
+#[test]
+fn merge_additional_profiles_env_no_profiles() {
+    let mut env = IndexMap::<String, EnvValue>::new();
+    env.insert("test".to_string(), EnvValue::Value("1".to_string()));
+
+    let output = merge_additional_profiles_env(env, "development", &vec![]);
+
+    assert_eq!(output.len(), 1);
+    assert!(!output.contains_key("development"));
+}
+
+#[test]
+fn merge_additional_profiles_env_combined() {
+    let mut env = IndexMap::<String, EnvValue>::new();
+    let mut profile_env = IndexMap::<String, EnvValue>::new();
+    profile_env.insert("primary".to_string(), EnvValue::Value("1".to_string()));
+    profile_env.insert("both".to_string(), EnvValue::Value("primary".to_string()));
+    env.insert("production".to_string(), EnvValue::Profile(profile_env));
+    let mut additional_env = IndexMap::<String, EnvValue>::new();
+    additional_env.insert("additional".to_string(), EnvValue::Value("2".to_string()));
+    additional_env.insert(
+        "both".to_string(),
+        EnvValue::Value("additional".to_string()),
+    );
+    env.insert("additional".to_string(), EnvValue::Profile(additional_env));
+
+    let output = merge_additional_profiles_env(
+        env,
+        "production",
+        &vec!["missing".to_string(), "additional".to_string()],
+    );
+
+    match output.get("production").unwrap() {
+        &EnvValue::Profile(ref sub_env) => {
+            assert_eq!(sub_env.len(), 3);
+            match sub_env.get("primary").unwrap() {
+                &EnvValue::Value(ref value) => assert_eq!(value, "1"),
+                _ => panic!("wrong value type"),
+            };
+            match sub_env.get("additional").unwrap() {
+                &EnvValue::Value(ref value) => assert_eq!(value, "2"),
+                _ => panic!("wrong value type"),
+            };
+            match sub_env.get("both").unwrap() {
+                &EnvValue::Value(ref value) => assert_eq!(value, "primary"),
+                _ => panic!("wrong value type"),
+            };
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_additional_profiles_env_no_primary_profile_env() {
+    let mut env = IndexMap::<String, EnvValue>::new();
+    let mut additional_env = IndexMap::<String, EnvValue>::new();
+    additional_env.insert("additional".to_string(), EnvValue::Value("2".to_string()));
+    env.insert("additional".to_string(), EnvValue::Profile(additional_env));
+
+    let output = merge_additional_profiles_env(env, "production", &vec!["additional".to_string()]);
+
+    match output.get("production").unwrap() {
+        &EnvValue::Profile(ref sub_env) => {
+            assert_eq!(sub_env.len(), 1);
+            assert!(sub_env.contains_key("additional"));
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_additional_profiles_env_missing_profile() {
+    let mut env = IndexMap::<String, EnvValue>::new();
+    env.insert("test".to_string(), EnvValue::Value("1".to_string()));
+
+    let output = merge_additional_profiles_env(env, "production", &vec!["missing".to_string()]);
+
+    assert_eq!(output.len(), 1);
+    assert!(!output.contains_key("production"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_tasks_subset() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: Some(vec!["selected".to_string(), "base".to_string()]),
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert_eq!(tasks.len(), 2);
+    assert!(tasks.contains_key("selected"));
+    assert!(tasks.contains_key("base"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_tasks_subset_include_dependencies() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: Some(vec!["selected".to_string()]),
+            include_dependencies: Some(true),
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert_eq!(tasks.len(), 4);
+    assert!(tasks.contains_key("selected"));
+    assert!(tasks.contains_key("dependency"));
+    assert!(tasks.contains_key("aliased"));
+    assert!(tasks.contains_key("base"));
+    assert!(!tasks.contains_key("not_selected"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_tasks_subset_not_found() {
+    let result = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: Some(vec!["selected".to_string(), "missing".to_string()]),
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::ExtendTaskNotFound { path, task } => {
+            assert_eq!(path, "src/lib/test/makefiles/subset/shared.toml");
+            assert_eq!(task, "missing");
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_tasks_subset_optional_not_exists() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/missing.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: Some(vec!["selected".to_string()]),
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_env_path_set() {
+    envmnt::set(
+        "CARGO_MAKE_TEST_EXTEND_ENV_PATH_SET",
+        "src/lib/test/makefiles",
+    );
+
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Path("${CARGO_MAKE_TEST_EXTEND_ENV_PATH_SET}/test1.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("test1"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_env_path_unset_required() {
+    envmnt::remove("CARGO_MAKE_TEST_EXTEND_ENV_PATH_UNSET");
+
+    let result = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Path("${CARGO_MAKE_TEST_EXTEND_ENV_PATH_UNSET}/test1.toml".to_string()),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    );
+
+    match result.err().unwrap() {
+        DescriptorError::UnresolvedExtendPath(path) => {
+            assert_eq!(path, "${CARGO_MAKE_TEST_EXTEND_ENV_PATH_UNSET}/test1.toml")
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_env_path_unset_optional() {
+    envmnt::remove("CARGO_MAKE_TEST_EXTEND_ENV_PATH_UNSET_OPTIONAL");
+
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "${CARGO_MAKE_TEST_EXTEND_ENV_PATH_UNSET_OPTIONAL}/test1.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn load_external_descriptor_merge_load_script_output() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/load_script_output.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("static"));
+    let task = tasks.get("generated").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+    assert!(task
+        .initial_makefile
+        .clone()
+        .unwrap()
+        .ends_with("load_script_output.toml"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn load_external_descriptor_merge_load_script_output_yaml() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/yaml/load_script_output.yaml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("static"));
+    let task = tasks.get("generated").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "echo");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn load_external_descriptor_merge_load_script_no_output() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/load_script_no_output.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert!(tasks.contains_key("static"));
+}
+
+#[test]
+fn load_external_descriptor_merge_load_script_output_not_invoked() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/load_script_output.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("static"));
+    assert!(!tasks.contains_key("generated"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_profiles_match() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: Some(vec!["other".to_string(), profile::get()]),
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("selected"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_profiles_no_match() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: Some(vec!["extend_profile_no_match".to_string()]),
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_profiles_no_match_optional_not_found() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/bad.toml".to_string(),
+            preset: None,
+            optional: Some(true),
+            tasks: Some(vec!["missing".to_string()]),
+            include_dependencies: None,
+            profiles: Some(vec!["extend_profile_no_match".to_string()]),
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.tasks.is_none());
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_profiles_no_match_in_list() {
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: Some(vec!["extend_profile_no_match".to_string()]),
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    let tasks = descriptor.tasks.unwrap();
+    assert!(tasks.contains_key("selected"));
+    assert!(!tasks.contains_key("test1"));
+}
+
+#[test]
+fn list_tasks_exclude_private() {
+    let tasks = list_tasks("./src/lib/test/makefiles/private_tasks.toml", false).unwrap();
+    let names: Vec<String> = tasks.iter().map(|(name, _)| name.to_string()).collect();
+
+    assert!(names.contains(&"list-public-task".to_string()));
+    assert!(!names.contains(&"list-private-task".to_string()));
+    assert!(names.contains(&"empty".to_string()));
+    assert!(tasks.iter().all(|(_, task)| !task.private.unwrap_or(false)));
+
+    let mut sorted_names = names.clone();
+    sorted_names.sort();
+    assert_eq!(names, sorted_names);
+}
+
+#[test]
+fn list_tasks_include_private() {
+    let tasks = list_tasks("./src/lib/test/makefiles/private_tasks.toml", true).unwrap();
+    let names: Vec<String> = tasks.iter().map(|(name, _)| name.to_string()).collect();
+
+    assert!(names.contains(&"list-public-task".to_string()));
+    assert!(names.contains(&"list-private-task".to_string()));
+
+    let (_, task) = tasks
+        .iter()
+        .find(|(name, _)| name == "list-private-task")
+        .unwrap();
+    assert!(task.private.unwrap());
+}
+
+#[test]
+fn list_tasks_not_found() {
+    let result = list_tasks("./src/lib/test/makefiles/missing.toml", false);
+
+    match result.err().unwrap() {
+        DescriptorError::FileNotFound(_) => (),
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_skip_core_tasks() {
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: Some(true),
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(descriptor.config.unwrap().skip_core_tasks.unwrap());
+    assert!(descriptor.tasks.unwrap().contains_key("selected"));
+}
+
+#[test]
+fn load_descriptor_extended_makefiles_skip_core_tasks_conflict_in_list() {
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/subset/shared.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: Some(true),
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/test1.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: Some(false),
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+    let descriptor = load_descriptor_extended_makefiles(
+        ".",
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap();
+
+    assert!(!descriptor.config.unwrap().skip_core_tasks.unwrap());
+}
+
+#[test]
+fn merge_external_configs_skip_core_tasks_conflict() {
+    let mut parent_config_section = ConfigSection::new();
+    parent_config_section.skip_core_tasks = Some(true);
+    let mut parent_config = ExternalConfig::new();
+    parent_config.config = Some(parent_config_section);
+
+    let mut config_section = ConfigSection::new();
+    config_section.skip_core_tasks = Some(false);
+    let mut config = ExternalConfig::new();
+    config.config = Some(config_section);
+
+    let merged = merge_external_configs(config, parent_config);
+
+    assert!(!merged.config.unwrap().skip_core_tasks.unwrap());
+}
+
+#[test]
+fn load_from_str_with_base_path_extend_skip_core_tasks() {
+    let config = load_from_str_with_base_path(
+        r#"
+extend = { path = "./test1.toml", skip_core_tasks = true }
+"#,
+        "./src/lib/test/makefiles",
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("test1"));
+    assert!(!config.tasks.contains_key("build"));
+}
+
+#[test]
+fn load_from_str_with_base_path_extend_skip_core_tasks_top_level_wins() {
+    let config = load_from_str_with_base_path(
+        r#"
+extend = { path = "./test1.toml", skip_core_tasks = true }
+
+[config]
+skip_core_tasks = false
+"#,
+        "./src/lib/test/makefiles",
+        None,
+        false,
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("test1"));
+    assert!(config.tasks.contains_key("build"));
+}
+
+fn get_workspace_makefiles_value(paths: Vec<&str>) -> String {
+    let value = env::join_paths(paths).unwrap();
+    value.to_str().unwrap().to_string()
+}
+
+#[test]
+fn load_workspace_makefiles_single() {
+    let workspace_makefiles =
+        get_workspace_makefiles_value(vec!["./src/lib/test/makefiles/workspace/workspace1.toml"]);
+
+    let config = load_workspace_makefiles(&workspace_makefiles, false, true).unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("workspace1"));
+    assert!(!tasks.contains_key("workspace2"));
+}
+
+#[test]
+fn load_workspace_makefiles_multiple() {
+    let workspace_makefiles = get_workspace_makefiles_value(vec![
+        "./src/lib/test/makefiles/workspace/workspace1.toml",
+        "./src/lib/test/makefiles/workspace/missing.toml",
+        "./src/lib/test/makefiles/workspace/workspace2.toml",
+    ]);
+
+    let config = load_workspace_makefiles(&workspace_makefiles, false, true).unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("workspace1"));
+    assert!(tasks.contains_key("workspace2"));
+    assert_eq!(
+        tasks
+            .get("workspace-shared")
+            .unwrap()
+            .command
+            .clone()
+            .unwrap(),
+        "workspace2"
+    );
+}
+
+fn get_env_value(env: &IndexMap<String, EnvValue>, key: &str) -> String {
+    match env.get(key).unwrap() {
+        EnvValue::Value(ref value) => value.to_string(),
+        _ => panic!("wrong value type"),
+    }
+}
+
+fn create_workspace_merge_configs(
+    workspace_env_override: bool,
+) -> (ExternalConfig, ExternalConfig) {
+    let member_config: ExternalConfig = toml::from_str(
+        r#"
+        [config]
+        init_task = "member-init"
+
+        [env]
+        PROXY = "member"
+        MEMBER_ONLY = "member"
+
+        [tasks.shared]
+        command = "member"
+        "#,
+    )
+    .unwrap();
+    let mut workspace_config: ExternalConfig = toml::from_str(
+        r#"
+        [config]
+        init_task = "workspace-init"
+
+        [env]
+        PROXY = "workspace"
+        WORKSPACE_ONLY = "workspace"
+
+        [tasks.shared]
+        command = "workspace"
+        "#,
+    )
+    .unwrap();
+    workspace_config
+        .config
+        .as_mut()
+        .unwrap()
+        .workspace_env_override = Some(workspace_env_override);
+
+    (member_config, workspace_config)
+}
+
+#[test]
+fn merge_workspace_config_no_override() {
+    let (member_config, workspace_config) = create_workspace_merge_configs(false);
+
+    let config = merge_workspace_config(member_config, workspace_config);
+
+    let env = config.env.unwrap();
+    assert_eq!(env.len(), 3);
+    assert_eq!(get_env_value(&env, "PROXY"), "member");
+    assert_eq!(get_env_value(&env, "MEMBER_ONLY"), "member");
+    assert_eq!(get_env_value(&env, "WORKSPACE_ONLY"), "workspace");
+    assert_eq!(config.config.unwrap().init_task.unwrap(), "member-init");
+    let tasks = config.tasks.unwrap();
+    assert_eq!(
+        tasks.get("shared").unwrap().command.clone().unwrap(),
+        "member"
+    );
+}
+
+#[test]
+fn merge_workspace_config_override() {
+    let (member_config, workspace_config) = create_workspace_merge_configs(true);
+
+    let config = merge_workspace_config(member_config, workspace_config);
+
+    let env = config.env.unwrap();
+    assert_eq!(env.len(), 3);
+    assert_eq!(get_env_value(&env, "PROXY"), "workspace");
+    assert_eq!(get_env_value(&env, "MEMBER_ONLY"), "member");
+    assert_eq!(get_env_value(&env, "WORKSPACE_ONLY"), "workspace");
+    assert_eq!(config.config.unwrap().init_task.unwrap(), "workspace-init");
+    let tasks = config.tasks.unwrap();
+    assert_eq!(
+        tasks.get("shared").unwrap().command.clone().unwrap(),
+        "member"
+    );
+}
+
+#[test]
+fn merge_workspace_config_override_defined_by_member() {
+    let (mut member_config, mut workspace_config) = create_workspace_merge_configs(false);
+    workspace_config
+        .config
+        .as_mut()
+        .unwrap()
+        .workspace_env_override = None;
+    member_config
+        .config
+        .as_mut()
+        .unwrap()
+        .workspace_env_override = Some(true);
+
+    let config = merge_workspace_config(member_config, workspace_config);
+
+    let env = config.env.unwrap();
+    assert_eq!(get_env_value(&env, "PROXY"), "member");
+}
+
+#[test]
+fn load_descriptors_multiple_workspace_makefiles() {
+    let workspace_makefiles = get_workspace_makefiles_value(vec![
+        "./src/lib/test/makefiles/workspace/workspace1.toml",
+        "./src/lib/test/makefiles/workspace/workspace2.toml",
+    ]);
+
+    let config = load_descriptors(
+        &|| {
+            let workspace_config = load_workspace_makefiles(&workspace_makefiles, false, true)?;
+            let external_config = load_external_descriptor(
+                ".",
+                "./src/lib/test/makefiles/test1.toml",
+                true,
+                false,
+                &vec![],
+                false,
+                true,
+                None,
+                None,
+            )?;
+            Ok(merge_external_configs(external_config, workspace_config))
+        },
+        None,
+        false,
+        false,
+        None,
+        true,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("test1"));
+    assert!(config.tasks.contains_key("workspace1"));
+    assert!(config.tasks.contains_key("workspace2"));
+}
+
+#[test]
+fn load_with_unresolved_handler_unset_reference() {
+    let unresolved_variables = RefCell::new(vec![]);
+
+    let config = load_with_unresolved_handler(
+        "./src/lib/test/makefiles/unresolved_env.toml",
+        true,
+        None,
+        false,
+        |variable| unresolved_variables.borrow_mut().push(variable.to_string()),
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.env.contains_key("UNRESOLVED_ENV_VALUE"));
+    let unresolved_variables = unresolved_variables.into_inner();
+    assert_eq!(
+        unresolved_variables
+            .iter()
+            .filter(|variable| *variable == "UNRESOLVED_ENV_TEST_UNSET")
+            .count(),
+        1
+    );
+    assert!(unresolved_variables.contains(&"UNRESOLVED_ENV_TEST_FILE".to_string()));
+    assert!(unresolved_variables.contains(&"UNRESOLVED_ENV_TEST_SCRIPT".to_string()));
+    assert!(!unresolved_variables.contains(&"UNRESOLVED_ENV_DEFINED".to_string()));
+    assert!(!unresolved_variables.contains(&"CARGO_MAKE_MAKEFILE_PATH".to_string()));
+    assert!(!unresolved_variables.contains(&"UNRESOLVED_ENV_TEST_TASK_UNSET".to_string()));
+    assert!(!unresolved_variables.contains(&"TASK_DEFINED".to_string()));
+}
+
+#[test]
+fn load_with_env_transform_none() {
+    let config = load_with_env_transform(
+        "./src/lib/test/makefiles/env_transform.toml",
+        true,
+        None,
+        false,
+        None,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    match config.env.get("ENV_TRANSFORM_VALUE").unwrap() {
+        EnvValue::Value(ref value) => assert_eq!(value, "value"),
+        _ => panic!("wrong env value type"),
+    };
+    assert!(config.env.contains_key("ENV_TRANSFORM_REMOVED"));
+}
+
+#[test]
+fn load_with_env_transform_uppercase() {
+    let env_transform: EnvTransform = Box::new(|key, value| {
+        if key == "ENV_TRANSFORM_REMOVED" {
+            None
+        } else {
+            match value {
+                EnvValue::Value(ref text) => Some(EnvValue::Value(text.to_uppercase())),
+                _ => Some(value.clone()),
+            }
+        }
+    });
+
+    let config = load_with_env_transform(
+        "./src/lib/test/makefiles/env_transform.toml",
+        true,
+        None,
+        false,
+        Some(env_transform),
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    match config.env.get("ENV_TRANSFORM_VALUE").unwrap() {
+        EnvValue::Value(ref value) => assert_eq!(value, "VALUE"),
+        _ => panic!("wrong env value type"),
+    };
+    match config.env.get("ENV_TRANSFORM_BOOL").unwrap() {
+        EnvValue::Boolean(ref value) => assert!(value),
+        _ => panic!("wrong env value type"),
+    };
+    assert!(!config.env.contains_key("ENV_TRANSFORM_REMOVED"));
+}
+
+fn get_env_file_paths(env_files: &Vec<EnvFile>) -> Vec<String> {
+    env_files
+        .iter()
+        .map(|env_file| match env_file {
+            EnvFile::Path(ref path) => path.to_string(),
+            EnvFile::Info(ref info) => info.path.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn add_file_location_info_task_env_files() {
+    let mut task = Task::new();
+    task.env_files = Some(vec![
+        EnvFile::Path("./task.env".to_string()),
+        EnvFile::Info(EnvFileInfo {
+            path: "./other.env".to_string(),
+            base_path: Some("./custom".to_string()),
+            profile: None,
+            strip_export: None,
+            optional: None,
+            include: None,
+        }),
+    ]);
+    let mut tasks = IndexMap::new();
+    tasks.insert("test".to_string(), task);
+    let mut external_config = ExternalConfig::new();
+    external_config.tasks = Some(tasks);
+
+    let external_config = add_file_location_info(external_config, "./base/Makefile.toml", "./base");
+
+    let tasks = external_config.tasks.unwrap();
+    let env_files = tasks.get("test").unwrap().env_files.clone().unwrap();
+    assert_eq!(env_files.len(), 2);
+    match env_files[0] {
+        EnvFile::Info(ref info) => {
+            assert_eq!(info.path, "./task.env");
+            assert_eq!(info.base_path.clone().unwrap(), "./base");
+        }
+        _ => panic!("wrong env file type"),
+    };
+    match env_files[1] {
+        EnvFile::Info(ref info) => assert_eq!(info.base_path.clone().unwrap(), "./custom"),
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn add_file_location_info_config_env_file() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_file = Some("./shorthand.env".to_string());
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+    external_config.env_files = Some(vec![EnvFile::Path("./list.env".to_string())]);
+
+    let external_config = add_file_location_info(external_config, "./base/Makefile.toml", "./base");
+
+    // the env_file is only added to the env_files once the config sections are merged
+    assert_eq!(
+        get_env_file_paths(&external_config.env_files.unwrap()),
+        vec!["./list.env"]
+    );
+    assert_eq!(
+        external_config.config.unwrap().env_file_directory.unwrap(),
+        "./base"
+    );
+}
+
+#[test]
+fn add_config_env_file_with_env_files() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_file = Some("./shorthand.env".to_string());
+    config_section.env_file_directory = Some("./base".to_string());
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+    external_config.env_files = Some(vec![EnvFile::Path("./list.env".to_string())]);
+
+    add_config_env_file(&mut external_config);
+
+    let env_files = external_config.env_files.unwrap();
+    assert_eq!(
+        get_env_file_paths(&env_files),
+        vec!["./list.env", "./shorthand.env"]
+    );
+    match env_files[1] {
+        EnvFile::Info(ref info) => assert_eq!(info.base_path.clone().unwrap(), "./base"),
+        _ => panic!("wrong env file type"),
+    };
+}
+
+#[test]
+fn add_config_env_file_without_env_files() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_file = Some("./shorthand.env".to_string());
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(config_section);
+
+    add_config_env_file(&mut external_config);
+
+    assert_eq!(
+        get_env_file_paths(&external_config.env_files.unwrap()),
+        vec!["./shorthand.env"]
+    );
+}
+
+#[test]
+fn add_config_env_file_not_defined() {
+    let mut external_config = ExternalConfig::new();
+    external_config.config = Some(ConfigSection::new());
+
+    add_config_env_file(&mut external_config);
+
+    assert!(external_config.env_files.is_none());
+}
+
+#[test]
+fn load_external_descriptor_config_env_file() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/env_file/Makefile.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(config.config.unwrap().env_file.unwrap(), "./shorthand.env");
+    assert_eq!(
+        get_env_file_paths(&config.env_files.unwrap()),
+        vec!["./list.env"]
+    );
+}
+
+#[test]
+fn load_config_env_file_overrides_extended() {
+    let config = load(
+        "./src/lib/test/makefiles/env_file/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert_eq!(config.config.env_file.unwrap(), "./shorthand.env");
+    let env_files = get_env_file_paths(&config.env_files);
+    assert!(env_files.contains(&"./list.env".to_string()));
+    assert!(env_files.contains(&"./shorthand.env".to_string()));
+    assert!(!env_files.contains(&"./base.env".to_string()));
+}
+
+fn create_empty_env_merge_maps() -> (IndexMap<String, EnvValue>, IndexMap<String, EnvValue>) {
+    let map1: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        CLEARED = "base"
+        KEPT = "base"
+
+        [development]
+        PROFILE_CLEARED = "base"
+        PROFILE_KEPT = "base"
+        "#,
+    )
+    .unwrap();
+    let map2: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        CLEARED = ""
+
+        [development]
+        PROFILE_CLEARED = ""
+        "#,
+    )
+    .unwrap();
+
+    (map1, map2)
+}
+
+#[test]
+fn merge_env_with_options_empty_is_unset() {
+    let (mut map1, mut map2) = create_empty_env_merge_maps();
+
+    let output = merge_env_with_options(&mut map1, &mut map2, &vec![], ":", true);
+
+    assert_eq!(output.len(), 2);
+    assert!(!output.contains_key("CLEARED"));
+    assert!(output.contains_key("KEPT"));
+    match output.get("development").unwrap() {
+        EnvValue::Profile(ref sub_env) => {
+            assert_eq!(sub_env.len(), 1);
+            assert!(!sub_env.contains_key("PROFILE_CLEARED"));
+            assert!(sub_env.contains_key("PROFILE_KEPT"));
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_env_with_options_empty_is_value() {
+    let (mut map1, mut map2) = create_empty_env_merge_maps();
+
+    let output = merge_env_with_options(&mut map1, &mut map2, &vec![], ":", false);
+
+    assert_eq!(output.len(), 3);
+    match output.get("CLEARED").unwrap() {
+        EnvValue::Value(ref value) => assert!(value.is_empty()),
+        _ => panic!("wrong value type"),
+    };
+    match output.get("development").unwrap() {
+        EnvValue::Profile(ref sub_env) => match sub_env.get("PROFILE_CLEARED").unwrap() {
+            EnvValue::Value(ref value) => assert!(value.is_empty()),
+            _ => panic!("wrong value type"),
+        },
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_external_configs_empty_env_is_unset() {
+    let (map1, map2) = create_empty_env_merge_maps();
+    let mut parent_config = ExternalConfig::new();
+    parent_config.env = Some(map1);
+    let mut config_section = ConfigSection::new();
+    config_section.empty_env_is_unset = Some(true);
+    parent_config.config = Some(config_section);
+    let mut config = ExternalConfig::new();
+    config.env = Some(map2);
+
+    let output = merge_external_configs(config, parent_config);
+
+    let env = output.env.unwrap();
+    assert!(!env.contains_key("CLEARED"));
+    assert!(env.contains_key("KEPT"));
+}
+
+#[test]
+fn add_file_location_info_env_value_files() {
+    let env: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        TOKEN = { from_file = "secret.txt", trim = true }
+        OTHER = { from_file = "other.txt", base_path = "./custom" }
+
+        [profile]
+        PROFILE_TOKEN = { from_file = "profile.txt", optional = true }
+        "#,
+    )
+    .unwrap();
+    let mut task = Task::new();
+    task.env = Some(env.clone());
+    let mut tasks = IndexMap::new();
+    tasks.insert("test".to_string(), task);
+    let mut external_config = ExternalConfig::new();
+    external_config.env = Some(env);
+    external_config.tasks = Some(tasks);
+
+    let external_config = add_file_location_info(external_config, "./base/Makefile.toml", "./base");
+
+    let tasks = external_config.tasks.unwrap();
+    for env in vec![
+        external_config.env.unwrap(),
+        tasks.get("test").unwrap().env.clone().unwrap(),
+    ] {
+        match env.get("TOKEN").unwrap() {
+            EnvValue::File(ref file) => {
+                assert_eq!(file.from_file, "secret.txt");
+                assert!(file.trim.unwrap());
+                assert_eq!(file.base_path.clone().unwrap(), "./base");
+            }
+            _ => panic!("wrong value type"),
+        };
+        match env.get("OTHER").unwrap() {
+            EnvValue::File(ref file) => assert_eq!(file.base_path.clone().unwrap(), "./custom"),
+            _ => panic!("wrong value type"),
+        };
+        match env.get("profile").unwrap() {
+            EnvValue::Profile(ref sub_env) => match sub_env.get("PROFILE_TOKEN").unwrap() {
+                EnvValue::File(ref file) => {
+                    assert!(file.optional.unwrap());
+                    assert_eq!(file.base_path.clone().unwrap(), "./base");
+                }
+                _ => panic!("wrong value type"),
+            },
+            _ => panic!("wrong value type"),
+        };
+    }
+}
+
+#[test]
+fn merge_env_file_value() {
+    let mut map1: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        TOKEN = "plain"
+        "#,
+    )
+    .unwrap();
+    let mut map2: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        TOKEN = { from_file = "secret.txt", trim = true }
+        "#,
+    )
+    .unwrap();
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("TOKEN").unwrap() {
+        &EnvValue::File(ref file) => {
+            assert_eq!(file.from_file, "secret.txt");
+            assert!(file.trim.unwrap());
+        }
+        _ => panic!("wrong value type"),
+    };
+}
+
+#[test]
+fn merge_tasks_task_env_files_merged() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.env_files = Some(vec![
+        EnvFile::Path("./base.env".to_string()),
+        EnvFile::Path("./shared.env".to_string()),
+    ]);
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.env_files = Some(vec![
+        EnvFile::Path("./extended.env".to_string()),
+        EnvFile::Path("./shared.env".to_string()),
+    ]);
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let env_files = output.get("test").unwrap().env_files.clone().unwrap();
+    assert_eq!(
+        get_env_file_paths(&env_files),
+        vec!["./extended.env", "./shared.env", "./base.env"]
+    );
+}
+
+#[test]
+fn merge_tasks_task_env_files_base_only() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.env_files = Some(vec![EnvFile::Path("./base.env".to_string())]);
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.command = Some("echo".to_string());
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let env_files = output.get("test").unwrap().env_files.clone().unwrap();
+    assert_eq!(get_env_file_paths(&env_files), vec!["./base.env"]);
+}
+
+#[test]
+fn merge_tasks_task_env_files_clear() {
+    let mut map1 = IndexMap::<String, Task>::new();
+    let mut map2 = IndexMap::<String, Task>::new();
+
+    let mut task1 = Task::new();
+    task1.env_files = Some(vec![EnvFile::Path("./base.env".to_string())]);
+    map1.insert("test".to_string(), task1);
+
+    let mut task2 = Task::new();
+    task2.clear = Some(true);
+    task2.env_files = Some(vec![EnvFile::Path("./extended.env".to_string())]);
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
+
+    let env_files = output.get("test").unwrap().env_files.clone().unwrap();
+    assert_eq!(get_env_file_paths(&env_files), vec!["./extended.env"]);
+}
+
+#[test]
+fn load_with_output_trace_two_level_extend() {
+    let LoadOutput {
+        config, load_trace, ..
+    } = load_with_output(
+        "./src/lib/test/makefiles/trace/level1.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("trace-level1"));
+    assert!(config.tasks.contains_key("trace-level2"));
+    assert!(config.tasks.contains_key("trace-level3"));
+
+    assert_eq!(load_trace.len(), 3);
+    assert!(load_trace[0].ends_with("level3.toml"));
+    assert!(load_trace[1].ends_with("level2.toml"));
+    assert!(load_trace[2].ends_with("level1.toml"));
+    for path in load_trace.iter() {
+        assert!(Path::new(path).is_absolute());
+    }
+}
+
+#[test]
+fn load_with_output_events_task_override() {
+    let LoadOutput {
+        config,
+        merge_events: events,
+        ..
+    } = load_with_output(
+        "./src/lib/test/makefiles/merge_events/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let task = config.tasks.get("merge-events-overridden").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "extending");
+
+    let task_events: Vec<&MergeEvent> = events
+        .iter()
+        .filter(|event| {
+            event.section == MergeSection::Task && event.key.starts_with("merge-events")
+        })
+        .collect();
+    assert_eq!(task_events.len(), 1);
+    assert_eq!(task_events[0].key, "merge-events-overridden");
+    assert!(task_events[0]
+        .previous_source
+        .clone()
+        .unwrap()
+        .ends_with("base.toml"));
+    assert!(task_events[0]
+        .new_source
+        .clone()
+        .unwrap()
+        .ends_with("extending.toml"));
+}
+
+#[test]
+fn load_with_output_events_env_override() {
+    let LoadOutput {
+        config,
+        merge_events: events,
+        ..
+    } = load_with_output(
+        "./src/lib/test/makefiles/merge_events/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    match config.env.get("MERGE_EVENTS_OVERRIDDEN").unwrap() {
+        EnvValue::Value(ref value) => assert_eq!(value, "extending"),
+        _ => panic!("wrong value type"),
+    };
+
+    let env_events: Vec<&MergeEvent> = events
+        .iter()
+        .filter(|event| event.section == MergeSection::Env && event.key.starts_with("MERGE_EVENTS"))
+        .collect();
+    assert_eq!(env_events.len(), 1);
+    assert_eq!(env_events[0].key, "MERGE_EVENTS_OVERRIDDEN");
+    assert!(env_events[0]
+        .previous_source
+        .clone()
+        .unwrap()
+        .ends_with("base.toml"));
+    assert!(env_events[0]
+        .new_source
+        .clone()
+        .unwrap()
+        .ends_with("extending.toml"));
+}
+
+#[test]
+fn load_with_output_events_precedence_order() {
+    let LoadOutput {
+        config,
+        merge_events: events,
+        ..
+    } = load_with_output(
+        "./src/lib/test/makefiles/merge_events/top.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let task = config.tasks.get("merge-events-overridden").unwrap();
+    assert_eq!(task.command.clone().unwrap(), "top");
+
+    let task_events: Vec<&MergeEvent> = events
+        .iter()
+        .filter(|event| event.key == "merge-events-overridden")
+        .collect();
+    assert_eq!(task_events.len(), 2);
+    assert!(task_events[0]
+        .new_source
+        .clone()
+        .unwrap()
+        .ends_with("extending.toml"));
+    assert!(task_events[1]
+        .previous_source
+        .clone()
+        .unwrap()
+        .ends_with("extending.toml"));
+    assert!(task_events[1]
+        .new_source
+        .clone()
+        .unwrap()
+        .ends_with("top.toml"));
+
+    let env_events: Vec<&MergeEvent> = events
+        .iter()
+        .filter(|event| event.key == "MERGE_EVENTS_OVERRIDDEN")
+        .collect();
+    assert_eq!(env_events.len(), 2);
+    assert!(env_events[0]
+        .previous_source
+        .clone()
+        .unwrap()
+        .ends_with("base.toml"));
+    assert!(env_events[1]
+        .previous_source
+        .clone()
+        .unwrap()
+        .ends_with("extending.toml"));
+    assert!(env_events[1]
+        .new_source
+        .clone()
+        .unwrap()
+        .ends_with("top.toml"));
+}
+
+#[test]
+fn merge_tasks_no_merge_events_collected() {
     let mut map1 = IndexMap::<String, Task>::new();
     let mut map2 = IndexMap::<String, Task>::new();
+    map1.insert("test".to_string(), Task::new());
+    map2.insert("test".to_string(), Task::new());
+
+    merge_tasks(map1, map2, false);
+
+    MERGE_EVENTS.with(|events| assert!(events.borrow().is_none()));
+}
+
+#[test]
+fn load_with_trace_extend_list() {
+    let load_trace = RefCell::new(vec![]);
+    let list = vec![
+        ExtendOptions {
+            path: "src/lib/test/makefiles/list/list1.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+        ExtendOptions {
+            path: "src/lib/test/makefiles/list/list2.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env: None,
+            import_config: None,
+            import_env_files: None,
+            import_env_scripts: None,
+        },
+    ];
+
+    load_descriptor_extended_makefiles(
+        ".",
+        &Extend::List(list),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        Some(&load_trace),
+    )
+    .unwrap();
+
+    let load_trace = load_trace.into_inner();
+    assert_eq!(load_trace.len(), 2);
+    assert!(load_trace[0].ends_with("list1.toml"));
+    assert!(load_trace[1].ends_with("list2.toml"));
+}
+
+#[test]
+fn resolve_task_origin_overridden_task() {
+    let config = load(
+        "./src/lib/test/makefiles/origin/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    let origin = resolve_task_origin(&config, "origin-overridden").unwrap();
+    assert!(Path::new(&origin).is_absolute());
+    assert!(origin.ends_with("extending.toml"));
+
+    let origin = resolve_task_origin(&config, "origin-base-only").unwrap();
+    assert!(Path::new(&origin).is_absolute());
+    assert!(origin.ends_with("base.toml"));
+}
+
+#[test]
+fn resolve_task_origin_core_task() {
+    let config = load(
+        "./src/lib/test/makefiles/origin/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("empty"));
+    assert!(resolve_task_origin(&config, "empty").is_none());
+}
+
+#[test]
+fn resolve_task_origin_unknown_task() {
+    let config = load(
+        "./src/lib/test/makefiles/origin/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(resolve_task_origin(&config, "origin-unknown").is_none());
+}
+
+#[test]
+fn load_external_descriptor_extend_mode_base() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_mode/base.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("shared"));
+    let task = tasks.get("conflict").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["current".to_string()]);
+}
+
+#[test]
+fn load_external_descriptor_extend_mode_overlay() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_mode/overlay.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("shared"));
+    assert!(tasks.contains_key("current"));
+    let task = tasks.get("conflict").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["shared".to_string()]);
+}
+
+#[test]
+fn load_external_descriptor_extend_mode_list() {
+    let config = load_external_descriptor(
+        ".",
+        "src/lib/test/makefiles/extend_mode/list.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("list3"));
+    let mut task = tasks.get("conflict").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["shared".to_string()]);
+    task = tasks.get("list_task").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["current".to_string()]);
+}
+
+#[test]
+fn find_descriptor_file_none_found() {
+    let file_name = find_descriptor_file(
+        "./src/lib/test/makefiles/search",
+        &["Makefile.toml", ".cargo-make.toml"],
+    );
+
+    assert!(file_name.is_none());
+}
+
+#[test]
+fn find_descriptor_file_fallback_only() {
+    let base_path = "./src/lib/test/makefiles/search/fallback";
+
+    let file_name =
+        find_descriptor_file(base_path, &["Makefile.toml", ".cargo-make.toml"]).unwrap();
+
+    assert_eq!(file_name, ".cargo-make.toml");
+    let config = load_external_descriptor(
+        base_path,
+        &file_name,
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+    let tasks = config.tasks.unwrap();
+    let task = tasks.get("search").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["fallback".to_string()]);
+}
+
+#[test]
+fn find_descriptor_file_primary_wins() {
+    let base_path = "./src/lib/test/makefiles/search/both";
+
+    let file_name =
+        find_descriptor_file(base_path, &["Makefile.toml", ".cargo-make.toml"]).unwrap();
+
+    assert_eq!(file_name, "Makefile.toml");
+    let config = load_external_descriptor(
+        base_path,
+        &file_name,
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+    let tasks = config.tasks.unwrap();
+    let task = tasks.get("search").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["primary".to_string()]);
+}
+
+fn create_config_with_task_names(names: Vec<&str>) -> Config {
+    let mut tasks = IndexMap::new();
+    for name in names {
+        tasks.insert(name.to_string(), Task::new());
+    }
+
+    let mut config_section = ConfigSection::new();
+    config_section.validate_task_names = Some(true);
+
+    Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks,
+    }
+}
+
+#[test]
+fn normalize_task_names_valid() {
+    let mut config = create_config_with_task_names(vec!["build", "test-2", "ci-flow"]);
+
+    normalize_task_names(&mut config).unwrap();
+
+    let names: Vec<&String> = config.tasks.keys().collect();
+    assert_eq!(names, vec!["build", "test-2", "ci-flow"]);
+}
+
+#[test]
+fn normalize_task_names_invalid_name() {
+    let mut config = create_config_with_task_names(vec!["build", "my_task"]);
+
+    match normalize_task_names(&mut config) {
+        Err(DescriptorError::InvalidTaskName { task, pattern }) => {
+            assert_eq!(task, "my_task");
+            assert_eq!(pattern, "^[a-z0-9-]+(::[a-z0-9-]+)*$");
+        }
+        _ => panic!("invalid task name not detected"),
+    };
+}
 
-    let output = merge_tasks(&mut map1, &mut map2, false);
-    assert_eq!(output.len(), 0);
+#[test]
+fn normalize_task_names_namespace() {
+    let mut config = create_config_with_task_names(vec!["build", "default::ci-flow"]);
+
+    normalize_task_names(&mut config).unwrap();
+
+    let names: Vec<&String> = config.tasks.keys().collect();
+    assert_eq!(names, vec!["build", "default::ci-flow"]);
 }
 
 #[test]
-fn merge_tasks_first_empty() {
-    let mut map1 = IndexMap::<String, Task>::new();
-    let mut map2 = IndexMap::<String, Task>::new();
+fn normalize_task_names_invalid_namespace() {
+    let mut config = create_config_with_task_names(vec!["build", "default::"]);
+
+    match normalize_task_names(&mut config) {
+        Err(DescriptorError::InvalidTaskName { task, .. }) => assert_eq!(task, "default::"),
+        _ => panic!("invalid task name not detected"),
+    };
+}
+
+#[test]
+fn normalize_task_names_custom_pattern() {
+    let mut config = create_config_with_task_names(vec!["build", "my_task"]);
+    config.config.task_name_pattern = Some("^[a-z_]+$".to_string());
+
+    normalize_task_names(&mut config).unwrap();
+
+    assert_eq!(config.tasks.len(), 2);
+}
+
+#[test]
+fn normalize_task_names_invalid_pattern() {
+    let mut config = create_config_with_task_names(vec!["build"]);
+    config.config.task_name_pattern = Some("^[a-z".to_string());
+
+    match normalize_task_names(&mut config) {
+        Err(DescriptorError::InvalidTaskNamePattern(_)) => (),
+        _ => panic!("invalid pattern not detected"),
+    };
+}
+
+#[test]
+fn normalize_task_names_case_conflict() {
+    let mut config = create_config_with_task_names(vec!["build", "test", "Build"]);
+    config.config.task_name_pattern = Some("^[a-zA-Z]+$".to_string());
+
+    match normalize_task_names(&mut config) {
+        Err(DescriptorError::TaskNameConflict { task, other }) => {
+            assert_eq!(task, "Build");
+            assert_eq!(other, "build");
+        }
+        _ => panic!("task name conflict not detected"),
+    };
+}
+
+#[test]
+fn normalize_task_names_fold_case() {
+    let mut config = create_config_with_task_names(vec!["Build", "test"]);
+    config.config.fold_task_name_case = Some(true);
+    let test_task = config.tasks.get_mut("test").unwrap();
+    test_task.dependencies = Some(vec!["Build".to_string()]);
+    test_task.alias = Some("Build".to_string());
+
+    normalize_task_names(&mut config).unwrap();
+
+    let names: Vec<&String> = config.tasks.keys().collect();
+    assert_eq!(names, vec!["build", "test"]);
+    let test_task = config.tasks.get("test").unwrap();
+    assert_eq!(test_task.dependencies.clone().unwrap(), vec!["build"]);
+    assert_eq!(test_task.alias.clone().unwrap(), "build");
+}
+
+#[test]
+fn load_with_external_config_validate_task_names() {
+    let load_external_config = || {
+        let mut external_config = ExternalConfig::new();
+        let mut config_section = ConfigSection::new();
+        config_section.validate_task_names = Some(true);
+        external_config.config = Some(config_section);
+        let mut tasks = IndexMap::new();
+        tasks.insert("Invalid_Name".to_string(), Task::new());
+        external_config.tasks = Some(tasks);
+
+        Ok(external_config)
+    };
+
+    let output = load_with_external_config(
+        &load_external_config,
+        None,
+        false,
+        false,
+        &LoadOptions::new(),
+    );
+
+    match output {
+        Err(DescriptorError::InvalidTaskName { task, .. }) => assert_eq!(task, "Invalid_Name"),
+        _ => panic!("invalid task name not detected"),
+    };
+}
+
+#[test]
+fn load_with_external_config_validate_task_names_namespace() {
+    let load_external_config = || {
+        let mut external_config = ExternalConfig::new();
+        let mut config_section = ConfigSection::new();
+        config_section.validate_task_names = Some(true);
+        config_section.modify_core_tasks = Some(ModifyConfig {
+            private: None,
+            namespace: Some("default".to_string()),
+            rename: None,
+        });
+        external_config.config = Some(config_section);
+        let mut tasks = IndexMap::new();
+        tasks.insert("my-task".to_string(), Task::new());
+        external_config.tasks = Some(tasks);
+
+        Ok(external_config)
+    };
+
+    let config = load_with_external_config(
+        &load_external_config,
+        None,
+        false,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+
+    assert!(config.tasks.contains_key("my-task"));
+    assert!(config.tasks.contains_key("default::empty"));
+    assert!(!config.tasks.contains_key("empty"));
+}
+
+#[test]
+fn get_extend_location_default() {
+    let (parent_path, path) = get_extend_location("./nested", "../base.toml", None);
+
+    assert_eq!(parent_path, "./nested");
+    assert_eq!(path, "../base.toml");
+}
+
+#[test]
+fn get_extend_location_file() {
+    let (parent_path, path) =
+        get_extend_location("./nested", "/base.toml", Some(ExtendRelativeTo::File));
+
+    assert_eq!(parent_path, "./nested");
+    assert_eq!(path, "/base.toml");
+}
+
+#[test]
+fn get_extend_location_cwd() {
+    let (parent_path, path) =
+        get_extend_location("./nested", "/common/base.toml", Some(ExtendRelativeTo::Cwd));
+
+    assert_eq!(parent_path, ".");
+    assert_eq!(path, "common/base.toml");
+}
 
+#[test]
+#[ignore]
+fn get_extend_location_workspace() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./src/lib/test/makefiles/relative_to/Makefile.toml",
+    );
+    let (parent_path, path) = get_extend_location(
+        "./nested",
+        "/common/base.toml",
+        Some(ExtendRelativeTo::Workspace),
+    );
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    assert_eq!(parent_path, "./src/lib/test/makefiles/relative_to");
+    assert_eq!(path, "common/base.toml");
+}
+
+#[test]
+#[ignore]
+fn get_extend_location_workspace_not_defined() {
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+    let (parent_path, path) = get_extend_location(
+        "./nested",
+        "common/base.toml",
+        Some(ExtendRelativeTo::Workspace),
+    );
+
+    assert_eq!(parent_path, ".");
+    assert_eq!(path, "common/base.toml");
+}
+
+#[test]
+fn load_external_descriptor_extend_relative_to_file() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/relative_to/nested/file.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("relative-to-base"));
+}
+
+#[test]
+fn load_external_descriptor_extend_relative_to_cwd() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/relative_to/nested/cwd.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(config.tasks.unwrap().contains_key("relative-to-base"));
+}
+
+#[test]
+#[ignore]
+fn load_external_descriptor_extend_relative_to_workspace() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./src/lib/test/makefiles/relative_to/Makefile.toml",
+    );
+    let output = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/relative_to/nested/workspace.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    );
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    let tasks = output.unwrap().tasks.unwrap();
+    assert!(tasks.contains_key("relative-to-base"));
+    assert!(!tasks.contains_key("relative-to-workspace"));
+}
+
+#[test]
+fn validate_task_valid() {
     let mut task = Task::new();
-    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
-    task.command = Some("test".to_string());
+    task.command = Some("echo".to_string());
+    task.dependencies = Some(vec!["build".to_string()]);
+    task.extend = Some("base".to_string());
 
-    map2.insert("test".to_string(), task);
+    assert!(validate_task("test", &task).is_ok());
+}
 
-    let output = merge_tasks(&mut map1, &mut map2, false);
-    assert_eq!(output.len(), 1);
-    let task = output.get("test").unwrap();
-    assert!(task.disabled.is_none());
-    assert!(task.alias.is_none());
-    assert!(task.linux_alias.is_none());
-    assert!(task.windows_alias.is_none());
-    assert!(task.mac_alias.is_none());
-    assert!(task.install_crate.is_some());
-    assert!(task.install_script.is_none());
-    assert!(task.command.is_some());
-    assert!(task.args.is_none());
-    assert!(task.script.is_none());
-    assert!(task.dependencies.is_none());
-    assert!(task.linux.is_none());
-    assert!(task.windows.is_none());
-    assert!(task.mac.is_none());
+#[test]
+fn validate_task_command_and_script() {
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    task.script = Some(ScriptValue::Text(vec!["echo test".to_string()]));
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Both command and script are defined."]);
 }
 
 #[test]
-fn merge_tasks_second_empty() {
+fn validate_task_run_task_and_command() {
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    task.run_task = Some(RunTaskInfo::Name("other".to_string()));
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Both run_task and command are defined."]);
+}
+
+#[test]
+fn validate_task_run_task_and_script() {
+    let mut task = Task::new();
+    task.script = Some(ScriptValue::Text(vec!["echo test".to_string()]));
+    task.run_task = Some(RunTaskInfo::Name("other".to_string()));
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Both run_task and script are defined."]);
+}
+
+#[test]
+fn validate_task_empty_dependency() {
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["build".to_string(), " ".to_string()]);
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Empty dependency name defined."]);
+}
+
+#[test]
+fn validate_task_empty_extend() {
+    let mut task = Task::new();
+    task.extend = Some("".to_string());
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Empty extend task name defined."]);
+}
+
+#[test]
+fn validate_task_extends_itself() {
+    let mut task = Task::new();
+    task.extend = Some("test".to_string());
+
+    assert!(validate_task("other", &task).is_ok());
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(problems, vec!["Task extends itself: test"]);
+}
+
+#[test]
+fn validate_task_all_problems() {
+    let mut task = Task::new();
+    task.command = Some("echo".to_string());
+    task.script = Some(ScriptValue::Text(vec!["echo test".to_string()]));
+    task.run_task = Some(RunTaskInfo::Name("other".to_string()));
+    task.dependencies = Some(vec!["".to_string()]);
+    task.extend = Some("test".to_string());
+
+    let problems = validate_task("test", &task).unwrap_err();
+
+    assert_eq!(
+        problems,
+        vec![
+            "Both command and script are defined.",
+            "Both run_task and command are defined.",
+            "Both run_task and script are defined.",
+            "Empty dependency name defined.",
+            "Task extends itself: test"
+        ]
+    );
+}
+
+#[test]
+fn merge_tasks_tags_union() {
     let mut map1 = IndexMap::<String, Task>::new();
     let mut map2 = IndexMap::<String, Task>::new();
 
-    let mut task = Task::new();
-    task.install_crate = Some(InstallCrate::Value("my crate".to_string()));
-    task.command = Some("test".to_string());
+    let mut task1 = Task::new();
+    task1.tags = Some(vec!["ci".to_string(), "local".to_string()]);
+    map1.insert("test".to_string(), task1);
 
-    map1.insert("test".to_string(), task);
+    let mut task2 = Task::new();
+    task2.tags = Some(vec!["release".to_string(), "ci".to_string()]);
+    map2.insert("test".to_string(), task2);
+
+    let output = merge_tasks(map1, map2, false);
 
-    let output = merge_tasks(&mut map1, &mut map2, false);
-    assert_eq!(output.len(), 1);
     let task = output.get("test").unwrap();
-    assert!(task.disabled.is_none());
-    assert!(task.alias.is_none());
-    assert!(task.linux_alias.is_none());
-    assert!(task.windows_alias.is_none());
-    assert!(task.mac_alias.is_none());
-    assert!(task.install_crate.is_some());
-    assert!(task.install_script.is_none());
-    assert!(task.command.is_some());
-    assert!(task.args.is_none());
-    assert!(task.script.is_none());
-    assert!(task.dependencies.is_none());
-    assert!(task.linux.is_none());
-    assert!(task.windows.is_none());
-    assert!(task.mac.is_none());
+    assert_eq!(
+        task.tags.clone().unwrap(),
+        vec!["ci".to_string(), "local".to_string(), "release".to_string()]
+    );
 }
 
 #[test]
-fn merge_tasks_both_with_values() {
+fn merge_tasks_tags_cleared() {
     let mut map1 = IndexMap::<String, Task>::new();
     let mut map2 = IndexMap::<String, Task>::new();
 
     let mut task1 = Task::new();
-    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
-    task1.command = Some("test".to_string());
-
+    task1.tags = Some(vec!["ci".to_string()]);
     map1.insert("test".to_string(), task1);
 
     let mut task2 = Task::new();
-    task2.command = Some("test".to_string());
+    task2.clear = Some(true);
+    task2.tags = Some(vec!["release".to_string()]);
+    map2.insert("test".to_string(), task2);
 
-    map2.insert("test2".to_string(), task2);
+    let output = merge_tasks(map1, map2, false);
+
+    let task = output.get("test").unwrap();
+    assert_eq!(task.tags.clone().unwrap(), vec!["release".to_string()]);
+}
+
+#[test]
+fn tasks_with_tag_found() {
+    let mut config = create_config_with_task_names(vec!["build", "test", "publish", "docs"]);
+    config.tasks.get_mut("build").unwrap().tags = Some(vec!["ci".to_string()]);
+    config.tasks.get_mut("test").unwrap().tags = Some(vec!["local".to_string(), "ci".to_string()]);
+    config.tasks.get_mut("publish").unwrap().tags = Some(vec!["release".to_string()]);
+
+    assert_eq!(tasks_with_tag(&config, "ci"), vec!["build", "test"]);
+    assert_eq!(tasks_with_tag(&config, "release"), vec!["publish"]);
+}
+
+#[test]
+fn tasks_with_tag_not_found() {
+    let mut config = create_config_with_task_names(vec!["build", "test"]);
+    config.tasks.get_mut("build").unwrap().tags = Some(vec!["ci".to_string()]);
+
+    assert!(tasks_with_tag(&config, "Ci").is_empty());
+    assert!(tasks_with_tag(&config, "release").is_empty());
+}
+
+#[test]
+fn base_tasks_defined() {
+    let tasks = base_tasks();
+
+    assert!(!tasks.is_empty());
+    assert!(tasks.contains_key("init"));
+    assert!(tasks.contains_key("end"));
+    assert!(!tasks.contains_key("ci-flow"));
+}
+
+#[test]
+fn stable_tasks_defined() {
+    let tasks = stable_tasks();
+
+    assert!(!tasks.is_empty());
+    assert!(tasks.contains_key("ci-flow"));
+    assert!(tasks.contains_key("build"));
+    assert!(!tasks.contains_key("coverage-lcov"));
+}
+
+#[test]
+fn experimental_tasks_defined() {
+    let tasks = experimental_tasks();
+
+    assert!(!tasks.is_empty());
+    assert!(tasks.contains_key("coverage-lcov"));
+    assert!(!tasks.contains_key("ci-flow"));
+}
+
+#[test]
+fn experimental_tasks_not_modified_by_load() {
+    let expected_task_names: Vec<String> = experimental_tasks().keys().cloned().collect();
+
+    load_internal_descriptors(true, true, None, &vec![]).unwrap();
+
+    let task_names: Vec<String> = experimental_tasks().keys().cloned().collect();
+    assert_eq!(task_names, expected_task_names);
+}
+
+#[test]
+fn get_toml_task_keys_all_definitions() {
+    let task_keys = get_toml_task_keys(
+        r#"
+[tasks]
+inline = { command = "echo" }
+"quoted.name" = { command = "echo" }
+
+[tasks.header]
+command = "echo"
+env = { A = "1" }
+
+[tasks.header.linux]
+command = "echo"
+
+[tasks.script]
+script = '''
+[tasks.in_script]
+in_script = "value"
+'''
+"#,
+    );
+
+    assert_eq!(task_keys, vec!["inline", "quoted.name", "header", "script"]);
+}
+
+#[test]
+fn deserialize_toml_descriptor_duplicate_task_tables() {
+    let output = deserialize_toml_descriptor(
+        r#"
+[tasks.duplicate]
+command = "echo"
+
+[tasks.duplicate]
+args = ["second"]
+"#,
+        "duplicate.toml",
+        false,
+    );
+
+    match output {
+        Err(DescriptorError::Parse { file, .. }) => assert_eq!(file, "duplicate.toml"),
+        _ => panic!("duplicate task tables not rejected by the parser"),
+    };
+}
+
+#[test]
+fn get_yaml_task_keys_all_definitions() {
+    let task_keys = get_yaml_task_keys(
+        r#"
+config:
+  skip_core_tasks: true
+tasks: # comment
+  first:
+    command: echo
+    env:
+      second: value
+
+  "quoted":
+    script: |
+      third:
+env:
+  fourth: value
+"#,
+    );
 
-    let output = merge_tasks(&mut map1, &mut map2, false);
-    assert_eq!(output.len(), 2);
+    assert_eq!(task_keys, vec!["first", "quoted"]);
+}
 
-    let mut task = output.get("test").unwrap();
-    assert!(task.disabled.is_none());
-    assert!(task.alias.is_none());
-    assert!(task.linux_alias.is_none());
-    assert!(task.windows_alias.is_none());
-    assert!(task.mac_alias.is_none());
-    assert!(task.install_crate.is_some());
-    assert!(task.install_script.is_none());
-    assert!(task.command.is_some());
-    assert!(task.args.is_none());
-    assert!(task.script.is_none());
-    assert!(task.dependencies.is_none());
-    assert!(task.linux.is_none());
-    assert!(task.windows.is_none());
-    assert!(task.mac.is_none());
+#[test]
+fn get_duplicate_keys_found() {
+    let keys = vec!["a", "b", "a", "c", "b", "a"]
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
 
-    task = output.get("test2").unwrap();
-    assert!(task.disabled.is_none());
-    assert!(task.alias.is_none());
-    assert!(task.linux_alias.is_none());
-    assert!(task.windows_alias.is_none());
-    assert!(task.mac_alias.is_none());
-    assert!(task.install_crate.is_none());
-    assert!(task.install_script.is_none());
-    assert!(task.command.is_some());
-    assert!(task.args.is_none());
-    assert!(task.script.is_none());
-    assert!(task.dependencies.is_none());
-    assert!(task.linux.is_none());
-    assert!(task.windows.is_none());
-    assert!(task.mac.is_none());
+    assert_eq!(get_duplicate_keys(keys), vec!["a", "b"]);
 }
 
 #[test]
-fn merge_tasks_extend_task() {
-    let mut map1 = IndexMap::<String, Task>::new();
-    let mut map2 = IndexMap::<String, Task>::new();
+fn load_external_descriptor_duplicate_task_keys() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/duplicate_task_keys.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
 
-    let mut task1 = Task::new();
-    task1.disabled = Some(false);
-    task1.install_crate = Some(InstallCrate::Value("my crate".to_string()));
-    task1.command = Some("test1".to_string());
+    assert_eq!(config.tasks.unwrap().len(), 2);
+}
 
-    map1.insert("test".to_string(), task1);
+#[test]
+fn load_external_descriptor_duplicate_task_keys_strict() {
+    let output = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/duplicate_task_keys.toml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    );
 
-    let mut task2 = Task::new();
-    task2.disabled = Some(true);
-    task2.command = Some("test2".to_string());
+    match output {
+        Err(DescriptorError::DuplicateTaskKeys { file, keys }) => {
+            assert!(file.ends_with("duplicate_task_keys.toml"));
+            assert_eq!(keys, vec!["duplicate"]);
+        }
+        _ => panic!("duplicate task keys not detected"),
+    };
+}
 
-    map2.insert("test".to_string(), task2);
+#[test]
+fn load_external_descriptor_duplicate_task_keys_yaml_strict() {
+    let output = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/yaml/duplicate_task_keys.yaml",
+        true,
+        false,
+        &vec![],
+        true,
+        true,
+        None,
+        None,
+    );
 
-    let output = merge_tasks(&mut map1, &mut map2, false);
-    assert_eq!(output.len(), 1);
+    match output {
+        Err(DescriptorError::DuplicateTaskKeys { file, keys }) => {
+            assert!(file.ends_with("duplicate_task_keys.yaml"));
+            assert_eq!(keys, vec!["duplicate"]);
+        }
+        _ => panic!("duplicate task keys not detected"),
+    };
+}
 
-    let task = output.get("test").unwrap();
-    assert!(task.disabled.is_some());
-    assert!(task.alias.is_none());
-    assert!(task.linux_alias.is_none());
-    assert!(task.windows_alias.is_none());
-    assert!(task.mac_alias.is_none());
-    assert!(task.install_crate.is_some());
-    assert!(task.install_script.is_none());
-    assert!(task.command.is_some());
-    assert!(task.args.is_none());
-    assert!(task.script.is_none());
-    assert!(task.dependencies.is_none());
-    assert!(task.linux.is_none());
-    assert!(task.windows.is_none());
-    assert!(task.mac.is_none());
+#[test]
+#[ignore]
+fn load_default_profile_not_set() {
+    let profile_name = profile::get();
+    profile::unset();
+
+    let config = load(
+        "./src/lib/test/makefiles/default_profile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    let default_profile_name = profile::get();
+    profile::set(&profile_name);
 
-    let task_clone = task.clone();
-    assert!(task_clone.disabled.unwrap());
     assert_eq!(
-        task_clone.install_crate.unwrap(),
-        InstallCrate::Value("my crate".to_string())
+        config.config.default_profile.unwrap(),
+        "default_profile_test"
     );
-    assert_eq!(task_clone.command.unwrap(), "test2");
+    assert_eq!(default_profile_name, "default_profile_test");
 }
 
 #[test]
 #[ignore]
-fn load_descriptors_load_workspace_makefile() {
-    envmnt::set(
-        "CARGO_MAKE_WORKSPACE_MAKEFILE",
-        "./examples/workspace/Makefile.toml",
-    );
-    let config = load_descriptors("./bad/bad.toml", false, None, false, false, None).unwrap();
-    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+fn load_default_profile_already_set() {
+    let profile_name = profile::get();
+    profile::set("active_profile_test");
 
-    let task = config.tasks.get("workspace-echo");
-    assert!(task.is_some());
+    load(
+        "./src/lib/test/makefiles/default_profile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    let active_profile_name = profile::get();
+    profile::set(&profile_name);
+
+    assert_eq!(active_profile_name, "active_profile_test");
 }
 
 #[test]
 #[ignore]
-fn load_descriptors_load_workspace_makefile_no_exists() {
-    envmnt::set(
-        "CARGO_MAKE_WORKSPACE_MAKEFILE",
-        "./examples/workspace/Makefile2.toml",
-    );
-    let config = load_descriptors("./bad/bad.toml", false, None, false, false, None).unwrap();
-    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+fn load_default_profile_extend_profiles() {
+    let profile_name = profile::get();
+    profile::unset();
 
-    let task = config.tasks.get("workspace-echo");
-    assert!(task.is_none());
+    let config = load(
+        "./src/lib/test/makefiles/default_profile/makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    let default_profile_name = profile::get();
+    profile::set(&profile_name);
+
+    assert_eq!(default_profile_name, "default_profile_extend_test");
+    assert!(config.tasks.contains_key("root"));
+    assert!(config.tasks.contains_key("profile-extended"));
 }
 
 #[test]
 #[ignore]
-fn load_descriptors_no_load_workspace_makefile() {
-    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
-    let config = load_descriptors("./bad/bad.toml", false, None, false, false, None).unwrap();
+fn load_from_str_default_profile_extend_profiles() {
+    let profile_name = profile::get();
+    profile::unset();
+
+    let config = load_from_str_with_base_path(
+        r#"
+        extend = { path = "./extended.toml", profiles = ["default_profile_extend_test"] }
+
+        [config]
+        default_profile = "default_profile_extend_test"
+        "#,
+        "./src/lib/test/makefiles/default_profile",
+        None,
+        false,
+    )
+    .unwrap();
+    let default_profile_name = profile::get();
+    profile::set(&profile_name);
 
-    let task = config.tasks.get("workspace-echo");
-    assert!(task.is_none());
+    assert_eq!(default_profile_name, "default_profile_extend_test");
+    assert!(config.tasks.contains_key("profile-extended"));
+}
+
+#[test]
+fn get_active_profile_provided() {
+    let mut config_section = ConfigSection::new();
+    config_section.default_profile = Some("other".to_string());
+
+    let profile_name = get_active_profile(Some("extended"), Some(&config_section));
+
+    assert_eq!(profile_name, "extended");
 }
 
 #[test]
 #[ignore]
-fn load_no_stable() {
-    let config = load("./examples/skip_core_tasks.toml", true, None, false).unwrap();
+fn get_active_profile_default_profile() {
+    let profile_name = profile::get();
+    profile::unset();
 
-    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_none());
+    let mut config_section = ConfigSection::new();
+    config_section.default_profile = Some(" Default_Profile_Test ".to_string());
 
-    assert!(config.env_scripts.is_empty());
-    assert!(config.env_files.is_empty());
+    let active_profile_name = get_active_profile(None, Some(&config_section));
+    let profile_set = profile::is_set();
+    profile::set(&profile_name);
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    task = config.tasks.get("default");
-    assert!(task.is_some());
-    task = config.tasks.get("init");
-    assert!(task.is_some());
-    task = config.tasks.get("end");
-    assert!(task.is_some());
-    task = config.tasks.get("wait");
-    assert!(task.is_none());
-    assert_eq!(config.config.init_task.unwrap(), "init");
-    assert_eq!(config.config.end_task.unwrap(), "end");
+    assert_eq!(active_profile_name, "default_profile_test");
+    assert!(!profile_set);
 }
 
 #[test]
 #[ignore]
-fn load_with_stable() {
-    let config = load("./examples/simple-example.toml", true, None, false).unwrap();
+fn get_active_profile_already_set() {
+    let profile_name = profile::get();
+    profile::set("active_profile_test");
 
-    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_some());
+    let mut config_section = ConfigSection::new();
+    config_section.default_profile = Some("default_profile_test".to_string());
 
-    assert!(config.env_scripts.is_empty());
-    assert!(config.env_files.is_empty());
+    let active_profile_name = get_active_profile(None, Some(&config_section));
+    profile::set(&profile_name);
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    task = config.tasks.get("default");
-    assert!(task.is_some());
-    task = config.tasks.get("init");
-    assert!(task.is_some());
-    task = config.tasks.get("end");
-    assert!(task.is_some());
-    task = config.tasks.get("wait");
-    assert!(task.is_some());
-    assert_eq!(config.config.init_task.unwrap(), "init");
-    assert_eq!(config.config.end_task.unwrap(), "end");
+    assert_eq!(active_profile_name, "active_profile_test");
 }
 
 #[test]
 #[ignore]
-fn load_with_modify() {
-    let config = load("./examples/modify_core_tasks.toml", true, None, false).unwrap();
+fn load_external_descriptor_default_profile_not_set() {
+    let profile_name = profile::get();
+    profile::unset();
 
-    assert!(config.env.get(&"RUST_BACKTRACE".to_string()).is_some());
+    let external_config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/default_profile/makefile.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+        None,
+    )
+    .unwrap();
+    let profile_set = profile::is_set();
+    profile::set(&profile_name);
+
+    // parsing has no side effects, the profile is only set by load
+    assert!(!profile_set);
+    assert!(external_config
+        .tasks
+        .unwrap()
+        .contains_key("profile-extended"));
+}
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_none());
-    task = config.tasks.get("default::empty");
-    assert!(task.is_some());
-    assert!(task.clone().unwrap().private.unwrap());
-    task = config.tasks.get("default::init");
-    assert!(task.is_some());
+fn load_import_library_makefile(
+    import_env: Option<bool>,
+    import_config: Option<bool>,
+    import_env_files: Option<bool>,
+    import_env_scripts: Option<bool>,
+) -> ExternalConfig {
+    load_descriptor_extended_makefiles(
+        ".",
+        &Extend::Options(ExtendOptions {
+            path: "src/lib/test/makefiles/import/library.toml".to_string(),
+            preset: None,
+            optional: None,
+            tasks: None,
+            include_dependencies: None,
+            profiles: None,
+            skip_core_tasks: None,
+            mode: None,
+            relative_to: None,
+            import_env,
+            import_config,
+            import_env_files,
+            import_env_scripts,
+        }),
+        &vec![],
+        false,
+        true,
+        &profile::get(),
+        None,
+    )
+    .unwrap()
 }
 
 #[test]
-#[should_panic]
-fn load_not_found() {
-    load("./examples/not-found.toml", true, None, false).unwrap();
+fn load_descriptor_extended_makefiles_import_all_by_default() {
+    let descriptor = load_import_library_makefile(None, None, None, None);
+
+    assert!(descriptor.env.unwrap().contains_key("LIBRARY_ENV"));
+    assert_eq!(descriptor.env_files.unwrap().len(), 1);
+    assert_eq!(descriptor.env_scripts.unwrap().len(), 1);
+    let config = descriptor.config.unwrap();
+    assert!(!config.default_to_workspace.unwrap());
+    assert!(config.additive_experimental_tasks.unwrap());
+    assert!(descriptor.tasks.unwrap().contains_key("library-task"));
 }
 
 #[test]
-fn load_internal_descriptors_no_stable() {
-    let config = load_internal_descriptors(false, false, None);
+fn load_descriptor_extended_makefiles_import_tasks_only() {
+    let descriptor =
+        load_import_library_makefile(Some(false), Some(false), Some(false), Some(false));
+
+    assert!(descriptor.env.is_none());
+    assert!(descriptor.env_files.is_none());
+    assert!(descriptor.env_scripts.is_none());
+    let config = descriptor.config.unwrap();
+    assert!(config.default_to_workspace.is_none());
+    assert!(config.additive_experimental_tasks.is_none());
+    assert!(descriptor.tasks.unwrap().contains_key("library-task"));
+}
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    task = config.tasks.get("wait");
-    assert!(task.is_none());
+#[test]
+fn load_descriptor_extended_makefiles_import_env_without_config() {
+    let descriptor = load_import_library_makefile(Some(true), Some(false), None, None);
+
+    assert!(descriptor.env.unwrap().contains_key("LIBRARY_ENV"));
+    assert_eq!(descriptor.env_files.unwrap().len(), 1);
+    assert_eq!(descriptor.env_scripts.unwrap().len(), 1);
+    let config = descriptor.config.unwrap();
+    assert!(config.default_to_workspace.is_none());
+    assert!(config.additive_experimental_tasks.is_none());
+    assert!(config
+        .get_env_source("LIBRARY_ENV")
+        .unwrap()
+        .ends_with("library.toml"));
 }
 
 #[test]
-fn load_internal_descriptors_with_stable() {
-    let config = load_internal_descriptors(true, false, None);
+fn load_external_descriptor_extend_import_tasks_only() {
+    let config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/import/tasks_only.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    task = config.tasks.get("wait");
-    assert!(task.is_some());
+    let env = config.env.unwrap();
+    assert!(env.contains_key("TASKS_ONLY_ENV"));
+    assert!(!env.contains_key("LIBRARY_ENV"));
+    assert!(config.env_files.unwrap_or(vec![]).is_empty());
+    assert!(config.env_scripts.unwrap_or(vec![]).is_empty());
+    let config_section = config.config.unwrap();
+    assert!(config_section.default_to_workspace.is_none());
+    assert!(config_section.additive_experimental_tasks.is_none());
+    let tasks = config.tasks.unwrap();
+    assert!(tasks.contains_key("library-task"));
+    assert!(tasks.contains_key("tasks-only-task"));
 }
 
 #[test]
-fn load_internal_descriptors_no_experimental() {
-    let config = load_internal_descriptors(true, false, None);
+fn load_external_descriptor_task_order_extend_chain_and_list() {
+    let chain_config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/order/chain.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
+    let list_config = load_external_descriptor(
+        ".",
+        "./src/lib/test/makefiles/order/list.toml",
+        true,
+        false,
+        &vec![],
+        false,
+        true,
+        None,
+        None,
+    )
+    .unwrap();
 
-    let mut task = config.tasks.get("ci-flow");
-    assert!(task.is_some());
-    task = config.tasks.get("coverage-lcov");
-    assert!(task.is_none());
+    let chain_tasks = chain_config.tasks.unwrap();
+    let list_tasks = list_config.tasks.unwrap();
+    assert_eq!(
+        get_task_names(&chain_tasks),
+        vec!["a", "b", "c", "d", "e", "f"]
+    );
+    assert_eq!(get_task_names(&chain_tasks), get_task_names(&list_tasks));
+
+    for (name, task) in chain_tasks.iter() {
+        assert_eq!(task.command, list_tasks.get(name).unwrap().command);
+    }
+    assert_eq!(
+        chain_tasks.get("a").unwrap().command.clone().unwrap(),
+        "top"
+    );
+    assert_eq!(
+        chain_tasks.get("b").unwrap().command.clone().unwrap(),
+        "middle"
+    );
 }
 
 #[test]
-fn load_internal_descriptors_with_experimental() {
-    let config = load_internal_descriptors(true, true, None);
+fn load_env_block() {
+    let config = load(
+        "./src/lib/test/makefiles/env_blocks/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
 
-    let mut task = config.tasks.get("ci-flow");
-    assert!(task.is_some());
-    task = config.tasks.get("coverage-lcov");
-    assert!(task.is_some());
+    let env = config.tasks.get("block").unwrap().env.clone().unwrap();
+    assert_env_value(&env, "DB_HOST", "localhost");
+    assert_env_value(&env, "DB_PORT", "5432");
 }
 
 #[test]
-fn load_internal_descriptors_modify_empty() {
-    let config = load_internal_descriptors(
+fn load_env_block_task_env_overrides_block() {
+    let config = load(
+        "./src/lib/test/makefiles/env_blocks/Makefile.toml",
         true,
+        None,
         false,
-        Some(ModifyConfig {
-            private: None,
-            namespace: None,
-        }),
-    );
+        &LoadOptions::new(),
+    )
+    .unwrap();
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.is_none());
-    task = config.tasks.get("init");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.is_none());
+    let env = config
+        .tasks
+        .get("block-override")
+        .unwrap()
+        .env
+        .clone()
+        .unwrap();
+    assert_env_value(&env, "DB_HOST", "localhost");
+    assert_env_value(&env, "DB_PORT", "6543");
 }
 
 #[test]
-fn load_internal_descriptors_modify_private() {
-    let config = load_internal_descriptors(
+fn load_env_block_extended() {
+    let config = load(
+        "./src/lib/test/makefiles/env_blocks/extending.toml",
         true,
+        None,
         false,
-        Some(ModifyConfig {
-            private: Some(true),
-            namespace: None,
-        }),
-    );
+        &LoadOptions::new(),
+    )
+    .unwrap();
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.unwrap());
-    task = config.tasks.get("init");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.unwrap());
+    let env = config.tasks.get("block").unwrap().env.clone().unwrap();
+    assert_env_value(&env, "DB_HOST", "remote");
+    assert_env_value(&env, "DB_PORT", "5432");
 }
 
 #[test]
-fn load_internal_descriptors_modify_namespace() {
-    let config = load_internal_descriptors(
+fn load_env_block_unknown() {
+    let result = load(
+        "./src/lib/test/makefiles/env_blocks/unknown.toml",
         true,
+        None,
         false,
-        Some(ModifyConfig {
-            private: None,
-            namespace: Some("default".to_string()),
-        }),
+        &LoadOptions::new(),
     );
 
-    let mut task = config.tasks.get("empty");
-    assert!(task.is_none());
-    task = config.tasks.get("default::empty");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.is_none());
-    task = config.tasks.get("init");
-    assert!(task.is_none());
-    task = config.tasks.get("default::init");
-    assert!(task.is_some());
-    assert!(task.unwrap().private.is_none());
+    match result {
+        Err(DescriptorError::EnvBlockNotFound { task, block }) => {
+            assert_eq!(task, "unknown-block");
+            assert_eq!(block, "missing");
+        }
+        _ => panic!("unknown env block not detected"),
+    };
 }
 
 #[test]
-#[ignore]
-fn load_external_descriptor_no_file() {
-    let config = load_external_descriptor(".", "bad_file.toml2", false, false).unwrap();
-
-    assert!(config.config.is_none());
-    assert!(config.env.is_none());
-    assert!(config.tasks.is_none());
+fn merge_env_blocks_both_defined() {
+    let mut base_env = IndexMap::new();
+    base_env.insert("A".to_string(), EnvValue::Value("base".to_string()));
+    base_env.insert("B".to_string(), EnvValue::Value("base".to_string()));
+    let mut base = IndexMap::new();
+    base.insert("shared".to_string(), base_env.clone());
+    base.insert("base_only".to_string(), base_env);
+
+    let mut extended_env = IndexMap::new();
+    extended_env.insert("B".to_string(), EnvValue::Value("extended".to_string()));
+    let mut extended = IndexMap::new();
+    extended.insert("shared".to_string(), extended_env);
+
+    let env_blocks = merge_env_blocks(Some(base), Some(extended)).unwrap();
+
+    assert_eq!(env_blocks.len(), 2);
+    let shared = env_blocks.get("shared").unwrap();
+    assert_env_value(shared, "A", "base");
+    assert_env_value(shared, "B", "extended");
+    assert_env_value(env_blocks.get("base_only").unwrap(), "B", "base");
 }
 
 #[test]
-#[should_panic]
-fn load_external_descriptor_no_file_force() {
-    load_external_descriptor(".", "bad_file.toml2", true, false).unwrap();
+fn merge_env_blocks_none() {
+    assert!(merge_env_blocks(None, None).is_none());
 }
 
 #[test]
-#[should_panic]
-fn load_external_descriptor_extended_not_found_force() {
-    load_external_descriptor(".", "./examples/extends_not_found.toml", true, false).unwrap();
+fn merge_env_profile_override_value() {
+    let mut map1: IndexMap<String, EnvValue> = toml::from_str(r#"test = "1""#).unwrap();
+    let mut map2: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        [test]
+        KEY = "2"
+        "#,
+    )
+    .unwrap();
+
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Profile(ref profile_env) => assert_env_value(profile_env, "KEY", "2"),
+        _ => panic!("wrong value type"),
+    };
 }
 
 #[test]
-#[ignore]
-fn load_external_descriptor_simple_file() {
-    let config = load_external_descriptor(".", "./examples/alias.toml", true, false).unwrap();
-
-    assert!(config.config.is_none());
-    assert!(config.env.is_none());
-    assert!(config.tasks.is_some());
+fn merge_env_value_override_profile() {
+    let mut map1: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        [test]
+        KEY = "1"
+        "#,
+    )
+    .unwrap();
+    let mut map2: IndexMap<String, EnvValue> = toml::from_str(r#"test = "2""#).unwrap();
 
-    let tasks = config.tasks.unwrap();
-    let test_task = tasks.get("D2").unwrap();
-    let alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "D");
+    let output = merge_env(&mut map1, &mut map2);
+    assert_eq!(output.len(), 1);
+    match output.get("test").unwrap() {
+        &EnvValue::Value(ref value) => assert_eq!(value, "2"),
+        _ => panic!("wrong value type"),
+    };
 }
 
-#[test]
-#[ignore]
-fn load_external_descriptor_extending_file() {
-    let config = load_external_descriptor(".", "examples/extending.toml", true, false).unwrap();
-
-    assert!(config.config.is_some());
-    assert!(config.env.is_some());
-    assert!(config.tasks.is_some());
+fn merge_env_types(
+    base_env: &str,
+    extended_env: &str,
+    strict_env_types: Option<bool>,
+) -> Result<ExternalConfig, DescriptorError> {
+    let mut parent_config = ExternalConfig::new();
+    parent_config.env = Some(toml::from_str(base_env).unwrap());
+    let mut config = ExternalConfig::new();
+    config.env = Some(toml::from_str(extended_env).unwrap());
+    let mut config_section = ConfigSection::new();
+    config_section.strict_env_types = strict_env_types;
+    config.config = Some(config_section);
+
+    merge_external_configs_checked(config, parent_config, false)
+}
 
-    assert_eq!(config.env.unwrap().len(), 0);
+fn assert_env_type_mismatch(
+    base_env: &str,
+    extended_env: &str,
+    base_type: &str,
+    extended_type: &str,
+) {
+    // without strict mode the extending value replaces the base value
+    for strict_env_types in vec![None, Some(false)] {
+        let config = merge_env_types(base_env, extended_env, strict_env_types).unwrap();
+        let extended: IndexMap<String, EnvValue> = toml::from_str(extended_env).unwrap();
+        let env = config.env.unwrap();
+        assert_eq!(
+            get_env_value_type(env.get("test").unwrap()),
+            get_env_value_type(extended.get("test").unwrap())
+        );
+    }
 
-    let tasks = config.tasks.unwrap();
-    let mut test_task = tasks.get("D2").unwrap();
-    let mut alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "D");
+    let error = merge_env_types(base_env, extended_env, Some(true)).unwrap_err();
+    match error {
+        DescriptorError::EnvTypeMismatch {
+            key,
+            base_type: error_base_type,
+            extended_type: error_extended_type,
+        } => {
+            assert_eq!(key, "test");
+            assert_eq!(error_base_type, base_type);
+            assert_eq!(error_extended_type, extended_type);
+        }
+        _ => panic!("wrong error type"),
+    }
+}
 
-    test_task = tasks.get("extended").unwrap();
-    alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "D2");
+fn assert_env_type_compatible(base_env: &str, extended_env: &str) {
+    for strict_env_types in vec![None, Some(false), Some(true)] {
+        assert!(merge_env_types(base_env, extended_env, strict_env_types).is_ok());
+    }
 }
 
 #[test]
-#[ignore]
-fn load_external_descriptor_extending_file_sub_folder() {
-    let config =
-        load_external_descriptor(".", "examples/files/extending.toml", true, false).unwrap();
-
-    assert!(config.config.is_some());
-    assert!(config.env.is_some());
-    assert!(config.tasks.is_some());
-
-    let config_section = config.config.unwrap();
-    assert!(config_section.init_task.is_some());
-    assert!(config_section.end_task.is_none());
-    assert_eq!(config_section.init_task.unwrap(), "test_init");
-
-    assert_eq!(config.env.unwrap().len(), 0);
-
-    let tasks = config.tasks.unwrap();
-    let mut test_task = tasks.get("D2").unwrap();
-    let mut alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "D");
-
-    test_task = tasks.get("extended").unwrap();
-    alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "D2");
-
-    test_task = tasks.get("extended2").unwrap();
-    alias = test_task.alias.clone();
-    assert_eq!(alias.unwrap(), "extended");
+fn merge_external_configs_checked_value_to_list() {
+    assert_env_type_mismatch(r#"test = "1""#, r#"test = ["1", "2"]"#, "value", "list");
 }
 
 #[test]
-#[ignore]
-fn load_external_descriptor_set_env() {
-    envmnt::set("CARGO_MAKE_MAKEFILE_PATH", "EMPTY");
-    assert_eq!(envmnt::get_or_panic("CARGO_MAKE_MAKEFILE_PATH"), "EMPTY");
+fn merge_external_configs_checked_list_to_value() {
+    assert_env_type_mismatch(r#"test = ["1", "2"]"#, r#"test = "1""#, "list", "value");
+}
 
-    load_external_descriptor(".", "./examples/alias.toml", true, true).unwrap();
+#[test]
+fn merge_external_configs_checked_value_to_profile() {
+    assert_env_type_mismatch(r#"test = "1""#, "[test]\nKEY = \"1\"", "value", "profile");
+}
 
-    assert!(envmnt::get_or_panic("CARGO_MAKE_MAKEFILE_PATH").ends_with("alias.toml"));
+#[test]
+fn merge_external_configs_checked_profile_to_value() {
+    assert_env_type_mismatch("[test]\nKEY = \"1\"", r#"test = "1""#, "profile", "value");
 }
 
 #[test]
-fn load_external_descriptor_min_version_broken_makefile_nopanic() {
-    // Ensure that we can properly get the min_version of a descriptor that
-    // doesn't match our internal data-structures.
-    assert_eq!(
-        load_external_descriptor(
-            ".",
-            "src/lib/test/makefiles/broken_makefile_minversion.toml",
-            false,
-            false
-        )
-        .err(),
-        Some("999.999.999".into())
+fn merge_external_configs_checked_list_to_profile() {
+    assert_env_type_mismatch(
+        r#"test = ["1", "2"]"#,
+        "[test]\nKEY = \"1\"",
+        "list",
+        "profile",
     );
 }
 
 #[test]
-#[should_panic]
-fn load_external_descriptor_broken_makefile_panic() {
-    // Ensure the descriptor we used in the test above is properly broken when
-    // it doesn't have a min_version. This is to ensure that the previous test
-    // is working properly - min_version is optional.
-    load_external_descriptor(
-        ".",
-        "src/lib/test/makefiles/broken_makefile.toml",
-        false,
-        false,
-    )
-    .unwrap();
+fn merge_external_configs_checked_profile_to_list() {
+    assert_env_type_mismatch(
+        "[test]\nKEY = \"1\"",
+        r#"test = ["1", "2"]"#,
+        "profile",
+        "list",
+    );
 }
 
 #[test]
-fn run_load_script_no_config_section() {
-    let external_config = ExternalConfig::new();
-
-    let invoked = run_load_script(&external_config);
-    assert!(!invoked);
+fn merge_external_configs_checked_scalar_variants() {
+    assert_env_type_compatible(r#"test = "1""#, "test = 1");
+    assert_env_type_compatible("test = true", r#"test = "1""#);
+    assert_env_type_compatible(
+        r#"test = "1""#,
+        r#"test = { value = "2", description = "test" }"#,
+    );
+    assert_env_type_compatible(r#"test = "1""#, r#"test = { script = ["echo 2"] }"#);
 }
 
 #[test]
-fn run_load_script_no_load_script() {
-    let mut external_config = ExternalConfig::new();
-    external_config.config = Some(ConfigSection::new());
-
-    let invoked = run_load_script(&external_config);
-    assert!(!invoked);
+fn merge_external_configs_checked_append() {
+    assert_env_type_compatible(r#"test = ["1"]"#, r#"test = { append = ["2"] }"#);
+    assert_env_type_compatible(r#"test = "1""#, r#"test = { append = ["2"] }"#);
+    assert_env_type_compatible(r#"test = { append = ["1"] }"#, r#"test = ["2"]"#);
 }
 
 #[test]
-fn run_load_script_valid_load_script() {
-    let mut config = ConfigSection::new();
-    config.load_script = Some(vec!["exit 0".to_string()]);
-
-    let mut external_config = ExternalConfig::new();
-    external_config.config = Some(config);
-
-    let invoked = run_load_script(&external_config);
-    assert!(invoked);
+fn merge_external_configs_checked_unset() {
+    assert_env_type_compatible(r#"test = ["1"]"#, "test = { unset = true }");
+    assert_env_type_compatible("test = { unset = true }", r#"test = ["1"]"#);
 }
 
 #[test]
-#[should_panic]
-fn run_load_script_invalid_load_script() {
-    let mut config = ConfigSection::new();
-    config.load_script = Some(vec!["exit 1".to_string()]);
+fn merge_external_configs_checked_empty_env_is_unset() {
+    let mut parent_config = ExternalConfig::new();
+    parent_config.env = Some(toml::from_str(r#"test = ["1"]"#).unwrap());
+    let mut config = ExternalConfig::new();
+    config.env = Some(toml::from_str(r#"test = """#).unwrap());
+    let mut config_section = ConfigSection::new();
+    config_section.strict_env_types = Some(true);
+    config_section.empty_env_is_unset = Some(true);
+    config.config = Some(config_section);
+
+    let config = merge_external_configs_checked(config, parent_config, false).unwrap();
+
+    assert!(!config.env.unwrap().contains_key("test"));
+}
 
-    let mut external_config = ExternalConfig::new();
-    external_config.config = Some(config);
+#[test]
+fn merge_external_configs_checked_profile_key() {
+    let error = merge_env_types(
+        "[development]\ntest = \"1\"",
+        "[development]\ntest = [\"1\"]",
+        Some(true),
+    )
+    .unwrap_err();
 
-    run_load_script(&external_config);
+    match error {
+        DescriptorError::EnvTypeMismatch { key, .. } => assert_eq!(key, "development.test"),
+        _ => panic!("wrong error type"),
+    }
 }
 
 #[test]
-fn run_load_script_valid_load_script_duckscript() {
-    envmnt::remove("run_load_script_valid_load_script_duckscript");
-    assert!(!envmnt::exists(
-        "run_load_script_valid_load_script_duckscript"
-    ));
-
-    let mut config = ConfigSection::new();
-    config.load_script = Some(vec![r#"#!@duckscript
-    set_env run_load_script_valid_load_script_duckscript true
-    "#
-    .to_string()]);
+fn merge_external_configs_checked_parent_strict() {
+    let mut parent_config = ExternalConfig::new();
+    parent_config.env = Some(toml::from_str(r#"test = "1""#).unwrap());
+    let mut config_section = ConfigSection::new();
+    config_section.strict_env_types = Some(true);
+    parent_config.config = Some(config_section);
+    let mut config = ExternalConfig::new();
+    config.env = Some(toml::from_str(r#"test = ["1"]"#).unwrap());
+
+    assert!(merge_external_configs_checked(config, parent_config, false).is_err());
+}
 
-    let mut external_config = ExternalConfig::new();
-    external_config.config = Some(config);
+#[test]
+fn load_strict_env_types_mismatch() {
+    let error = load(
+        "./src/lib/test/makefiles/strict_env_types/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap_err();
+
+    match error {
+        DescriptorError::EnvTypeMismatch {
+            key,
+            base_type,
+            extended_type,
+        } => {
+            assert_eq!(key, "STRICT_ENV_TYPES_VALUE");
+            assert_eq!(base_type, "value");
+            assert_eq!(extended_type, "list");
+        }
+        _ => panic!("wrong error type"),
+    }
+}
 
-    let invoked = run_load_script(&external_config);
-    assert!(invoked);
+#[test]
+fn load_strict_env_types_compatible() {
+    let config = load(
+        "./src/lib/test/makefiles/strict_env_types/compatible.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
 
-    assert!(envmnt::exists(
-        "run_load_script_valid_load_script_duckscript"
-    ));
-    assert!(envmnt::is_or(
-        "run_load_script_valid_load_script_duckscript",
-        false
-    ));
+    match config.env.get("STRICT_ENV_TYPES_LIST").unwrap() {
+        EnvValue::List(ref values) => assert_eq!(values, &vec!["1", "2", "3"]),
+        _ => panic!("wrong value type"),
+    }
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_path_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let descriptor = load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Path("src/lib/test/makefiles/test1.toml".to_string()),
+fn load_strict_env_types_disabled() {
+    let config = load(
+        "./src/lib/test/makefiles/strict_env_types/disabled.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
+    match config.env.get("STRICT_ENV_TYPES_VALUE").unwrap() {
+        EnvValue::List(ref values) => assert_eq!(values, &vec!["1", "2"]),
+        _ => panic!("wrong value type"),
+    }
+    assert_env_value(&config.env, "STRICT_ENV_TYPES_LIST", "extended");
 }
 
 #[test]
-#[ignore]
-#[should_panic]
-fn load_descriptor_extended_makefiles_path_not_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Path("src/lib/test/makefiles/bad.toml".to_string()),
+fn resolve_default_task_not_defined() {
+    let config = load(
+        "./src/lib/test/makefiles/origin/extending.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
+
+    assert!(resolve_default_task(&config).is_none());
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_options_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let descriptor = load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: None,
-        }),
+fn resolve_default_task_no_namespace() {
+    let config = load(
+        "./src/lib/test/makefiles/default_task/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
+    assert_eq!(resolve_default_task(&config).unwrap(), "build");
 }
 
 #[test]
-#[ignore]
-#[should_panic]
-fn load_descriptor_extended_makefiles_options_not_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/bad.toml".to_string(),
-            optional: None,
-        }),
+fn resolve_default_task_namespace() {
+    let mut config = load(
+        "./src/lib/test/makefiles/default_task/namespaced.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
+
+    assert!(!config.tasks.contains_key("build"));
+    assert_eq!(resolve_default_task(&config).unwrap(), "core::build");
+
+    // already namespaced names are used as is
+    config.config.default_task = Some("core::build".to_string());
+    assert_eq!(resolve_default_task(&config).unwrap(), "core::build");
+
+    // tasks which are not core tasks are not namespaced
+    config.config.default_task = Some("custom".to_string());
+    assert_eq!(resolve_default_task(&config).unwrap(), "custom");
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_options_exists_optional() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let descriptor = load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: Some(true),
-        }),
+fn resolve_default_task_not_found() {
+    let config = load(
+        "./src/lib/test/makefiles/default_task/missing.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
+    assert!(resolve_default_task(&config).is_none());
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_options_exists_not_optional() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let descriptor = load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: Some(false),
-        }),
+fn get_final_task_override_found() {
+    let mut base = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.is_final = Some(true);
+    base.insert("final".to_string(), task);
+    base.insert("open".to_string(), Task::new());
+    let mut extended = IndexMap::<String, Task>::new();
+    extended.insert("open".to_string(), Task::new());
+    extended.insert("final".to_string(), Task::new());
+
+    assert_eq!(
+        get_final_task_override(Some(&base), Some(&extended)).unwrap(),
+        "final"
+    );
+}
+
+#[test]
+fn get_final_task_override_not_found() {
+    let mut base = IndexMap::<String, Task>::new();
+    let mut task = Task::new();
+    task.is_final = Some(false);
+    base.insert("test".to_string(), task);
+    let mut extended = IndexMap::<String, Task>::new();
+    extended.insert("test".to_string(), Task::new());
+
+    assert!(get_final_task_override(Some(&base), Some(&extended)).is_none());
+    assert!(get_final_task_override(Some(&base), None).is_none());
+    assert!(get_final_task_override(None, Some(&extended)).is_none());
+}
+
+#[test]
+fn load_final_task_override_ignored() {
+    let config = load(
+        "./src/lib/test/makefiles/final/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
     )
     .unwrap();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
+    let task = config.tasks.get("final-locked").unwrap();
+    assert!(task.is_final.unwrap());
+    assert_eq!(task.args.clone().unwrap(), vec!["base"]);
+    let task = config.tasks.get("final-open").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["extended"]);
 }
 
 #[test]
-#[ignore]
-#[should_panic]
-fn load_descriptor_extended_makefiles_options_not_exists_optional() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let descriptor = load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/bad.toml".to_string(),
-            optional: Some(true),
-        }),
+fn load_final_task_override_strict() {
+    let error = load(
+        "./src/lib/test/makefiles/final/Makefile.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            strict: true,
+            ..LoadOptions::new()
+        },
     )
-    .unwrap();
+    .unwrap_err();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(!tasks.contains_key("test1"));
+    match error {
+        DescriptorError::FinalTaskOverride(task) => assert_eq!(task, "final-locked"),
+        _ => panic!("wrong error type"),
+    }
 }
 
 #[test]
-#[ignore]
-#[should_panic]
-fn load_descriptor_extended_makefiles_options_not_exists_not_optional() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    load_descriptor_extended_makefiles(
-        &parent_path,
-        &Extend::Options(ExtendOptions {
-            path: "src/lib/test/makefiles/bad.toml".to_string(),
-            optional: Some(false),
-        }),
+fn load_final_task_not_overridden_strict() {
+    let config = load(
+        "./src/lib/test/makefiles/final/not_overridden.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            strict: true,
+            ..LoadOptions::new()
+        },
     )
     .unwrap();
+
+    let task = config.tasks.get("final-locked").unwrap();
+    assert_eq!(task.args.clone().unwrap(), vec!["base"]);
+}
+
+struct FailingReader {}
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::new(std::io::ErrorKind::Other, "test"))
+    }
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_list_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let list = vec![
-        ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: Some(false),
-        },
-        ExtendOptions {
-            path: "src/lib/test/makefiles/test2.toml".to_string(),
-            optional: Some(false),
-        },
-    ];
-    let descriptor = load_descriptor_extended_makefiles(&parent_path, &Extend::List(list)).unwrap();
+fn read_stdin_descriptor_valid() {
+    let content = read_stdin_descriptor("[tasks.stdin]".as_bytes()).unwrap();
+    assert_eq!(content, "[tasks.stdin]");
+}
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
-    assert!(tasks.contains_key("test2"));
+#[test]
+fn read_root_stdin_descriptor_stdin() {
+    let content = read_root_stdin_descriptor("-", "[tasks.stdin]".as_bytes()).unwrap();
+    assert_eq!(content, Some("[tasks.stdin]".to_string()));
 }
 
 #[test]
-#[ignore]
-#[should_panic]
-fn load_descriptor_extended_makefiles_list_not_exists() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let list = vec![
-        ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: Some(false),
-        },
-        ExtendOptions {
-            path: "src/lib/test/makefiles/bad.toml".to_string(),
-            optional: Some(false),
-        },
-    ];
-    load_descriptor_extended_makefiles(&parent_path, &Extend::List(list)).unwrap();
+fn read_root_stdin_descriptor_file() {
+    let content = read_root_stdin_descriptor("Makefile.toml", FailingReader {}).unwrap();
+    assert!(content.is_none());
 }
 
 #[test]
-#[ignore]
-fn load_descriptor_extended_makefiles_list_exists_optional() {
-    let parent_path = envmnt::get_or_panic("CARGO_MAKE_WORKING_DIRECTORY");
-    let list = vec![
-        ExtendOptions {
-            path: "src/lib/test/makefiles/test1.toml".to_string(),
-            optional: Some(false),
-        },
-        ExtendOptions {
-            path: "src/lib/test/makefiles/bad.toml".to_string(),
-            optional: Some(true),
-        },
-    ];
-    let descriptor = load_descriptor_extended_makefiles(&parent_path, &Extend::List(list)).unwrap();
+fn read_stdin_descriptor_error() {
+    let error = read_stdin_descriptor(FailingReader {}).unwrap_err();
 
-    let tasks = descriptor.tasks.unwrap();
-    assert!(tasks.contains_key("test1"));
-    assert!(!tasks.contains_key("test2"));
+    match error {
+        DescriptorError::Stdin(message) => assert_eq!(message, "test"),
+        _ => panic!("wrong error type"),
+    }
 }
 
 #[test]
-fn check_makefile_min_version_empty() {
-    let toml_string = "";
-    let result = check_makefile_min_version(toml_string);
+fn load_stdin_descriptor_extend() {
+    let external_config = load_stdin_descriptor(
+        r#"
+        extend = "./src/lib/test/makefiles/final/base.toml"
 
-    assert!(result.is_ok());
+        [tasks.stdin-task]
+        command = "echo"
+
+        [tasks.final-open]
+        args = ["stdin"]
+        "#,
+        false,
+        &vec![],
+        false,
+        false,
+        None,
+    )
+    .unwrap();
+
+    let tasks = external_config.tasks.unwrap();
+    assert!(tasks.contains_key("stdin-task"));
+    assert!(tasks.contains_key("final-locked"));
+    assert_eq!(
+        tasks.get("final-open").unwrap().args.clone().unwrap(),
+        vec!["stdin"]
+    );
 }
 
 #[test]
-fn check_makefile_min_version_invalid_format() {
-    let toml_string = "123";
-    let result = check_makefile_min_version(toml_string);
+#[ignore]
+fn load_stdin() {
+    let output = load_with_output_from_reader(
+        "-",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+        "[tasks.stdin-task]\ncommand = \"echo\"".as_bytes(),
+    )
+    .unwrap();
 
-    assert!(result.is_ok());
+    assert!(output.config.tasks.contains_key("stdin-task"));
+    assert_eq!(envmnt::get_or_panic("CARGO_MAKE_MAKEFILE_PATH"), "<stdin>");
+
+    envmnt::remove("CARGO_MAKE_MAKEFILE_PATH");
 }
 
 #[test]
-fn check_makefile_min_version_no_config() {
-    let toml_string = "test = true";
-    let result = check_makefile_min_version(toml_string);
+#[ignore]
+fn load_stdin_multiple_loads() {
+    let output = load_with_output_from_reader(
+        "-",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+        "[tasks.stdin-first]\ncommand = \"echo\"".as_bytes(),
+    )
+    .unwrap();
 
-    assert!(result.is_ok());
+    assert!(output.config.tasks.contains_key("stdin-first"));
+
+    // every load reads its own stdin content
+    let output = load_with_output_from_reader(
+        "-",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+        "[tasks.stdin-second]\ncommand = \"echo\"".as_bytes(),
+    )
+    .unwrap();
+
+    assert!(output.config.tasks.contains_key("stdin-second"));
+    assert!(!output.config.tasks.contains_key("stdin-first"));
+
+    envmnt::remove("CARGO_MAKE_MAKEFILE_PATH");
 }
 
 #[test]
-fn check_makefile_min_version_no_min_version() {
-    let toml_string = r#"
-    [config]
-    test = true
-    "#;
-    let result = check_makefile_min_version(toml_string);
+#[ignore]
+fn load_skip_workspace_makefile_isolated() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./examples/workspace/Makefile.toml",
+    );
 
-    assert!(result.is_ok());
+    let merged_config = load(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        None,
+        false,
+        &LoadOptions::new(),
+    )
+    .unwrap();
+    let isolated_config = load(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            skip_workspace_makefile: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    assert!(merged_config.tasks.contains_key("workspace-echo"));
+    assert!(!isolated_config.tasks.contains_key("workspace-echo"));
+    assert!(isolated_config.tasks.contains_key("test1"));
 }
 
 #[test]
-fn check_makefile_min_version_smaller_min_version() {
-    let toml_string = r#"
-    [config]
-    min_version = "0.0.1"
-    "#;
-    let result = check_makefile_min_version(toml_string);
+#[ignore]
+fn load_with_output_trace_skip_workspace_makefile() {
+    envmnt::set(
+        "CARGO_MAKE_WORKSPACE_MAKEFILE",
+        "./examples/workspace/Makefile.toml",
+    );
 
-    assert!(result.is_ok());
+    let LoadOutput {
+        config,
+        load_trace: trace,
+        ..
+    } = load_with_output(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            skip_workspace_makefile: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
+    envmnt::remove("CARGO_MAKE_WORKSPACE_MAKEFILE");
+
+    assert!(!config.tasks.contains_key("workspace-echo"));
+    assert!(config.tasks.contains_key("test1"));
+    assert!(!trace.is_empty());
 }
 
 #[test]
-fn check_makefile_min_version_bigger_min_version() {
-    let toml_string = r#"
-    [config]
-    min_version = "999.999.999"
-    "#;
-    let result = check_makefile_min_version(toml_string);
+fn load_skip_workspace_makefile_not_defined() {
+    let config = load(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        None,
+        false,
+        &LoadOptions {
+            skip_workspace_makefile: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
 
-    assert!(result.is_err());
-    assert_eq!(result.err().unwrap(), "999.999.999");
+    assert!(config.tasks.contains_key("test1"));
+    assert!(config.tasks.contains_key("empty"));
 }
 
 #[test]
-fn check_makefile_min_version_same_min_version() {
-    let mut toml_string = r#"
-    [config]
-    min_version = ""#;
-    let mut buffer = toml_string.to_string();
-    buffer.push_str(env!("CARGO_PKG_VERSION"));
-    buffer.push_str("\"");
-    toml_string = buffer.as_str();
-    let result = check_makefile_min_version(toml_string);
+fn load_strict_cli_env_undefined_key() {
+    let config = load(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        Some(vec!["CLI_STRICT_UNDEFINED_TEST=cli".to_string()]),
+        false,
+        &LoadOptions {
+            strict: true,
+            ..LoadOptions::new()
+        },
+    )
+    .unwrap();
 
-    assert!(result.is_ok());
+    assert_env_value(&config.env, "CLI_STRICT_UNDEFINED_TEST", "cli");
+}
+
+#[test]
+fn load_strict_env_cli_env_undefined_key() {
+    let result = load(
+        "./src/lib/test/makefiles/test1.toml",
+        true,
+        Some(vec!["CLI_STRICT_UNDEFINED_TEST=cli".to_string()]),
+        false,
+        &LoadOptions {
+            strict_env: true,
+            ..LoadOptions::new()
+        },
+    );
+
+    match result {
+        Err(DescriptorError::UndefinedCliEnv(key)) => assert_eq!(key, "CLI_STRICT_UNDEFINED_TEST"),
+        _ => panic!("undefined cli env key not detected"),
+    };
 }
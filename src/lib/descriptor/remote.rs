@@ -0,0 +1,156 @@
+//! # remote
+//!
+//! Loads external descriptors from HTTP(S) URLs.<br>
+//! Downloaded descriptors are cached locally so they can be used in case the remote
+//! descriptor is not available (for example in offline builds).
+//!
+
+#[cfg(test)]
+#[path = "./remote_test.rs"]
+mod remote_test;
+
+use crate::storage;
+use attohttpc;
+use dirs;
+use envmnt;
+use fsio::file::{read_text_file, write_text_file};
+use std::path::PathBuf;
+use std::time::Duration;
+
+static CACHE_DIRECTORY: &'static str = "makefiles";
+// an unreachable host should fall back to the cached descriptor instead of hanging the build
+static CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+static READ_TIMEOUT: Duration = Duration::from_secs(30);
+static REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Returns true if the provided value is an HTTP(S) URL
+pub(crate) fn is_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// Returns the descriptor URL in case the file or the base path are URLs
+pub(crate) fn get_url(base_path: &str, file_name: &str) -> Option<String> {
+    if is_url(file_name) {
+        Some(file_name.to_string())
+    } else if is_url(base_path) {
+        Some(format!(
+            "{}/{}",
+            base_path.trim_end_matches('/'),
+            file_name.trim_start_matches("./")
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns the URL 'directory' used to resolve relative extended descriptors
+pub(crate) fn get_parent_url(url: &str) -> String {
+    let scheme_length = match url.find("://") {
+        Some(index) => index + 3,
+        None => 0,
+    };
+
+    match url.rfind('/') {
+        Some(index) if index > scheme_length => url[..index].to_string(),
+        _ => url.to_string(),
+    }
+}
+
+/// Returns the FNV-1a hash of the URL, which is stable across builds and platforms so the
+/// cache files can be found by later cargo-make invocations.
+fn hash_url(url: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for byte in url.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Returns the cache file of the URL, which is named by the URL hash so different URLs do not
+/// share a cache file and long URLs do not exceed the file name limits.
+fn get_cache_file(url: &str) -> Option<PathBuf> {
+    if envmnt::is("CARGO_MAKE_DISABLE_REMOTE_MAKEFILE_CACHE") {
+        None
+    } else {
+        let os_directory = dirs::cache_dir();
+        match storage::get_storage_directory(os_directory, CACHE_DIRECTORY, false) {
+            Some(directory) => {
+                let file_name = format!("{:016x}", hash_url(url));
+
+                Some(directory.join(CACHE_DIRECTORY).join(file_name))
+            }
+            None => None,
+        }
+    }
+}
+
+fn download(url: &str) -> Result<String, String> {
+    match attohttpc::get(url)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+    {
+        Ok(response) => {
+            if response.is_success() {
+                match response.text() {
+                    Ok(text) => Ok(text),
+                    Err(error) => Err(format!(
+                        "Unable to read remote descriptor: {}, {}",
+                        url, error
+                    )),
+                }
+            } else {
+                Err(format!(
+                    "Unable to download remote descriptor: {}, status: {}",
+                    url,
+                    response.status()
+                ))
+            }
+        }
+        Err(error) => Err(format!(
+            "Unable to download remote descriptor: {}, {}",
+            url, error
+        )),
+    }
+}
+
+fn load_with_cache(url: &str, cache_file: Option<PathBuf>) -> Result<String, String> {
+    match download(url) {
+        Ok(text) => {
+            if let Some(ref file) = cache_file {
+                if let Err(error) = write_text_file(file, &text) {
+                    debug!(
+                        "Unable to cache remote descriptor: {:?}, {}",
+                        &file,
+                        error.to_string()
+                    );
+                }
+            }
+
+            Ok(text)
+        }
+        Err(download_error) => match cache_file {
+            Some(ref file) if file.exists() => {
+                warn!("{}, using cached descriptor: {:?}", &download_error, &file);
+
+                match read_text_file(file) {
+                    Ok(text) => Ok(text),
+                    Err(error) => Err(format!("{}, {}", &download_error, error.to_string())),
+                }
+            }
+            _ => Err(download_error),
+        },
+    }
+}
+
+/// Downloads the remote descriptor text and caches it.<br>
+/// In case the download fails, the last cached descriptor text is returned (if exists).
+pub(crate) fn load(url: &str) -> Result<String, String> {
+    debug!("Loading remote descriptor: {}", &url);
+
+    load_with_cache(url, get_cache_file(url))
+}
@@ -0,0 +1,132 @@
+use super::*;
+use fsio;
+
+#[test]
+fn is_url_http() {
+    assert!(is_url("http://example.com/Makefile.toml"));
+}
+
+#[test]
+fn is_url_https() {
+    assert!(is_url("https://example.com/Makefile.toml"));
+}
+
+#[test]
+fn is_url_file() {
+    assert!(!is_url("./Makefile.toml"));
+}
+
+#[test]
+fn get_url_local() {
+    let url = get_url(".", "./Makefile.toml");
+
+    assert!(url.is_none());
+}
+
+#[test]
+fn get_url_file_name() {
+    let url = get_url(".", "https://example.com/Makefile.toml");
+
+    assert_eq!(url.unwrap(), "https://example.com/Makefile.toml");
+}
+
+#[test]
+fn get_url_base_path() {
+    let url = get_url("https://example.com/makefiles/", "./Makefile.toml");
+
+    assert_eq!(url.unwrap(), "https://example.com/makefiles/Makefile.toml");
+}
+
+#[test]
+fn get_parent_url_with_path() {
+    let url = get_parent_url("https://example.com/makefiles/Makefile.toml");
+
+    assert_eq!(url, "https://example.com/makefiles");
+}
+
+#[test]
+fn get_parent_url_no_path() {
+    let url = get_parent_url("https://example.com");
+
+    assert_eq!(url, "https://example.com");
+}
+
+#[test]
+fn hash_url_stable() {
+    assert_eq!(hash_url(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash_url("a"), 0xaf63_dc4c_8601_ec8c);
+}
+
+#[test]
+fn hash_url_different_urls() {
+    assert_ne!(
+        hash_url("https://example.com/a/b"),
+        hash_url("https://example.com/a_b")
+    );
+    assert_ne!(
+        hash_url("https://example.com/Makefile.toml?x=1"),
+        hash_url("https://example.com/Makefile.toml_x_1")
+    );
+}
+
+#[test]
+#[ignore]
+fn get_cache_file_hashed_name() {
+    let url = format!("https://example.com/{}/Makefile.toml", "a".repeat(500));
+    let cache_file = get_cache_file(&url).unwrap();
+
+    let file_name = cache_file.file_name().unwrap().to_str().unwrap();
+    assert_eq!(file_name, format!("{:016x}", hash_url(&url)));
+}
+
+#[test]
+fn get_cache_file_disabled() {
+    envmnt::set("CARGO_MAKE_DISABLE_REMOTE_MAKEFILE_CACHE", "true");
+    let cache_file = get_cache_file("https://example.com/Makefile.toml");
+    envmnt::remove("CARGO_MAKE_DISABLE_REMOTE_MAKEFILE_CACHE");
+
+    assert!(cache_file.is_none());
+}
+
+#[test]
+fn load_with_cache_not_found_no_cache() {
+    let result = load_with_cache("http://127.0.0.1:1/Makefile.toml", None);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn load_with_cache_not_found_cache_exists() {
+    let file = PathBuf::from("./target/_temp/remote/cached.toml");
+    fsio::file::write_text_file(&file, "[tasks.cached]").unwrap();
+
+    let text = load_with_cache("http://127.0.0.1:1/Makefile.toml", Some(file)).unwrap();
+
+    assert_eq!(text, "[tasks.cached]");
+}
+
+#[test]
+fn load_with_cache_not_found_cache_not_exists() {
+    let file = PathBuf::from("./target/_temp/remote/not_exists.toml");
+    fsio::file::delete_ignore_error(&file);
+
+    let result = load_with_cache("http://127.0.0.1:1/Makefile.toml", Some(file));
+
+    assert!(result.is_err());
+}
+
+#[test]
+#[ignore]
+fn load_with_cache_found() {
+    let file = PathBuf::from("./target/_temp/remote/found.toml");
+    fsio::file::delete_ignore_error(&file);
+
+    let text = load_with_cache(
+        "https://raw.githubusercontent.com/sagiegurari/cargo-make/master/examples/alias.toml",
+        Some(file.clone()),
+    )
+    .unwrap();
+
+    assert!(text.contains("[tasks.D2]"));
+    assert!(file.exists());
+}
@@ -14,23 +14,90 @@ use crate::condition;
 use crate::profile;
 use crate::scriptengine;
 use crate::types::{
-    CliArgs, Config, CrateInfo, EnvFile, EnvInfo, EnvValue, EnvValueConditioned, EnvValueDecode,
-    EnvValueScript, PackageInfo, ScriptValue, Step, Task, Workspace,
+    CliArgs, Config, CrateInfo, EnvFile, EnvInfo, EnvScript, EnvSetupOrder, EnvValue,
+    EnvValueConditioned, EnvValueDecode, EnvValueFile, EnvValueScript, PackageInfo, ScriptValue,
+    Step, Task, Workspace,
 };
 use ci_info::types::CiInfo;
 use duckscript;
 use duckscriptsdk;
 use envmnt;
-use envmnt::{ExpandOptions, ExpansionType};
 use fsio::path::from_path::FromPath;
 use git_info;
 use git_info::types::GitInfo;
 use indexmap::IndexMap;
 use rust_info;
 use rust_info::types::{RustChannel, RustInfo};
+use std::cell::RefCell;
 use std::env;
 use std::path::{Path, PathBuf};
 
+thread_local! {
+    // while set, the env values are read from and written into this copy of the process env
+    static ENV_COPY: RefCell<Option<IndexMap<String, String>>> = RefCell::new(None);
+    // while set, the ${} variables left unresolved by the value expansion are collected here
+    static UNRESOLVED_VARIABLES: RefCell<Option<Vec<String>>> = RefCell::new(None);
+}
+
+/// Returns the env value from the env copy (in case one is active) or the process env.
+fn get_env_value(key: &str) -> Option<String> {
+    ENV_COPY.with(|env_copy| match *env_copy.borrow() {
+        Some(ref env) => env.get(key).cloned(),
+        None => env::var(key).ok(),
+    })
+}
+
+/// Sets the env value in the env copy (in case one is active) or the process env.
+fn set_env_value(key: &str, value: &str) {
+    ENV_COPY.with(|env_copy| match *env_copy.borrow_mut() {
+        Some(ref mut env) => {
+            env.insert(key.to_string(), value.to_string());
+        }
+        None => envmnt::set(key, value),
+    })
+}
+
+/// Removes the env value from the env copy (in case one is active) or the process env.
+fn remove_env_value(key: &str) {
+    ENV_COPY.with(|env_copy| match *env_copy.borrow_mut() {
+        Some(ref mut env) => {
+            env.shift_remove(key);
+        }
+        None => envmnt::remove(key),
+    })
+}
+
+/// Runs the provided function while all env reads and writes go to a copy of the process env
+/// and returns the updated copy. The process env itself is not modified.
+fn with_env_copy<F: FnOnce()>(function: F) -> IndexMap<String, String> {
+    let env_copy: IndexMap<String, String> = env::vars().collect();
+    ENV_COPY.with(|current_env_copy| *current_env_copy.borrow_mut() = Some(env_copy));
+
+    function();
+
+    ENV_COPY
+        .with(|current_env_copy| current_env_copy.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+/// Adds the ${} variables left in the provided expanded value to the unresolved variables (each
+/// name only once), in case they are currently collected.
+fn add_unresolved_variables(expanded_value: &str) {
+    if !expanded_value.contains("${") {
+        return;
+    }
+
+    UNRESOLVED_VARIABLES.with(|unresolved_variables| {
+        if let Some(ref mut unresolved_variables) = *unresolved_variables.borrow_mut() {
+            for variable in get_unresolved_variables(expanded_value) {
+                if !unresolved_variables.contains(&variable) {
+                    unresolved_variables.push(variable);
+                }
+            }
+        }
+    });
+}
+
 fn evaluate_env_value(key: &str, env_value: &EnvValueScript) -> String {
     match command::run_script_get_output(&env_value.script, None, &vec![], true, Some(false)) {
         Ok(output) => {
@@ -78,24 +145,172 @@ fn evaluate_env_value(key: &str, env_value: &EnvValueScript) -> String {
     }
 }
 
+fn is_env_key_break(value: char) -> bool {
+    value == ' ' || value == '\n' || value == '\t' || value == '\r' || value == '='
+}
+
+/// Expands the ${KEY} variables (same as the envmnt unix brackets expansion) while reading the
+/// values from the env copy (in case one is active).<br>
+/// Undefined variables are left as is.
+fn expand_value_without_defaults(value: &str) -> String {
+    let mut expanded_value = String::new();
+    let mut env_key = String::new();
+    let mut found_dollar = false;
+    let mut found_prefix = false;
+
+    for next_char in value.chars() {
+        if !found_prefix {
+            if found_dollar && next_char == '{' {
+                found_prefix = true;
+                env_key.clear();
+            } else {
+                if found_dollar {
+                    expanded_value.push('$');
+                }
+                if next_char != '$' || found_dollar {
+                    expanded_value.push(next_char);
+                }
+            }
+            found_dollar = !found_dollar && !found_prefix && next_char == '$';
+        } else if next_char == '}' {
+            match get_env_value(&env_key) {
+                Some(env_value) => expanded_value.push_str(&env_value),
+                None => {
+                    expanded_value.push_str("${");
+                    expanded_value.push_str(&env_key);
+                    expanded_value.push('}');
+                }
+            };
+
+            found_prefix = false;
+        } else if is_env_key_break(next_char) {
+            expanded_value.push_str("${");
+            expanded_value.push_str(&env_key);
+            expanded_value.push(next_char);
+
+            found_prefix = false;
+        } else {
+            env_key.push(next_char);
+        }
+    }
+
+    if found_dollar {
+        expanded_value.push('$');
+    } else if found_prefix {
+        expanded_value.push_str("${");
+        expanded_value.push_str(&env_key);
+    }
+
+    expanded_value
+}
+
+/// Returns the index of the bracket which closes the ${ at the start of the value.
+fn find_closing_bracket(value: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut previous_char = None;
+
+    for (index, next_char) in value.char_indices() {
+        if next_char == '{' && previous_char == Some('$') {
+            depth = depth + 1;
+        } else if next_char == '}' && depth > 0 {
+            depth = depth - 1;
+
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+
+        previous_char = Some(next_char);
+    }
+
+    None
+}
+
+/// Parses the KEY:-default (default used if KEY is not defined) and KEY:=default
+/// (default used if KEY is not defined or empty) expressions and returns the key, whether
+/// an empty value uses the default and the default value.
+fn parse_default_expression(expression: &str) -> Option<(&str, bool, &str)> {
+    let separator_index = expression.find(':')?;
+    let key = &expression[..separator_index];
+
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|key_char| key_char.is_alphanumeric() || key_char == '_')
+    {
+        return None;
+    }
+
+    let operation = &expression[separator_index + 1..];
+    if operation.starts_with('-') {
+        Some((key, false, &operation[1..]))
+    } else if operation.starts_with('=') {
+        Some((key, true, &operation[1..]))
+    } else {
+        None
+    }
+}
+
+fn evaluate_default_expression(key: &str, empty_uses_default: bool, default_value: &str) -> String {
+    match get_env_value(key) {
+        Some(ref value) if !(empty_uses_default && value.is_empty()) => value.to_string(),
+        _ => expand_value(default_value),
+    }
+}
+
 pub(crate) fn expand_value(value: &str) -> String {
-    let mut options = ExpandOptions::new();
-    options.expansion_type = Some(ExpansionType::UnixBrackets);
-    options.default_to_empty = false;
+    let mut expanded_value = String::new();
+    let mut remaining_value = value;
+    let mut search_index = 0;
+
+    // the ${KEY:-default} and ${KEY:=default} expressions (which may be nested) are evaluated
+    // here while the rest of the value is expanded by envmnt
+    while let Some(found_index) = remaining_value[search_index..].find("${") {
+        let start_index = search_index + found_index;
+        let expression_value = &remaining_value[start_index..];
+
+        let evaluated = match find_closing_bracket(expression_value) {
+            Some(end_index) => match parse_default_expression(&expression_value[2..end_index]) {
+                Some((key, empty_uses_default, default_value)) => Some((
+                    end_index,
+                    evaluate_default_expression(key, empty_uses_default, default_value),
+                )),
+                None => None,
+            },
+            None => None,
+        };
 
-    envmnt::expand(&value, Some(options))
+        match evaluated {
+            Some((end_index, evaluated_value)) => {
+                expanded_value.push_str(&expand_value_without_defaults(
+                    &remaining_value[..start_index],
+                ));
+                expanded_value.push_str(&evaluated_value);
+
+                remaining_value = &expression_value[end_index + 1..];
+                search_index = 0;
+            }
+            None => search_index = start_index + 2,
+        }
+    }
+
+    expanded_value.push_str(&expand_value_without_defaults(remaining_value));
+
+    add_unresolved_variables(&expanded_value);
+
+    expanded_value
 }
 
 fn evaluate_and_set_env(key: &str, value: &str) {
     let env_value = expand_value(&value);
 
     debug!("Setting Env: {} Value: {}", &key, &env_value);
-    envmnt::set(&key, &env_value);
+    set_env_value(&key, &env_value);
 }
 
 fn set_env_for_bool(key: &str, value: bool) {
     debug!("Setting Env: {} Value: {}", &key, &value);
-    envmnt::set_bool(&key, value);
+    set_env_value(&key, &value.to_string());
 }
 
 fn set_env_for_list(key: &str, list: &Vec<String>) {
@@ -106,7 +321,7 @@ fn set_env_for_list(key: &str, list: &Vec<String>) {
         expanded_list.push(env_value);
     }
 
-    envmnt::set_list(&key, &expanded_list);
+    set_env_value(&key, &expanded_list.join(";"));
 }
 
 fn set_env_for_script(key: &str, env_value: &EnvValueScript) {
@@ -115,27 +330,84 @@ fn set_env_for_script(key: &str, env_value: &EnvValueScript) {
     evaluate_and_set_env(&key, &value);
 }
 
-fn set_env_for_decode_info(key: &str, decode_info: &EnvValueDecode) {
+fn get_decoded_value(decode_info: &EnvValueDecode) -> String {
     let source_value = expand_value(&decode_info.source);
 
-    let mapped_value = match decode_info.mapping.get(&source_value) {
+    match decode_info.mapping.get(&source_value) {
         Some(value) => value.to_string(),
         None => match decode_info.default_value {
             Some(ref value) => value.clone().to_string(),
             None => source_value.clone(),
         },
-    };
+    }
+}
+
+fn set_env_for_decode_info(key: &str, decode_info: &EnvValueDecode) {
+    let mapped_value = get_decoded_value(decode_info);
 
     evaluate_and_set_env(&key, &mapped_value);
 }
 
-fn set_env_for_conditional_value(key: &str, conditional_value: &EnvValueConditioned) {
-    let valid = match conditional_value.condition {
+/// Returns the env value file path, relative paths are resolved from the file base path.
+fn get_env_value_file_path(file_info: &EnvValueFile) -> PathBuf {
+    let file_path = expand_value(&file_info.from_file);
+
+    match file_info.base_path {
+        Some(ref base_path) if Path::new(&file_path).is_relative() => {
+            Path::new(&expand_value(base_path)).join(&file_path)
+        }
+        _ => PathBuf::from(file_path),
+    }
+}
+
+/// Reads the env value from the file.<br>
+/// Returns None in case an optional file does not exist and an error in case the file can't be read.
+fn read_env_value_file(file_info: &EnvValueFile) -> Result<Option<String>, String> {
+    let file_path = get_env_value_file_path(file_info);
+
+    if !file_path.exists() && file_info.optional.unwrap_or(false) {
+        debug!(
+            "Optional env value file: {:#?} not found, skipping.",
+            &file_path
+        );
+        return Ok(None);
+    }
+
+    match std::fs::read_to_string(&file_path) {
+        Ok(value) => {
+            if file_info.trim.unwrap_or(false) {
+                Ok(Some(value.trim_end_matches(&['\n', '\r'][..]).to_string()))
+            } else {
+                Ok(Some(value))
+            }
+        }
+        Err(error) => Err(format!(
+            "Unable to read env value file: {:#?}, {}",
+            &file_path, error
+        )),
+    }
+}
+
+fn set_env_for_file(key: &str, file_info: &EnvValueFile) {
+    match read_env_value_file(file_info) {
+        Ok(Some(value)) => {
+            debug!("Setting Env: {} from file: {}", &key, &file_info.from_file);
+            set_env_value(&key, &value);
+        }
+        Ok(None) => (),
+        Err(error) => error!("Unable to set env: {}, {}", &key, error),
+    }
+}
+
+fn is_conditional_value_valid(conditional_value: &EnvValueConditioned) -> bool {
+    match conditional_value.condition {
         Some(ref condition) => condition::validate_conditions_without_context(condition.clone()),
         None => true,
-    };
+    }
+}
 
-    if valid {
+fn set_env_for_conditional_value(key: &str, conditional_value: &EnvValueConditioned) {
+    if is_conditional_value_valid(conditional_value) {
         let value = expand_value(&conditional_value.value);
 
         evaluate_and_set_env(&key, &value);
@@ -168,7 +440,7 @@ pub(crate) fn set_env(env: IndexMap<String, EnvValue>) {
 }
 
 fn unset_env(key: &str) {
-    envmnt::remove(key);
+    remove_env_value(key);
 }
 
 /// Updates the env based on the provided data
@@ -187,8 +459,13 @@ fn set_env_for_config(
             EnvValue::Boolean(value) => set_env_for_bool(&key, value),
             EnvValue::Number(value) => evaluate_and_set_env(&key, &value.to_string()),
             EnvValue::List(ref value) => set_env_for_list(&key, value),
+            EnvValue::Append(ref append_info) => set_env_for_list(&key, &append_info.append),
             EnvValue::Script(ref script_info) => set_env_for_script(&key, script_info),
             EnvValue::Decode(ref decode_info) => set_env_for_decode_info(&key, decode_info),
+            EnvValue::Documented(ref documented_value) => {
+                evaluate_and_set_env(&key, &documented_value.value)
+            }
+            EnvValue::File(ref file_info) => set_env_for_file(&key, file_info),
             EnvValue::Conditional(ref conditioned_value) => {
                 set_env_for_conditional_value(&key, conditioned_value)
             }
@@ -252,7 +529,13 @@ fn set_env_files_for_config(
                 };
 
                 if is_valid_profile {
-                    load_env_file_with_base_directory(Some(info.path), info.base_path)
+                    load_env_file_with_options(
+                        Some(info.path),
+                        info.base_path,
+                        info.strip_export.unwrap_or(false),
+                        info.optional.unwrap_or(false),
+                        info.include.unwrap_or(false),
+                    )
                 } else {
                     false
                 }
@@ -265,13 +548,14 @@ fn set_env_files_for_config(
     all_loaded
 }
 
-fn set_env_scripts(env_scripts: Vec<String>, cli_arguments: &Vec<String>) {
+fn invoke_env_scripts(env_scripts: Vec<EnvScript>, cli_arguments: &Vec<String>) {
     for env_script in env_scripts {
-        if !env_script.is_empty() {
+        let script = env_script.get_script();
+        if !script.is_empty() {
             scriptengine::invoke_script_pre_flow(
-                &ScriptValue::Text(vec![env_script]),
-                None,
-                None,
+                &ScriptValue::Text(vec![script]),
+                env_script.get_script_runner(),
+                env_script.get_script_runner_args(),
                 None,
                 true,
                 cli_arguments,
@@ -280,6 +564,38 @@ fn set_env_scripts(env_scripts: Vec<String>, cli_arguments: &Vec<String>) {
     }
 }
 
+fn set_env_scripts(env_scripts: Vec<EnvScript>, cli_arguments: &Vec<String>) {
+    let env_copy_active = ENV_COPY.with(|env_copy| env_copy.borrow().is_some());
+
+    if !env_copy_active {
+        invoke_env_scripts(env_scripts, cli_arguments);
+        return;
+    }
+
+    // in process scripts (such as duckscript) can only update the process env, so their
+    // changes are moved into the env copy
+    let previous_env: IndexMap<String, String> = env::vars().collect();
+
+    invoke_env_scripts(env_scripts, cli_arguments);
+
+    let current_env: IndexMap<String, String> = env::vars().collect();
+    for (key, value) in current_env.iter() {
+        if previous_env.get(key) != Some(value) {
+            set_env_value(key, value);
+            match previous_env.get(key) {
+                Some(previous_value) => envmnt::set(key, previous_value),
+                None => envmnt::remove(key),
+            };
+        }
+    }
+    for key in previous_env.keys() {
+        if !current_env.contains_key(key) {
+            remove_env_value(key);
+            envmnt::set(key, &previous_env[key]);
+        }
+    }
+}
+
 pub(crate) fn set_current_task_meta_info_env(env: IndexMap<String, EnvValue>) {
     debug!("Setting Up Env.");
 
@@ -295,6 +611,20 @@ pub(crate) fn set_current_task_meta_info_env(env: IndexMap<String, EnvValue>) {
     }
 }
 
+/// Sets the task makefile location env vars (the makefile which defined the task).
+pub(crate) fn set_current_task_location_env(task: &Task) {
+    if let Some(ref initial_makefile) = task.initial_makefile {
+        envmnt::set("CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE", initial_makefile);
+    }
+
+    if let Some(ref initial_makefile_dir) = task.initial_makefile_dir {
+        envmnt::set(
+            "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY",
+            initial_makefile_dir,
+        );
+    }
+}
+
 /// Updates the env for the current execution based on the descriptor.
 fn initialize_env(config: &Config, cli_args: &Vec<String>) {
     debug!("Initializing Env.");
@@ -304,11 +634,153 @@ fn initialize_env(config: &Config, cli_args: &Vec<String>) {
         None => None,
     };
 
+    let scripts_first = config.config.get_env_setup_order() == EnvSetupOrder::ScriptsFirst;
+    if scripts_first {
+        set_env_scripts(config.env_scripts.clone(), cli_args);
+    }
+
     set_env_files_for_config(config.env_files.clone(), additional_profiles);
 
     set_env_for_config(config.env.clone(), additional_profiles, true);
 
-    set_env_scripts(config.env_scripts.clone(), cli_args);
+    if !scripts_first {
+        set_env_scripts(config.env_scripts.clone(), cli_args);
+    }
+}
+
+/// Returns the env value as written into an env file, values with whitespace, quotes,
+/// backslashes or comment markers are wrapped with double quotes (and escaped).
+fn get_env_file_value(value: &str) -> String {
+    let requires_quotes = value.is_empty()
+        || value.chars().any(|character| {
+            character.is_whitespace() || character == '"' || character == '\'' || character == '\\'
+        })
+        || value.contains('#')
+        || value.contains("//");
+
+    if requires_quotes {
+        // backslashes are escaped first so the other escape sequences are not doubled
+        let escaped_value = value
+            .replace("\\", "\\\\")
+            .replace("\"", "\\\"")
+            .replace("\n", "\\n")
+            .replace("\r", "\\r");
+
+        format!("\"{}\"", escaped_value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Materializes the config env (env files, env scripts and env values based on the active
+/// profile) on a copy of the process env and returns the names of the ${} variables which the
+/// expansion of the produced values left unresolved (each name only once).<br>
+/// Values which are only expanded later (for example the task env) are not checked.
+pub(crate) fn get_unresolved_env_variables(config: &Config) -> Vec<String> {
+    UNRESOLVED_VARIABLES
+        .with(|unresolved_variables| *unresolved_variables.borrow_mut() = Some(vec![]));
+
+    with_env_copy(|| initialize_env(config, &vec![]));
+
+    UNRESOLVED_VARIABLES
+        .with(|unresolved_variables| unresolved_variables.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+/// Materializes the config env (env files, env scripts and env values based on the active
+/// profile) and writes all env vars it defined or modified, sorted by key, as KEY=VALUE lines
+/// into the provided env file.<br>
+/// The values are computed on a copy of the process env, so the process env is not modified.
+/// Env scripts, env value scripts and conditions are evaluated against the process env.
+pub fn dump_env(config: &Config, path: &Path) -> Result<(), String> {
+    let previous_env: IndexMap<String, String> = env::vars().collect();
+
+    let current_env = with_env_copy(|| initialize_env(config, &vec![]));
+
+    let mut keys = vec![];
+    for (key, value) in current_env.iter() {
+        let modified = match previous_env.get(key) {
+            Some(previous_value) => previous_value != value,
+            None => true,
+        };
+
+        if modified || config.env.contains_key(key) {
+            keys.push(key.to_string());
+        }
+    }
+    keys.sort();
+
+    let mut content = String::new();
+    for key in keys {
+        if let Some(value) = current_env.get(&key) {
+            content.push_str(&format!("{}={}\n", &key, get_env_file_value(value)));
+        }
+    }
+
+    match std::fs::write(path, content) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!(
+            "Unable to write env file: {:#?} Error: {}",
+            path, error
+        )),
+    }
+}
+
+/// Returns the string value the env value is set to, or None in case the env value does not set
+/// a value (for example unset values, profiles and conditional values with invalid conditions).
+fn get_effective_env_value(key: &str, env_value: &EnvValue) -> Option<String> {
+    match *env_value {
+        EnvValue::Value(ref value) => Some(expand_value(value)),
+        EnvValue::Boolean(value) => Some(value.to_string()),
+        EnvValue::Number(value) => Some(expand_value(&value.to_string())),
+        EnvValue::List(ref value) => Some(
+            value
+                .iter()
+                .map(|item| expand_value(item))
+                .collect::<Vec<String>>()
+                .join(";"),
+        ),
+        EnvValue::Append(ref append_info) => {
+            get_effective_env_value(key, &EnvValue::List(append_info.append.clone()))
+        }
+        EnvValue::Script(ref script_info) => {
+            Some(expand_value(&evaluate_env_value(&key, &script_info)))
+        }
+        EnvValue::Decode(ref decode_info) => Some(expand_value(&get_decoded_value(decode_info))),
+        EnvValue::Documented(ref documented_value) => Some(expand_value(&documented_value.value)),
+        EnvValue::File(ref file_info) => match read_env_value_file(file_info) {
+            Ok(value) => value,
+            Err(error) => {
+                warn!("Unable to read env: {}, {}", &key, error);
+                None
+            }
+        },
+        EnvValue::Conditional(ref conditioned_value) => {
+            if is_conditional_value_valid(conditioned_value) {
+                Some(expand_value(&conditioned_value.value))
+            } else {
+                None
+            }
+        }
+        EnvValue::Profile(_) | EnvValue::Unset(_) => None,
+    }
+}
+
+/// Returns the value the merged config env sets for the provided env key.<br>
+/// In case a profile is provided, the profile env value takes precedence over the global value.
+/// Variables (including their default values) are expanded based on the current process env.<br>
+/// Returns None in case the key is not defined or its value is not set (for example in case
+/// the value condition is not met).
+pub fn effective_env(config: &Config, key: &str, profile: Option<&str>) -> Option<String> {
+    let profile_value = match profile.and_then(|profile_name| config.env.get(profile_name)) {
+        Some(EnvValue::Profile(ref sub_env)) => sub_env.get(key),
+        _ => None,
+    };
+
+    match profile_value.or_else(|| config.env.get(key)) {
+        Some(env_value) => get_effective_env_value(key, env_value),
+        None => None,
+    }
 }
 
 fn setup_env_for_duckscript() {
@@ -652,30 +1124,323 @@ pub(crate) fn load_env_file_with_base_directory(
     env_file: Option<String>,
     base_directory: Option<String>,
 ) -> bool {
-    match env_file {
-        Some(file_name) => {
-            let file_path = if file_name.starts_with(".") {
-                let (base_path, check_relative_path) = match base_directory {
-                    Some(file) => (file, true),
-                    None => (envmnt::get_or("CARGO_MAKE_WORKING_DIRECTORY", "."), false),
+    load_env_file_with_options(env_file, base_directory, false, false, false)
+}
+
+fn is_env_file_comment(text: &str) -> bool {
+    text.starts_with("#") || text.starts_with("//")
+}
+
+/// Removes the trailing comment (starting with # or // after a whitespace) from the env value.<br>
+/// Comment markers inside quoted text are kept.
+fn strip_env_value_comment(value: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut previous_whitespace = false;
+
+    for (index, character) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if character == '\\' {
+            escaped = true;
+        } else if let Some(quote_character) = quote {
+            if character == quote_character {
+                quote = None;
+            }
+        } else if character == '"' || character == '\'' {
+            quote = Some(character);
+        } else if previous_whitespace && is_env_file_comment(&value[index..]) {
+            return value[..index].trim_end();
+        }
+
+        previous_whitespace = character.is_whitespace();
+    }
+
+    value
+}
+
+/// Replaces the \\", \\n and \\r escape sequences (and \\\\ in double quoted values) in a
+/// single pass, any other backslash is kept as is.
+fn unescape_env_file_value(value: &str, quoted: bool) -> String {
+    let mut unescaped_value = String::new();
+    let mut characters = value.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped_value.push(character);
+            continue;
+        }
+
+        let unescaped_character = match characters.peek() {
+            Some('"') => Some('"'),
+            Some('n') => Some('\n'),
+            Some('r') => Some('\r'),
+            Some('\\') if quoted => Some('\\'),
+            _ => None,
+        };
+
+        match unescaped_character {
+            Some(unescaped_character) => {
+                unescaped_value.push(unescaped_character);
+                characters.next();
+            }
+            None => unescaped_value.push(character),
+        }
+    }
+
+    unescaped_value
+}
+
+/// Parses the env file content into key/value pairs.<br>
+/// Lines starting with # or // and unquoted trailing comments are ignored and values wrapped
+/// with double quotes are unquoted.
+fn parse_env_file_content(content: &str) -> IndexMap<String, String> {
+    let mut env = IndexMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || is_env_file_comment(line) {
+            continue;
+        }
+
+        let env_part: Vec<&str> = line.splitn(2, '=').collect();
+
+        if env_part.len() == 2 {
+            let key = env_part[0].trim().to_string();
+            let value = strip_env_value_comment(env_part[1].trim());
+
+            let value = if value.len() > 1 && value.starts_with('"') && value.ends_with('"') {
+                unescape_env_file_value(&value[1..value.len() - 1], true)
+            } else {
+                unescape_env_file_value(value, false)
+            };
+
+            env.insert(key, value);
+        }
+    }
+
+    env
+}
+
+fn parse_env_file(file_path: &str) -> Result<IndexMap<String, String>, std::io::Error> {
+    let content = std::fs::read_to_string(file_path)?;
+
+    Ok(parse_env_file_content(&content))
+}
+
+/// Returns the included env file path in case the env file line is an include line
+/// (for example: include ./other.env)
+fn get_env_file_include(line: &str) -> Option<String> {
+    let line = line.trim();
+
+    if !line.starts_with("include") {
+        return None;
+    }
+
+    let path = &line["include".len()..];
+    if !path.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let path = path.trim().trim_matches('"');
+    if path.is_empty() || path.starts_with('=') {
+        None
+    } else {
+        Some(path.to_string())
+    }
+}
+
+/// Parses the env file same as parse_env_file, however include lines load the referenced env
+/// files (relative to the including env file directory).<br>
+/// The including env file values override the included values and include cycles are errors.
+fn parse_env_file_with_includes(
+    file_path: &Path,
+    include_chain: &mut Vec<PathBuf>,
+) -> Result<IndexMap<String, String>, std::io::Error> {
+    let canonical_path = file_path.canonicalize()?;
+    if include_chain.contains(&canonical_path) {
+        let mut cycle: Vec<String> = include_chain
+            .iter()
+            .map(|path| FromPath::from_path(path))
+            .collect();
+        cycle.push(FromPath::from_path(&canonical_path));
+
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Env file include cycle detected: {}", cycle.join(" -> ")),
+        ));
+    }
+    include_chain.push(canonical_path);
+
+    let content = std::fs::read_to_string(file_path)?;
+    let mut env = IndexMap::new();
+    let mut file_content = String::new();
+    for line in content.lines() {
+        match get_env_file_include(line) {
+            Some(include_path) => {
+                let include_file_path = match file_path.parent() {
+                    Some(directory) => directory.join(&include_path),
+                    None => PathBuf::from(&include_path),
                 };
 
-                if check_relative_path && base_path.starts_with(".") {
-                    Path::new(&envmnt::get_or("CARGO_MAKE_WORKING_DIRECTORY", "."))
-                        .join(&base_path)
-                        .join(file_name)
-                } else {
-                    Path::new(&base_path).join(file_name)
+                env.extend(parse_env_file_with_includes(
+                    &include_file_path,
+                    include_chain,
+                )?);
+            }
+            None => {
+                file_content.push_str(line);
+                file_content.push('\n');
+            }
+        }
+    }
+    env.extend(parse_env_file_content(&file_content));
+
+    include_chain.pop();
+
+    Ok(env)
+}
+
+/// Removes the shell export keyword from the env keys (for example: export KEY=VALUE)
+fn strip_export_prefix(env: IndexMap<String, String>) -> IndexMap<String, String> {
+    let mut stripped_env = IndexMap::new();
+
+    for (key, value) in env {
+        let stripped_key = if key.starts_with("export ") || key.starts_with("export\t") {
+            key[6..].trim().to_string()
+        } else {
+            key
+        };
+
+        stripped_env.insert(stripped_key, value);
+    }
+
+    stripped_env
+}
+
+/// Returns the names of the ${} variables which are left in the provided (expanded) value
+pub(crate) fn get_unresolved_variables(value: &str) -> Vec<String> {
+    let mut variables = vec![];
+
+    let mut remaining = value;
+    while let Some(start) = remaining.find("${") {
+        remaining = &remaining[start + 2..];
+
+        match remaining.find('}') {
+            Some(end) => {
+                variables.push(remaining[..end].to_string());
+                remaining = &remaining[end + 1..];
+            }
+            None => break,
+        }
+    }
+
+    variables
+}
+
+/// Expands the env variables in the env file base path.<br>
+/// In case some variables are not defined, an error is printed and None is returned.
+fn expand_env_file_base_path(base_path: &str, file_name: &str) -> Option<String> {
+    if !base_path.contains("${") {
+        return Some(base_path.to_string());
+    }
+
+    let expanded_base_path = expand_value(base_path);
+    let unresolved_variables = get_unresolved_variables(&expanded_base_path);
+
+    if unresolved_variables.is_empty() {
+        debug!(
+            "Expanded env file: {} base path: {} to: {}",
+            file_name, base_path, &expanded_base_path
+        );
+
+        Some(expanded_base_path)
+    } else {
+        error!(
+            "Unable to load env file: {} base path: {} undefined variables: {}",
+            file_name,
+            base_path,
+            unresolved_variables.join(", ")
+        );
+
+        None
+    }
+}
+
+fn get_working_directory() -> String {
+    get_env_value("CARGO_MAKE_WORKING_DIRECTORY").unwrap_or(".".to_string())
+}
+
+/// Returns the env file path.<br>
+/// Relative paths are resolved from the provided (already expanded) base directory or from the
+/// current working directory in case no base directory is provided.
+pub(crate) fn get_env_file_path(file_name: &str, base_directory: Option<String>) -> PathBuf {
+    if file_name.starts_with(".") {
+        let (base_path, check_relative_path) = match base_directory {
+            Some(directory) => (directory, true),
+            None => (get_working_directory(), false),
+        };
+
+        if check_relative_path && base_path.starts_with(".") {
+            Path::new(&get_working_directory())
+                .join(&base_path)
+                .join(file_name)
+        } else {
+            Path::new(&base_path).join(file_name)
+        }
+    } else {
+        Path::new(&file_name).to_path_buf()
+    }
+}
+
+fn load_env_file_with_options(
+    env_file: Option<String>,
+    base_directory: Option<String>,
+    strip_export: bool,
+    optional: bool,
+    include: bool,
+) -> bool {
+    match env_file {
+        Some(file_name) => {
+            let base_directory = match base_directory {
+                Some(ref directory) if file_name.starts_with(".") => {
+                    match expand_env_file_base_path(directory, &file_name) {
+                        Some(expanded_base_path) => Some(expanded_base_path),
+                        None => return false,
+                    }
                 }
-            } else {
-                Path::new(&file_name).to_path_buf()
+                _ => base_directory,
             };
+            let file_path = get_env_file_path(&file_name, base_directory);
+
+            if optional && !file_path.exists() {
+                debug!("Optional env file: {:#?} not found, skipping.", &file_path);
+                return true;
+            }
 
             match file_path.to_str() {
                 Some(file_path_str) => {
                     let evaluate_env_var = |value: String| expand_value(&value);
 
-                    match envmnt::evaluate_and_load_file(file_path_str, evaluate_env_var) {
+                    let parsed_env = if include {
+                        parse_env_file_with_includes(&file_path, &mut vec![])
+                    } else {
+                        parse_env_file(file_path_str)
+                    };
+                    let result = parsed_env.map(|env| {
+                        let env = if strip_export {
+                            strip_export_prefix(env)
+                        } else {
+                            env
+                        };
+
+                        for (key, value) in env.iter() {
+                            set_env_value(key, &evaluate_env_var(value.to_string()));
+                        }
+                    });
+
+                    match result {
                         Err(error) => {
                             error!(
                                 "Unable to load env file: {} Error: {:#?}",
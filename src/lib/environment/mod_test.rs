@@ -1,6 +1,10 @@
 use super::*;
 
-use crate::types::{ConfigSection, EnvFileInfo, EnvValueUnset, Task, TaskCondition};
+use crate::test;
+use crate::types::{
+    ConfigSection, EnvFileInfo, EnvScriptInfo, EnvValueAppend, EnvValueDocumented, EnvValueFile,
+    EnvValueUnset, Task, TaskCondition,
+};
 use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::env;
@@ -91,6 +95,134 @@ fn evaluate_and_set_env_complex() {
     );
 }
 
+#[test]
+fn find_closing_bracket_simple() {
+    assert_eq!(find_closing_bracket("${KEY} text"), Some(5));
+}
+
+#[test]
+fn find_closing_bracket_nested() {
+    assert_eq!(find_closing_bracket("${A:-${B:-x}} text"), Some(12));
+}
+
+#[test]
+fn find_closing_bracket_not_closed() {
+    assert!(find_closing_bracket("${A:-${B:-x}").is_none());
+}
+
+#[test]
+fn parse_default_expression_unset_default() {
+    let (key, empty_uses_default, default_value) = parse_default_expression("KEY:-8080").unwrap();
+
+    assert_eq!(key, "KEY");
+    assert!(!empty_uses_default);
+    assert_eq!(default_value, "8080");
+}
+
+#[test]
+fn parse_default_expression_empty_default() {
+    let (key, empty_uses_default, default_value) = parse_default_expression("KEY:=8080").unwrap();
+
+    assert_eq!(key, "KEY");
+    assert!(empty_uses_default);
+    assert_eq!(default_value, "8080");
+}
+
+#[test]
+fn parse_default_expression_no_default() {
+    assert!(parse_default_expression("KEY").is_none());
+    assert!(parse_default_expression("KEY:8080").is_none());
+    assert!(parse_default_expression(":-8080").is_none());
+    assert!(parse_default_expression("BAD KEY:-8080").is_none());
+}
+
+#[test]
+#[ignore]
+fn expand_value_default_unset() {
+    envmnt::remove("EXPAND_DEFAULT_UNSET");
+
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_UNSET:-8080}"),
+        "port: 8080"
+    );
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_UNSET:=8080}"),
+        "port: 8080"
+    );
+}
+
+#[test]
+#[ignore]
+fn expand_value_default_set_empty() {
+    envmnt::set("EXPAND_DEFAULT_EMPTY", "");
+
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_EMPTY:-8080}"),
+        "port: "
+    );
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_EMPTY:=8080}"),
+        "port: 8080"
+    );
+}
+
+#[test]
+#[ignore]
+fn expand_value_default_set_value() {
+    envmnt::set("EXPAND_DEFAULT_VALUE", "9090");
+
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_VALUE:-8080}"),
+        "port: 9090"
+    );
+    assert_eq!(
+        expand_value("port: ${EXPAND_DEFAULT_VALUE:=8080}"),
+        "port: 9090"
+    );
+}
+
+#[test]
+#[ignore]
+fn expand_value_default_nested() {
+    envmnt::remove("EXPAND_DEFAULT_NESTED1");
+    envmnt::remove("EXPAND_DEFAULT_NESTED2");
+    envmnt::set("EXPAND_DEFAULT_NESTED3", "nested");
+
+    assert_eq!(
+        expand_value(
+            "${EXPAND_DEFAULT_NESTED1:-${EXPAND_DEFAULT_NESTED2:-${EXPAND_DEFAULT_NESTED3}}}"
+        ),
+        "nested"
+    );
+    assert_eq!(
+        expand_value("${EXPAND_DEFAULT_NESTED1:-${EXPAND_DEFAULT_NESTED2:-default}}"),
+        "default"
+    );
+}
+
+#[test]
+#[ignore]
+fn expand_value_default_mixed_with_plain_values() {
+    envmnt::remove("EXPAND_DEFAULT_MIXED_UNSET");
+    envmnt::set("EXPAND_DEFAULT_MIXED_SET", "host");
+
+    assert_eq!(
+        expand_value(
+            "${EXPAND_DEFAULT_MIXED_SET}:${EXPAND_DEFAULT_MIXED_UNSET:-8080} ${EXPAND_DEFAULT_MIXED_BAD}"
+        ),
+        "host:8080 ${EXPAND_DEFAULT_MIXED_BAD}"
+    );
+}
+
+#[test]
+#[ignore]
+fn evaluate_and_set_env_default() {
+    envmnt::remove("EVAL_SET_DEFAULT_PORT");
+    evaluate_and_set_env("EVAL_SET_DEFAULT", "${EVAL_SET_DEFAULT_PORT:-8080}");
+
+    assert_eq!(envmnt::get_or_panic("EVAL_SET_DEFAULT"), "8080");
+}
+
 #[test]
 #[ignore]
 fn set_env_for_bool_false() {
@@ -109,6 +241,23 @@ fn set_env_for_bool_true() {
     assert!(output);
 }
 
+#[test]
+#[ignore]
+fn set_env_number_and_bool_string_form() {
+    let env: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        ENV_NUMBER_STRING_FORM = 8080
+        ENV_BOOL_STRING_FORM = true
+        "#,
+    )
+    .unwrap();
+
+    set_env(env);
+
+    assert!(envmnt::is_equal("ENV_NUMBER_STRING_FORM", "8080"));
+    assert!(envmnt::is_equal("ENV_BOOL_STRING_FORM", "true"));
+}
+
 #[test]
 fn set_env_for_list_empty() {
     envmnt::remove("SET_ENV_FOR_LIST_EMPTY");
@@ -462,6 +611,68 @@ fn set_env_for_config_list() {
     );
 }
 
+#[test]
+fn set_env_for_config_append() {
+    envmnt::remove("SET_ENV_FOR_CONFIG_APPEND_MATCH_TEST");
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "SET_ENV_FOR_CONFIG_APPEND_MATCH_TEST".to_string(),
+        EnvValue::Append(EnvValueAppend {
+            append: vec!["ARG1".to_string(), "ARG2".to_string()],
+        }),
+    );
+
+    set_env_for_config(env, None, true);
+
+    assert_eq!(
+        envmnt::get_or_panic("SET_ENV_FOR_CONFIG_APPEND_MATCH_TEST"),
+        "ARG1;ARG2"
+    );
+}
+
+#[test]
+fn set_env_for_config_documented() {
+    envmnt::remove("SET_ENV_FOR_CONFIG_DOCUMENTED_TEST");
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "SET_ENV_FOR_CONFIG_DOCUMENTED_TEST".to_string(),
+        EnvValue::Documented(EnvValueDocumented {
+            value: "documented".to_string(),
+            description: "test description".to_string(),
+        }),
+    );
+
+    set_env_for_config(env, None, true);
+
+    assert_eq!(
+        envmnt::get_or_panic("SET_ENV_FOR_CONFIG_DOCUMENTED_TEST"),
+        "documented"
+    );
+}
+
+#[test]
+#[ignore]
+fn set_env_scripts_script_runner() {
+    envmnt::remove("SET_ENV_SCRIPTS_SCRIPT_RUNNER_TEST");
+
+    set_env_scripts(
+        vec![EnvScript::Info(EnvScriptInfo {
+            script: "set_env SET_ENV_SCRIPTS_SCRIPT_RUNNER_TEST duckscript".to_string(),
+            order: None,
+            script_runner: Some("@duckscript".to_string()),
+            script_runner_args: None,
+        })],
+        &vec![],
+    );
+
+    assert!(envmnt::is_equal(
+        "SET_ENV_SCRIPTS_SCRIPT_RUNNER_TEST",
+        "duckscript"
+    ));
+}
+
 #[test]
 #[ignore]
 fn set_env_for_config_unset() {
@@ -481,6 +692,73 @@ fn set_env_for_config_unset() {
     assert!(!envmnt::exists("set_env_for_config_unset"));
 }
 
+fn create_env_set_condition(key: &str) -> TaskCondition {
+    TaskCondition {
+        fail_message: None,
+        profiles: None,
+        platforms: None,
+        channels: None,
+        env_set: Some(vec![key.to_string()]),
+        env_not_set: None,
+        env_true: None,
+        env_false: None,
+        env: None,
+        env_contains: None,
+        rust_version: None,
+        files_exist: None,
+        files_not_exist: None,
+    }
+}
+
+#[test]
+#[ignore]
+fn set_env_for_config_conditional_env_set_met() {
+    envmnt::set("SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_MET_FLAG", "1");
+    envmnt::remove("SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_MET");
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_MET".to_string(),
+        EnvValue::Conditional(EnvValueConditioned {
+            value: "musl".to_string(),
+            condition: Some(create_env_set_condition(
+                "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_MET_FLAG",
+            )),
+        }),
+    );
+
+    set_env_for_config(env, None, true);
+
+    assert!(envmnt::is_equal(
+        "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_MET",
+        "musl"
+    ));
+}
+
+#[test]
+#[ignore]
+fn set_env_for_config_conditional_env_set_not_met() {
+    envmnt::remove("SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_NOT_MET_FLAG");
+    envmnt::remove("SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_NOT_MET");
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_NOT_MET".to_string(),
+        EnvValue::Conditional(EnvValueConditioned {
+            value: "musl".to_string(),
+            condition: Some(create_env_set_condition(
+                "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_NOT_MET_FLAG",
+            )),
+        }),
+    );
+
+    set_env_for_config(env, None, true);
+
+    assert!(!envmnt::exists(
+        "SET_ENV_FOR_CONFIG_CONDITIONAL_ENV_SET_NOT_MET"
+    ));
+}
+
 #[test]
 #[ignore]
 fn set_env_for_config_conditional() {
@@ -578,6 +856,323 @@ fn set_env_files_for_config_files() {
     }
 }
 
+#[test]
+fn strip_export_prefix_env_file() {
+    let env = envmnt::parse_file("./src/lib/test/test_files/export.env").unwrap();
+
+    let output = strip_export_prefix(env);
+
+    assert_eq!(output.len(), 3);
+    assert_eq!(output.get("CARGO_MAKE_ENV_FILE_EXPORT_TEST1").unwrap(), "1");
+    assert_eq!(output.get("CARGO_MAKE_ENV_FILE_EXPORT_TEST2").unwrap(), "2");
+    assert_eq!(
+        output.get("CARGO_MAKE_ENV_FILE_EXPORT_TEST3").unwrap(),
+        "quoted value"
+    );
+    assert!(!output.contains_key("CARGO_MAKE_ENV_FILE_EXPORT_COMMENTED"));
+}
+
+#[test]
+fn parse_env_file_content_hash_comment() {
+    let env = parse_env_file_content("# KEY=1\nKEY2=2");
+
+    assert_eq!(env.len(), 1);
+    assert_eq!(env.get("KEY2").unwrap(), "2");
+}
+
+#[test]
+fn parse_env_file_content_slash_comment() {
+    let env = parse_env_file_content("// KEY=1\n  // comment\nKEY2=2");
+
+    assert_eq!(env.len(), 1);
+    assert_eq!(env.get("KEY2").unwrap(), "2");
+}
+
+#[test]
+fn parse_env_file_content_trailing_comment() {
+    let env =
+        parse_env_file_content("KEY1=value1 # note\nKEY2=value2\t// note\nKEY3=\"value3\" # note");
+
+    assert_eq!(env.get("KEY1").unwrap(), "value1");
+    assert_eq!(env.get("KEY2").unwrap(), "value2");
+    assert_eq!(env.get("KEY3").unwrap(), "value3");
+}
+
+#[test]
+fn parse_env_file_content_comment_in_quoted_value() {
+    let env = parse_env_file_content(
+        "KEY1=\"value # 1\"\nKEY2=\"value // 2\" # note\nKEY3='value # 3'\nKEY4=\"a \\\" # b\"",
+    );
+
+    assert_eq!(env.get("KEY1").unwrap(), "value # 1");
+    assert_eq!(env.get("KEY2").unwrap(), "value // 2");
+    assert_eq!(env.get("KEY3").unwrap(), "'value # 3'");
+    assert_eq!(env.get("KEY4").unwrap(), "a \" # b");
+}
+
+#[test]
+fn parse_env_file_content_comment_marker_without_whitespace() {
+    let env = parse_env_file_content("KEY1=https://example.com/#anchor\nKEY2=#fff");
+
+    assert_eq!(env.get("KEY1").unwrap(), "https://example.com/#anchor");
+    assert_eq!(env.get("KEY2").unwrap(), "#fff");
+}
+
+#[test]
+fn parse_env_file_comments() {
+    let env = parse_env_file("./src/lib/test/test_files/comments.env").unwrap();
+
+    assert_eq!(env.len(), 5);
+    assert_eq!(env.get("CARGO_MAKE_ENV_FILE_COMMENTS_TEST1").unwrap(), "1");
+    assert_eq!(env.get("CARGO_MAKE_ENV_FILE_COMMENTS_TEST2").unwrap(), "2");
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_COMMENTS_TEST3").unwrap(),
+        "quoted # value"
+    );
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_COMMENTS_TEST4").unwrap(),
+        "'quoted // value'"
+    );
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_COMMENTS_TEST5").unwrap(),
+        "https://example.com/#anchor"
+    );
+}
+
+#[test]
+fn parse_env_file_not_found() {
+    let output = parse_env_file("./src/lib/test/test_files/missing.env");
+
+    assert!(output.is_err());
+}
+
+#[test]
+fn strip_export_prefix_no_export() {
+    let mut env = IndexMap::new();
+    env.insert("FOO".to_string(), "bar".to_string());
+    env.insert("exported".to_string(), "value".to_string());
+
+    let output = strip_export_prefix(env);
+
+    assert_eq!(output.get("FOO").unwrap(), "bar");
+    assert_eq!(output.get("exported").unwrap(), "value");
+}
+
+#[test]
+fn get_env_file_include_valid() {
+    assert_eq!(
+        get_env_file_include("include ./other.env").unwrap(),
+        "./other.env"
+    );
+    assert_eq!(
+        get_env_file_include("  include\t\"./other file.env\"  ").unwrap(),
+        "./other file.env"
+    );
+}
+
+#[test]
+fn get_env_file_include_invalid() {
+    assert!(get_env_file_include("KEY=include ./other.env").is_none());
+    assert!(get_env_file_include("include=./other.env").is_none());
+    assert!(get_env_file_include("include = ./other.env").is_none());
+    assert!(get_env_file_include("included ./other.env").is_none());
+    assert!(get_env_file_include("include").is_none());
+}
+
+#[test]
+fn parse_env_file_with_includes_simple() {
+    let env = parse_env_file_with_includes(
+        Path::new("./src/lib/test/test_files/include/shared.env"),
+        &mut vec![],
+    )
+    .unwrap();
+
+    assert_eq!(env.len(), 3);
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_INCLUDE_NESTED").unwrap(),
+        "nested"
+    );
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_INCLUDE_SHARED").unwrap(),
+        "shared"
+    );
+}
+
+#[test]
+fn parse_env_file_with_includes_precedence() {
+    let env = parse_env_file_with_includes(
+        Path::new("./src/lib/test/test_files/include/main.env"),
+        &mut vec![],
+    )
+    .unwrap();
+
+    assert_eq!(env.len(), 4);
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_INCLUDE_NESTED").unwrap(),
+        "nested"
+    );
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_INCLUDE_SHARED").unwrap(),
+        "shared"
+    );
+    assert_eq!(env.get("CARGO_MAKE_ENV_FILE_INCLUDE_MAIN").unwrap(), "main");
+    assert_eq!(
+        env.get("CARGO_MAKE_ENV_FILE_INCLUDE_OVERRIDE").unwrap(),
+        "main"
+    );
+}
+
+#[test]
+fn parse_env_file_with_includes_cycle() {
+    let error = parse_env_file_with_includes(
+        Path::new("./src/lib/test/test_files/include/cycle_a.env"),
+        &mut vec![],
+    )
+    .unwrap_err();
+
+    let message = error.to_string();
+    assert!(message.contains("include cycle"));
+    assert!(message.contains("cycle_a.env"));
+    assert!(message.contains("cycle_b.env"));
+}
+
+#[test]
+fn parse_env_file_with_includes_missing() {
+    let env = parse_env_file_with_includes(
+        Path::new("./src/lib/test/test_files/include/missing.env"),
+        &mut vec![],
+    );
+
+    assert!(env.is_err());
+}
+
+#[test]
+#[ignore]
+fn set_env_files_for_config_include() {
+    envmnt::remove("CARGO_MAKE_ENV_FILE_INCLUDE_NESTED");
+    envmnt::remove("CARGO_MAKE_ENV_FILE_INCLUDE_OVERRIDE");
+
+    let loaded = set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./src/lib/test/test_files/include/main.env".to_string(),
+            base_path: None,
+            profile: None,
+            strip_export: None,
+            optional: None,
+            include: Some(true),
+        })],
+        None,
+    );
+
+    assert!(loaded);
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_ENV_FILE_INCLUDE_NESTED",
+        "nested"
+    ));
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_ENV_FILE_INCLUDE_OVERRIDE",
+        "main"
+    ));
+
+    for key in vec!["NESTED", "SHARED", "MAIN", "OVERRIDE"] {
+        envmnt::remove(&format!("CARGO_MAKE_ENV_FILE_INCLUDE_{}", key));
+    }
+}
+
+#[test]
+#[ignore]
+fn set_env_files_for_config_include_disabled() {
+    envmnt::remove("CARGO_MAKE_ENV_FILE_INCLUDE_NESTED");
+
+    let loaded = set_env_files_for_config(
+        vec![EnvFile::Path(
+            "./src/lib/test/test_files/include/main.env".to_string(),
+        )],
+        None,
+    );
+
+    assert!(loaded);
+    assert!(!envmnt::exists("CARGO_MAKE_ENV_FILE_INCLUDE_NESTED"));
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_ENV_FILE_INCLUDE_OVERRIDE",
+        "main"
+    ));
+
+    for key in vec!["MAIN", "OVERRIDE"] {
+        envmnt::remove(&format!("CARGO_MAKE_ENV_FILE_INCLUDE_{}", key));
+    }
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn set_env_files_for_config_include_cycle() {
+    test::on_test_startup();
+
+    set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./src/lib/test/test_files/include/cycle_a.env".to_string(),
+            base_path: None,
+            profile: None,
+            strip_export: None,
+            optional: None,
+            include: Some(true),
+        })],
+        None,
+    );
+}
+
+#[test]
+#[ignore]
+fn set_env_files_for_config_strip_export() {
+    let env =
+        strip_export_prefix(envmnt::parse_file("./src/lib/test/test_files/export.env").unwrap());
+    for (key, _) in env.clone().iter() {
+        envmnt::remove(&key);
+    }
+
+    let loaded = set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./src/lib/test/test_files/export.env".to_string(),
+            base_path: None,
+            profile: None,
+            strip_export: Some(true),
+            optional: None,
+            include: None,
+        })],
+        None,
+    );
+
+    assert!(loaded);
+    assert!(envmnt::is_equal("CARGO_MAKE_ENV_FILE_EXPORT_TEST1", "1"));
+    assert!(envmnt::is_equal("CARGO_MAKE_ENV_FILE_EXPORT_TEST2", "2"));
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_ENV_FILE_EXPORT_TEST3",
+        "quoted value"
+    ));
+
+    for (key, _) in env.iter() {
+        envmnt::remove(&key);
+    }
+}
+
+#[test]
+fn set_env_files_for_config_missing_optional() {
+    let loaded = set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./src/lib/test/test_files/missing_optional.env".to_string(),
+            base_path: None,
+            profile: None,
+            strip_export: None,
+            optional: Some(true),
+            include: None,
+        })],
+        None,
+    );
+
+    assert!(loaded);
+}
+
 #[test]
 #[ignore]
 fn set_env_files_for_config_base_directory() {
@@ -596,6 +1191,9 @@ fn set_env_files_for_config_base_directory() {
                 path: "./test/test_files/env.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: None,
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
             EnvFile::Path("./src/lib/test/test_files/profile.env".to_string()),
         ],
@@ -606,9 +1204,81 @@ fn set_env_files_for_config_base_directory() {
     assert!(envmnt::exists("CARGO_MAKE_ENV_FILE_TEST1"));
     assert!(envmnt::exists("CARGO_MAKE_ENV_FILE_PROFILE_TEST1"));
 
-    for (key, _) in env.iter() {
-        envmnt::remove(&key);
-    }
+    for (key, _) in env.iter() {
+        envmnt::remove(&key);
+    }
+}
+
+#[test]
+#[ignore]
+fn set_env_files_for_config_base_directory_with_variable() {
+    let env = envmnt::parse_file("./src/lib/test/test_files/env.env").unwrap();
+    for (key, _) in env.clone().iter() {
+        envmnt::remove(&key);
+    }
+    envmnt::set("CARGO_MAKE_TEST_ENV_FILE_ROOT", "./src/lib");
+
+    let loaded = set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./test/test_files/env.env".to_string(),
+            base_path: Some("${CARGO_MAKE_TEST_ENV_FILE_ROOT}".to_string()),
+            profile: None,
+            strip_export: None,
+            optional: None,
+            include: None,
+        })],
+        None,
+    );
+
+    assert!(loaded);
+    assert!(envmnt::exists("CARGO_MAKE_ENV_FILE_TEST1"));
+
+    envmnt::remove("CARGO_MAKE_TEST_ENV_FILE_ROOT");
+    for (key, _) in env.iter() {
+        envmnt::remove(&key);
+    }
+}
+
+#[test]
+#[ignore]
+#[should_panic]
+fn set_env_files_for_config_base_directory_with_undefined_variable() {
+    test::on_test_startup();
+
+    envmnt::remove("CARGO_MAKE_TEST_ENV_FILE_UNDEFINED_ROOT");
+
+    set_env_files_for_config(
+        vec![EnvFile::Info(EnvFileInfo {
+            path: "./test/test_files/env.env".to_string(),
+            base_path: Some("${CARGO_MAKE_TEST_ENV_FILE_UNDEFINED_ROOT}".to_string()),
+            profile: None,
+            strip_export: None,
+            optional: None,
+            include: None,
+        })],
+        None,
+    );
+}
+
+#[test]
+fn get_unresolved_variables_none() {
+    let variables = get_unresolved_variables("./src/lib");
+
+    assert!(variables.is_empty());
+}
+
+#[test]
+fn get_unresolved_variables_found() {
+    let variables = get_unresolved_variables("${ROOT}/src/${SUB}/${BAD");
+
+    assert_eq!(variables, vec!["ROOT".to_string(), "SUB".to_string()]);
+}
+
+#[test]
+fn expand_env_file_base_path_no_variables() {
+    let base_path = expand_env_file_base_path("./src/lib", "./test.env");
+
+    assert_eq!(base_path.unwrap(), "./src/lib");
 }
 
 #[test]
@@ -631,11 +1301,17 @@ fn set_env_files_for_config_profile() {
                 path: "./test/test_files/profile.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test1".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
             EnvFile::Info(EnvFileInfo {
                 path: "./test/test_files/env.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test2".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
         ],
         None,
@@ -670,11 +1346,17 @@ fn set_env_files_for_config_profile_inverse() {
                 path: "./test/test_files/env.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test2".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
             EnvFile::Info(EnvFileInfo {
                 path: "./test/test_files/profile.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test1".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
         ],
         None,
@@ -709,11 +1391,17 @@ fn set_env_files_for_config_additional_profiles() {
                 path: "./test/test_files/profile.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test1".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
             EnvFile::Info(EnvFileInfo {
                 path: "./test/test_files/env.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test2".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
         ],
         Some(&vec!["env_test2".to_string()]),
@@ -760,11 +1448,17 @@ fn initialize_env_all() {
                 path: "./test/test_files/profile.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test1".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
             EnvFile::Info(EnvFileInfo {
                 path: "./test/test_files/env.env".to_string(),
                 base_path: Some("./src/lib".to_string()),
                 profile: Some("env_test2".to_string()),
+                strip_export: None,
+                optional: None,
+                include: None,
             }),
         ],
         env,
@@ -1556,6 +2250,25 @@ fn set_current_task_meta_info_env_mixed() {
     assert!(!envmnt::exists("CARGO_MAKE_CURRENT_TASKBAD_TEST2"));
 }
 
+#[test]
+#[ignore]
+fn set_current_task_location_env_from_task() {
+    let mut task = Task::new();
+    task.initial_makefile = Some("./examples/Makefile.toml".to_string());
+    task.initial_makefile_dir = Some("./examples".to_string());
+
+    set_current_task_location_env(&task);
+
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE",
+        "./examples/Makefile.toml"
+    ));
+    assert!(envmnt::is_equal(
+        "CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY",
+        "./examples"
+    ));
+}
+
 #[test]
 fn get_base_directory_name_valid() {
     let name = get_base_directory_name();
@@ -1636,3 +2349,502 @@ fn setup_env_for_project_workspace_no_main_crate() {
     assert!(envmnt::is_equal("CARGO_MAKE_PROJECT_NAME", "workspace1"));
     assert!(!envmnt::exists("CARGO_MAKE_PROJECT_VERSION"));
 }
+
+#[test]
+fn read_env_value_file_present() {
+    let file_info = EnvValueFile {
+        from_file: "env_value_file.txt".to_string(),
+        trim: None,
+        optional: None,
+        base_path: Some("./src/lib/test/test_files".to_string()),
+    };
+
+    let value = read_env_value_file(&file_info).unwrap();
+
+    assert_eq!(value.unwrap(), "secret value\n");
+}
+
+#[test]
+fn read_env_value_file_trimmed() {
+    let file_info = EnvValueFile {
+        from_file: "./src/lib/test/test_files/env_value_file.txt".to_string(),
+        trim: Some(true),
+        optional: None,
+        base_path: None,
+    };
+
+    let value = read_env_value_file(&file_info).unwrap();
+
+    assert_eq!(value.unwrap(), "secret value");
+}
+
+#[test]
+fn read_env_value_file_missing_optional() {
+    let file_info = EnvValueFile {
+        from_file: "missing.txt".to_string(),
+        trim: Some(true),
+        optional: Some(true),
+        base_path: Some("./src/lib/test/test_files".to_string()),
+    };
+
+    let value = read_env_value_file(&file_info).unwrap();
+
+    assert!(value.is_none());
+}
+
+#[test]
+fn read_env_value_file_missing_required() {
+    let file_info = EnvValueFile {
+        from_file: "missing.txt".to_string(),
+        trim: None,
+        optional: Some(false),
+        base_path: Some("./src/lib/test/test_files".to_string()),
+    };
+
+    let output = read_env_value_file(&file_info);
+
+    assert!(output.is_err());
+}
+
+#[test]
+#[ignore]
+fn set_env_for_config_file_values() {
+    env::remove_var("ENV_FILE_VALUE_PRESENT");
+    env::remove_var("ENV_FILE_VALUE_MISSING");
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "ENV_FILE_VALUE_PRESENT".to_string(),
+        EnvValue::File(EnvValueFile {
+            from_file: "env_value_file.txt".to_string(),
+            trim: Some(true),
+            optional: None,
+            base_path: Some("./src/lib/test/test_files".to_string()),
+        }),
+    );
+    env.insert(
+        "ENV_FILE_VALUE_MISSING".to_string(),
+        EnvValue::File(EnvValueFile {
+            from_file: "missing.txt".to_string(),
+            trim: None,
+            optional: Some(true),
+            base_path: Some("./src/lib/test/test_files".to_string()),
+        }),
+    );
+
+    set_env_for_config(env, None, false);
+
+    assert_eq!(
+        envmnt::get_or_panic("ENV_FILE_VALUE_PRESENT"),
+        "secret value"
+    );
+    assert!(!envmnt::exists("ENV_FILE_VALUE_MISSING"));
+}
+
+#[test]
+fn get_env_file_value_simple() {
+    assert_eq!(get_env_file_value("value"), "value");
+}
+
+#[test]
+fn get_env_file_value_empty() {
+    assert_eq!(get_env_file_value(""), "\"\"");
+}
+
+#[test]
+fn get_env_file_value_spaces() {
+    assert_eq!(get_env_file_value("some value"), "\"some value\"");
+}
+
+#[test]
+fn get_env_file_value_new_lines() {
+    assert_eq!(get_env_file_value("line1\nline2"), "\"line1\\nline2\"");
+}
+
+#[test]
+fn get_env_file_value_quotes() {
+    assert_eq!(get_env_file_value("a\"b"), "\"a\\\"b\"");
+}
+
+#[test]
+fn get_env_file_value_comment() {
+    assert_eq!(get_env_file_value("a#b"), "\"a#b\"");
+}
+
+#[test]
+fn get_env_file_value_round_trip() {
+    let content = format!(
+        "KEY1={}\nKEY2={}\n",
+        get_env_file_value("some \"quoted\" value"),
+        get_env_file_value("line1\nline2 # text")
+    );
+
+    let env = parse_env_file_content(&content);
+
+    assert_eq!(env.get("KEY1").unwrap(), "some \"quoted\" value");
+    assert_eq!(env.get("KEY2").unwrap(), "line1\nline2 # text");
+}
+
+#[test]
+fn get_env_file_value_backslashes() {
+    assert_eq!(get_env_file_value("C:\\dir"), "\"C:\\\\dir\"");
+}
+
+#[test]
+fn get_env_file_value_windows_path_round_trip() {
+    let content = format!(
+        "KEY1={}\nKEY2={}\nKEY3={}\n",
+        get_env_file_value("C:\\new\\dir"),
+        get_env_file_value("\\\"quoted\\\""),
+        get_env_file_value("C:\\\\server\\share\\")
+    );
+
+    let env = parse_env_file_content(&content);
+
+    assert_eq!(env.get("KEY1").unwrap(), "C:\\new\\dir");
+    assert_eq!(env.get("KEY2").unwrap(), "\\\"quoted\\\"");
+    assert_eq!(env.get("KEY3").unwrap(), "C:\\\\server\\share\\");
+}
+
+#[test]
+fn unescape_env_file_value_quoted() {
+    assert_eq!(
+        unescape_env_file_value("a\\\"b\\nc\\rd\\\\n\\x", true),
+        "a\"b\nc\rd\\n\\x"
+    );
+}
+
+#[test]
+fn unescape_env_file_value_unquoted() {
+    assert_eq!(
+        unescape_env_file_value("a\\\"b\\nc\\\\d", false),
+        "a\"b\nc\\\\d"
+    );
+}
+
+#[test]
+fn with_env_copy_process_env_not_modified() {
+    let env_copy = with_env_copy(|| {
+        set_env_value("WITH_ENV_COPY_TEST_SET", "copy");
+        remove_env_value("PATH");
+
+        assert_eq!(get_env_value("WITH_ENV_COPY_TEST_SET").unwrap(), "copy");
+        assert!(get_env_value("PATH").is_none());
+        assert_eq!(
+            expand_value("${WITH_ENV_COPY_TEST_SET}"),
+            "copy".to_string()
+        );
+    });
+
+    assert_eq!(env_copy.get("WITH_ENV_COPY_TEST_SET").unwrap(), "copy");
+    assert!(!env_copy.contains_key("PATH"));
+    assert!(!envmnt::exists("WITH_ENV_COPY_TEST_SET"));
+    assert!(envmnt::exists("PATH"));
+    assert!(ENV_COPY.with(|env_copy| env_copy.borrow().is_none()));
+}
+
+#[test]
+fn expand_value_without_defaults_undefined_and_partial() {
+    assert_eq!(
+        expand_value_without_defaults(
+            "$${EXPAND_TEST_UNDEFINED} a$b ${EXPAND_TEST_UNDEFINED} ${ab"
+        ),
+        "$${EXPAND_TEST_UNDEFINED} a$b ${EXPAND_TEST_UNDEFINED} ${ab"
+    );
+    assert_eq!(expand_value_without_defaults("cost$"), "cost$");
+    assert_eq!(
+        expand_value_without_defaults("${EXPAND TEST}"),
+        "${EXPAND TEST}"
+    );
+}
+
+#[test]
+#[ignore]
+fn dump_env_resolved_values() {
+    let profile_name = profile::get();
+    profile::set("dump_env_profile");
+    envmnt::remove("CARGO_MAKE_ENV_FILE_TEST1");
+    envmnt::remove("CARGO_MAKE_ENV_FILE_TEST2");
+    envmnt::remove("CARGO_MAKE_ENV_FILE_TEST3");
+    envmnt::set("DUMP_ENV_EXISTING", "existing");
+
+    let mut profile_env = IndexMap::new();
+    profile_env.insert(
+        "DUMP_ENV_PROFILE".to_string(),
+        EnvValue::Value("active".to_string()),
+    );
+    let mut other_profile_env = IndexMap::new();
+    other_profile_env.insert(
+        "DUMP_ENV_OTHER_PROFILE".to_string(),
+        EnvValue::Value("inactive".to_string()),
+    );
+
+    let mut env = IndexMap::new();
+    env.insert(
+        "DUMP_ENV_SIMPLE".to_string(),
+        EnvValue::Value("${DUMP_ENV_EXISTING}".to_string()),
+    );
+    env.insert(
+        "DUMP_ENV_SPACES".to_string(),
+        EnvValue::Value("some value".to_string()),
+    );
+    env.insert(
+        "DUMP_ENV_NEW_LINES".to_string(),
+        EnvValue::Value("line1\nline2".to_string()),
+    );
+    env.insert(
+        "dump_env_profile".to_string(),
+        EnvValue::Profile(profile_env),
+    );
+    env.insert(
+        "dump_env_other_profile".to_string(),
+        EnvValue::Profile(other_profile_env),
+    );
+
+    let config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![EnvFile::Path(
+            "./src/lib/test/test_files/env.env".to_string(),
+        )],
+        env,
+        env_scripts: vec![EnvScript::Info(EnvScriptInfo {
+            script: "set_env DUMP_ENV_SCRIPT script".to_string(),
+            order: None,
+            script_runner: Some("@duckscript".to_string()),
+            script_runner_args: None,
+        })],
+        tasks: IndexMap::new(),
+    };
+
+    let mut file = test::get_temp_test_directory();
+    file.push("dump.env");
+
+    let result = dump_env(&config, &file);
+    profile::set(&profile_name);
+
+    assert!(result.is_ok());
+    let content = fsio::file::read_text_file(&file).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "CARGO_MAKE_ENV_FILE_TEST1=1",
+            "CARGO_MAKE_ENV_FILE_TEST2=2",
+            "CARGO_MAKE_ENV_FILE_TEST3=3",
+            "DUMP_ENV_NEW_LINES=\"line1\\nline2\"",
+            "DUMP_ENV_PROFILE=active",
+            "DUMP_ENV_SCRIPT=script",
+            "DUMP_ENV_SIMPLE=existing",
+            "DUMP_ENV_SPACES=\"some value\"",
+        ]
+    );
+
+    // the values are computed on a copy of the process env
+    assert!(!envmnt::exists("DUMP_ENV_SIMPLE"));
+    assert!(!envmnt::exists("DUMP_ENV_SCRIPT"));
+    assert!(!envmnt::exists("CARGO_MAKE_ENV_FILE_TEST1"));
+}
+
+#[test]
+#[ignore]
+fn dump_env_invalid_path() {
+    let config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    let result = dump_env(
+        &config,
+        Path::new("./target/_cargo_make_temp/missing/directory/dump.env"),
+    );
+
+    assert!(result.is_err());
+}
+
+fn create_effective_env_config() -> Config {
+    let env: IndexMap<String, EnvValue> = toml::from_str(
+        r#"
+        EFFECTIVE_ENV_VALUE = "global"
+        EFFECTIVE_ENV_DEFAULT = "${EFFECTIVE_ENV_UNDEFINED:-fallback}"
+        EFFECTIVE_ENV_LIST = ["1", "2"]
+        EFFECTIVE_ENV_BOOL = true
+        EFFECTIVE_ENV_DOCUMENTED = { value = "documented", description = "test" }
+        EFFECTIVE_ENV_DECODE = { source = "a", mapping = { a = "decoded" } }
+        EFFECTIVE_ENV_CONDITION_VALID = { value = "valid", condition = { env_not_set = ["EFFECTIVE_ENV_UNDEFINED"] } }
+        EFFECTIVE_ENV_CONDITION_INVALID = { value = "invalid", condition = { env_set = ["EFFECTIVE_ENV_UNDEFINED"] } }
+        EFFECTIVE_ENV_UNSET = { unset = true }
+
+        [effective_profile]
+        EFFECTIVE_ENV_VALUE = "profile"
+        EFFECTIVE_ENV_PROFILE_ONLY = "profile only"
+        "#,
+    )
+    .unwrap();
+
+    Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env,
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    }
+}
+
+#[test]
+fn effective_env_value() {
+    let config = create_effective_env_config();
+
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_VALUE", None).unwrap(),
+        "global"
+    );
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_DEFAULT", None).unwrap(),
+        "fallback"
+    );
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_LIST", None).unwrap(),
+        "1;2"
+    );
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_BOOL", None).unwrap(),
+        "true"
+    );
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_DOCUMENTED", None).unwrap(),
+        "documented"
+    );
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_DECODE", None).unwrap(),
+        "decoded"
+    );
+}
+
+#[test]
+fn effective_env_profile() {
+    let config = create_effective_env_config();
+
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_VALUE", Some("effective_profile")).unwrap(),
+        "profile"
+    );
+    assert_eq!(
+        effective_env(
+            &config,
+            "EFFECTIVE_ENV_PROFILE_ONLY",
+            Some("effective_profile")
+        )
+        .unwrap(),
+        "profile only"
+    );
+    // keys not defined in the profile env fall back to the global env
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_LIST", Some("effective_profile")).unwrap(),
+        "1;2"
+    );
+    // other profiles env is ignored
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_VALUE", Some("other_profile")).unwrap(),
+        "global"
+    );
+    assert!(effective_env(&config, "EFFECTIVE_ENV_PROFILE_ONLY", None).is_none());
+}
+
+#[test]
+fn effective_env_condition() {
+    let config = create_effective_env_config();
+
+    assert_eq!(
+        effective_env(&config, "EFFECTIVE_ENV_CONDITION_VALID", None).unwrap(),
+        "valid"
+    );
+    assert!(effective_env(&config, "EFFECTIVE_ENV_CONDITION_INVALID", None).is_none());
+}
+
+#[test]
+fn effective_env_not_defined() {
+    let config = create_effective_env_config();
+
+    assert!(effective_env(&config, "EFFECTIVE_ENV_MISSING", None).is_none());
+    assert!(effective_env(&config, "EFFECTIVE_ENV_UNSET", None).is_none());
+    assert!(effective_env(&config, "effective_profile", None).is_none());
+}
+
+#[test]
+fn get_unresolved_env_variables_none() {
+    let mut env = IndexMap::new();
+    env.insert(
+        "UNRESOLVED_ENV_VARIABLES_DEFINED".to_string(),
+        EnvValue::Value("defined".to_string()),
+    );
+    env.insert(
+        "UNRESOLVED_ENV_VARIABLES_VALUE".to_string(),
+        EnvValue::Value(
+            "${UNRESOLVED_ENV_VARIABLES_DEFINED}/${UNRESOLVED_ENV_VARIABLES_UNSET:-default}"
+                .to_string(),
+        ),
+    );
+
+    let config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env,
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    let unresolved_variables = get_unresolved_env_variables(&config);
+
+    assert!(unresolved_variables.is_empty());
+    assert!(!envmnt::exists("UNRESOLVED_ENV_VARIABLES_DEFINED"));
+    assert!(UNRESOLVED_VARIABLES.with(|variables| variables.borrow().is_none()));
+}
+
+#[test]
+fn get_unresolved_env_variables_found() {
+    let mut env = IndexMap::new();
+    env.insert(
+        "UNRESOLVED_ENV_VARIABLES_VALUE".to_string(),
+        EnvValue::Value(
+            "${UNRESOLVED_ENV_VARIABLES_UNSET}/${UNRESOLVED_ENV_VARIABLES_UNSET}".to_string(),
+        ),
+    );
+    env.insert(
+        "UNRESOLVED_ENV_VARIABLES_LIST".to_string(),
+        EnvValue::List(vec![
+            "${UNRESOLVED_ENV_VARIABLES_UNSET}".to_string(),
+            "${UNRESOLVED_ENV_VARIABLES_LIST_UNSET}".to_string(),
+        ]),
+    );
+
+    let mut task_env = IndexMap::new();
+    task_env.insert(
+        "UNRESOLVED_ENV_VARIABLES_TASK".to_string(),
+        EnvValue::Value("${UNRESOLVED_ENV_VARIABLES_TASK_UNSET}".to_string()),
+    );
+    let mut task = Task::new();
+    task.env = Some(task_env);
+    let mut tasks = IndexMap::new();
+    tasks.insert("test".to_string(), task);
+
+    let config = Config {
+        config: ConfigSection::new(),
+        env_files: vec![],
+        env,
+        env_scripts: vec![],
+        tasks,
+    };
+
+    let unresolved_variables = get_unresolved_env_variables(&config);
+
+    assert_eq!(
+        unresolved_variables,
+        vec![
+            "UNRESOLVED_ENV_VARIABLES_UNSET".to_string(),
+            "UNRESOLVED_ENV_VARIABLES_LIST_UNSET".to_string()
+        ]
+    );
+}
@@ -7,6 +7,7 @@
 #[path = "./execution_plan_test.rs"]
 mod execution_plan_test;
 
+use crate::descriptor;
 use crate::environment;
 use crate::logger;
 use crate::profile;
@@ -342,6 +343,11 @@ fn create_for_step(
         let add = !task_config.disabled.unwrap_or(false);
 
         if add {
+            if let Err(error) = descriptor::check_task_min_version(&task_config) {
+                error!("Unable to invoke task: {}, {}", &task, &error);
+                panic!("Unable to invoke task: {}, {}", &task, &error);
+            }
+
             match task_config.dependencies {
                 Some(ref dependencies) => {
                     for dependency in dependencies {
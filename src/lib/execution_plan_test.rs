@@ -833,6 +833,112 @@ fn create_single_private() {
     create(&config, "test-private", false, false, false);
 }
 
+#[test]
+#[should_panic]
+fn create_single_min_version_not_met() {
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    config_section.end_task = Some("end".to_string());
+    let mut config = Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    config.tasks.insert("init".to_string(), Task::new());
+    config.tasks.insert("end".to_string(), Task::new());
+
+    let mut task = Task::new();
+    task.min_version = Some("999.999.999".to_string());
+
+    config.tasks.insert("test".to_string(), task);
+
+    create(&config, "test", false, false, false);
+}
+
+#[test]
+#[should_panic]
+fn create_with_dependencies_min_version_not_met() {
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    config_section.end_task = Some("end".to_string());
+    let mut config = Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    config.tasks.insert("init".to_string(), Task::new());
+    config.tasks.insert("end".to_string(), Task::new());
+
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["task_dependency".to_string()]);
+    config.tasks.insert("test".to_string(), task);
+
+    let mut task_dependency = Task::new();
+    task_dependency.min_version = Some("999.999.999".to_string());
+    config
+        .tasks
+        .insert("task_dependency".to_string(), task_dependency);
+
+    create(&config, "test", false, false, false);
+}
+
+#[test]
+fn create_single_min_version_not_invoked() {
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    config_section.end_task = Some("end".to_string());
+    let mut config = Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    config.tasks.insert("init".to_string(), Task::new());
+    config.tasks.insert("end".to_string(), Task::new());
+    config.tasks.insert("test".to_string(), Task::new());
+
+    let mut task = Task::new();
+    task.min_version = Some("999.999.999".to_string());
+    config.tasks.insert("newer".to_string(), task);
+
+    let execution_plan = create(&config, "test", false, false, false);
+    assert_eq!(execution_plan.steps.len(), 3);
+    assert_eq!(execution_plan.steps[1].name, "test");
+}
+
+#[test]
+fn create_single_min_version_met() {
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    config_section.end_task = Some("end".to_string());
+    let mut config = Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks: IndexMap::new(),
+    };
+
+    config.tasks.insert("init".to_string(), Task::new());
+    config.tasks.insert("end".to_string(), Task::new());
+
+    let mut task = Task::new();
+    task.min_version = Some("0.1.0".to_string());
+    config.tasks.insert("test".to_string(), task);
+
+    let execution_plan = create(&config, "test", false, false, false);
+    assert_eq!(execution_plan.steps.len(), 3);
+    assert_eq!(execution_plan.steps[1].name, "test");
+}
+
 #[test]
 fn create_single_allow_private() {
     let mut config_section = ConfigSection::new();
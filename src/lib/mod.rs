@@ -152,13 +152,15 @@ mod test;
 // make types public for docs
 pub mod types;
 
+// make descriptor loading public for embedding
+pub mod descriptor;
+
 mod cache;
 mod cli;
 mod cli_commands;
 mod command;
 mod condition;
 mod config;
-mod descriptor;
 mod environment;
 mod execution_plan;
 mod functions;
@@ -36,12 +36,26 @@ pub(crate) fn get() -> String {
     envmnt::get_or(PROFILE_ENV_KEY, DEFAULT_PROFILE)
 }
 
-pub(crate) fn set(profile: &str) -> String {
-    let mut profile_normalized = normalize_profile(&profile);
+pub(crate) fn is_set() -> bool {
+    envmnt::exists(PROFILE_ENV_KEY)
+}
+
+pub(crate) fn unset() {
+    envmnt::remove(PROFILE_ENV_KEY);
+}
+
+pub(crate) fn normalize(profile: &str) -> String {
+    let profile_normalized = normalize_profile(&profile);
 
     if profile_normalized.len() == 0 {
-        profile_normalized = DEFAULT_PROFILE.to_string();
+        DEFAULT_PROFILE.to_string()
+    } else {
+        profile_normalized
     }
+}
+
+pub(crate) fn set(profile: &str) -> String {
+    let profile_normalized = normalize(&profile);
 
     envmnt::set(PROFILE_ENV_KEY, &profile_normalized);
 
@@ -29,6 +29,20 @@ fn normalize_profile_case_and_spaces() {
     assert_eq!(output, "test");
 }
 
+#[test]
+fn normalize_case_and_spaces() {
+    let output = normalize("  tEst  ");
+
+    assert_eq!(output, "test");
+}
+
+#[test]
+fn normalize_empty() {
+    let output = normalize("  ");
+
+    assert_eq!(output, DEFAULT_PROFILE);
+}
+
 #[test]
 fn normalize_additional_profiles_empty() {
     let output = normalize_additional_profiles(&vec![]);
@@ -112,3 +126,16 @@ fn set_additional_multiple() {
     let output = envmnt::get_or_panic("CARGO_MAKE_ADDITIONAL_PROFILES");
     assert_eq!(output, "test1;test2".to_string());
 }
+
+#[test]
+#[ignore]
+fn is_set_after_unset() {
+    let profile_name = get();
+
+    unset();
+    let after_unset = is_set();
+    set(&profile_name);
+
+    assert!(!after_unset);
+    assert!(is_set());
+}
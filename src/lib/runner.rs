@@ -299,6 +299,7 @@ pub(crate) fn run_task(flow_info: &FlowInfo, flow_state: &mut FlowState, step: &
             Some(ref env) => environment::set_current_task_meta_info_env(env.clone()),
             None => (),
         };
+        environment::set_current_task_location_env(&step.config);
 
         if validate_condition(&flow_info, &step) {
             if logger::should_reduce_output(&flow_info) && step.config.script.is_none() {
@@ -13,6 +13,10 @@ use git_info::types::GitInfo;
 use indexmap::IndexMap;
 use rust_info::types::RustInfo;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use toml;
 
 /// Returns the platform name
 pub fn get_platform_name() -> String {
@@ -25,12 +29,30 @@ pub fn get_platform_name() -> String {
     }
 }
 
+/// Appends the extended values which are not already defined to the base values
+fn append_unique_values(base: &Option<Vec<String>>, extended: &Vec<String>) -> Vec<String> {
+    let mut values = base.clone().unwrap_or(vec![]);
+
+    for value in extended {
+        if !values.contains(value) {
+            values.push(value.to_string());
+        }
+    }
+
+    values
+}
+
 fn get_namespaced_task_name(namespace: &str, task: &str) -> String {
     let mut namespaced_task = String::new();
 
     if namespace.len() > 0 {
         namespaced_task.push_str(namespace);
         namespaced_task.push_str("::");
+
+        // namespace already applied
+        if task.len() > 0 && task.starts_with(&namespaced_task) {
+            return task.to_string();
+        }
     }
     namespaced_task.push_str(task);
 
@@ -84,6 +106,11 @@ pub struct CliArgs {
     pub output_file: Option<String>,
     /// Print time summary at end of the flow
     pub print_time_summary: bool,
+    /// Fail in case the makefiles contain unknown keys or duplicate tasks, are empty, override a
+    /// final task, reference a missing env file or their path can not be canonicalized
+    pub strict: bool,
+    /// Fail in case a cli env override key is not defined in the makefiles env
+    pub strict_env: bool,
 }
 
 impl CliArgs {
@@ -112,6 +139,8 @@ impl CliArgs {
             output_format: "default".to_string(),
             output_file: None,
             print_time_summary: false,
+            strict: false,
+            strict_env: false,
         }
     }
 }
@@ -337,6 +366,12 @@ pub struct EnvFileInfo {
     pub base_path: Option<String>,
     /// The profile name this file is relevant to
     pub profile: Option<String>,
+    /// If true, a leading export keyword is removed from the env file keys (default to false)
+    pub strip_export: Option<bool>,
+    /// If true, the env file is skipped in case it does not exist (default to false)
+    pub optional: Option<bool>,
+    /// If true, include lines (for example: include ./other.env) load the referenced env files (default to false)
+    pub include: Option<bool>,
 }
 
 impl EnvFileInfo {
@@ -346,6 +381,9 @@ impl EnvFileInfo {
             path,
             base_path: None,
             profile: None,
+            strip_export: None,
+            optional: None,
+            include: None,
         }
     }
 }
@@ -360,6 +398,72 @@ pub enum EnvFile {
     Info(EnvFileInfo),
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Env script and attributes
+pub struct EnvScriptInfo {
+    /// The script text
+    pub script: String,
+    /// The script order (lower values are invoked first, plain string scripts have order 0)
+    pub order: Option<i32>,
+    /// The script runner (defaults to the default script engine same as plain string scripts)
+    pub script_runner: Option<String>,
+    /// The script runner arguments before the script file path
+    pub script_runner_args: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+/// Holds the env script and attributes
+pub enum EnvScript {
+    /// The script as string
+    Text(String),
+    /// Extended info object for env script
+    Info(EnvScriptInfo),
+}
+
+impl EnvScript {
+    /// Returns the script text
+    pub fn get_script(self: &EnvScript) -> String {
+        match self {
+            EnvScript::Text(ref script) => script.to_string(),
+            EnvScript::Info(ref info) => info.script.to_string(),
+        }
+    }
+
+    /// Returns the script order (defaults to 0)
+    pub fn get_order(self: &EnvScript) -> i32 {
+        match self {
+            EnvScript::Text(_) => 0,
+            EnvScript::Info(ref info) => info.order.unwrap_or(0),
+        }
+    }
+
+    /// Returns the script runner (not defined for plain string scripts)
+    pub fn get_script_runner(self: &EnvScript) -> Option<String> {
+        match self {
+            EnvScript::Text(_) => None,
+            EnvScript::Info(ref info) => info.script_runner.clone(),
+        }
+    }
+
+    /// Returns the script runner arguments (not defined for plain string scripts)
+    pub fn get_script_runner_args(self: &EnvScript) -> Option<Vec<String>> {
+        match self {
+            EnvScript::Text(_) => None,
+            EnvScript::Info(ref info) => info.script_runner_args.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+/// Defines the order in which the env files and env scripts are applied
+pub enum EnvSetupOrder {
+    /// The env files are loaded before the env scripts are invoked
+    FilesFirst,
+    /// The env scripts are invoked before the env files are loaded
+    ScriptsFirst,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 /// Env value provided by a script
 pub struct EnvValueScript {
@@ -396,6 +500,37 @@ pub struct EnvValueConditioned {
     pub condition: Option<TaskCondition>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+/// Env value with a description documenting its purpose
+pub struct EnvValueDocumented {
+    /// The value to set (can be an env expression)
+    pub value: String,
+    /// The env value description
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+/// Env value read from a file
+pub struct EnvValueFile {
+    /// The file path (relative paths are resolved from the makefile directory)
+    pub from_file: String,
+    /// If true, the trailing new line characters are removed from the file content (default false)
+    pub trim: Option<bool>,
+    /// If true, the env variable is not set in case the file does not exist (default false)
+    pub optional: Option<bool>,
+    /// The directory the relative file path is resolved from (defaults to the makefile directory)
+    pub base_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Env value appended to the extended list value
+pub struct EnvValueAppend {
+    /// The values to append
+    pub append: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 /// Holds the env value or script
@@ -414,12 +549,30 @@ pub enum EnvValue {
     Script(EnvValueScript),
     /// Env decoding info
     Decode(EnvValueDecode),
+    /// Documented env value
+    Documented(EnvValueDocumented),
+    /// Env value read from a file
+    File(EnvValueFile),
     /// Conditional env value
     Conditional(EnvValueConditioned),
+    /// Values appended to the extended list value
+    Append(EnvValueAppend),
     /// Profile env
     Profile(IndexMap<String, EnvValue>),
 }
 
+impl EnvValue {
+    /// Returns the env value description (only defined for documented env values)
+    pub fn get_description(self: &EnvValue) -> Option<String> {
+        match self {
+            EnvValue::Documented(ref documented_value) => {
+                Some(documented_value.description.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Arguments used to check whether a crate or rustup component is installed.
 ///
 /// Deserialize into an array of strings. Allows both a single string (which will
@@ -453,7 +606,7 @@ impl<'de> serde::de::Deserialize<'de> for TestArg {
         impl<'de> serde::de::Visitor<'de> for StringVecVisitor {
             type Value = TestArg;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("A string or an array of strings")
             }
 
@@ -859,10 +1012,28 @@ pub struct Task {
     pub disabled: Option<bool>,
     /// if true, the task is hidden from the list of available tasks and also cannot be invoked directly from cli
     pub private: Option<bool>,
+    /// if true, extending makefiles can not override this task (overrides are ignored or fail in strict mode)
+    #[serde(rename = "final")]
+    pub is_final: Option<bool>,
     /// if not false, this task is defined as deprecated
     pub deprecated: Option<DeprecationInfo>,
     /// Extend any task based on the defined name
     pub extend: Option<String>,
+    /// if true, the task intentionally overrides the core task with the same name and no warning is printed
+    pub override_core_task: Option<bool>,
+    /// The minimum cargo-make version required to invoke this task (only checked if the task is invoked)
+    pub min_version: Option<String>,
+    /// Tags used to categorize the task (merged with the tags of the extended task definitions)
+    pub tags: Option<Vec<String>>,
+    /// The makefile which defined (or last overrode) this task
+    #[serde(skip)]
+    source_file: Option<String>,
+    /// The makefile which defined the task (exposed as CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE)
+    #[serde(skip)]
+    pub(crate) initial_makefile: Option<String>,
+    /// The directory of the makefile which defined the task (exposed as CARGO_MAKE_CURRENT_TASK_INITIAL_MAKEFILE_DIRECTORY)
+    #[serde(skip)]
+    pub(crate) initial_makefile_dir: Option<String>,
     /// set to false to notify cargo-make that this is not a workspace and should not call task for every member (same as --no-workspace CLI flag)
     pub workspace: Option<bool>,
     /// set to true to watch for file changes and invoke the task operation
@@ -879,6 +1050,8 @@ pub struct Task {
     pub env_files: Option<Vec<EnvFile>>,
     /// The env vars to setup before running the task commands
     pub env: Option<IndexMap<String, EnvValue>>,
+    /// The name of the shared env block (defined in the env_blocks section) merged into the task env while loading the makefiles
+    pub env_block: Option<String>,
     /// The working directory for the task to execute its command/script
     pub cwd: Option<String>,
     /// if defined, task points to another task and all other properties are ignored
@@ -927,6 +1100,16 @@ impl Task {
         Default::default()
     }
 
+    /// Returns the makefile which defined this task or, in case it was overridden, the overriding makefile
+    pub fn get_source_file(self: &Task) -> Option<String> {
+        self.source_file.clone()
+    }
+
+    /// Sets the makefile which defined this task
+    pub(crate) fn set_source_file(self: &mut Task, source_file: &str) {
+        self.source_file = Some(source_file.to_string());
+    }
+
     /// Apply modifications
     pub fn apply(self: &mut Task, modify_config: &ModifyConfig) {
         match modify_config.private {
@@ -938,115 +1121,108 @@ impl Task {
             None => (),
         };
 
+        match modify_config.rename {
+            Some(ref rename) => {
+                if !rename.is_empty() {
+                    self.update_task_references(&|name: &str| match rename.get(name) {
+                        Some(new_name) => new_name.to_string(),
+                        None => name.to_string(),
+                    });
+                }
+            }
+            None => (),
+        };
+
         match modify_config.namespace {
             Some(ref namespace) => {
                 if namespace.len() > 0 {
-                    if self.extend.is_some() {
-                        self.extend = Some(get_namespaced_task_name(
-                            namespace,
-                            &self.extend.clone().unwrap(),
-                        ));
-                    }
+                    self.update_task_references(&|name: &str| {
+                        get_namespaced_task_name(namespace, name)
+                    });
+                }
+            }
+            None => (),
+        };
+    }
 
-                    if self.alias.is_some() {
-                        self.alias = Some(get_namespaced_task_name(
-                            namespace,
-                            &self.alias.clone().unwrap(),
-                        ));
-                    }
+    /// Updates all the task names referenced by this task (extend, aliases, run task and dependencies)
+    fn update_task_references(self: &mut Task, update_name: &dyn Fn(&str) -> String) {
+        if self.extend.is_some() {
+            self.extend = Some(update_name(&self.extend.clone().unwrap()));
+        }
 
-                    if self.linux_alias.is_some() {
-                        self.linux_alias = Some(get_namespaced_task_name(
-                            namespace,
-                            &self.linux_alias.clone().unwrap(),
-                        ));
-                    }
+        if self.alias.is_some() {
+            self.alias = Some(update_name(&self.alias.clone().unwrap()));
+        }
 
-                    if self.windows_alias.is_some() {
-                        self.windows_alias = Some(get_namespaced_task_name(
-                            namespace,
-                            &self.windows_alias.clone().unwrap(),
-                        ));
-                    }
+        if self.linux_alias.is_some() {
+            self.linux_alias = Some(update_name(&self.linux_alias.clone().unwrap()));
+        }
 
-                    if self.mac_alias.is_some() {
-                        self.mac_alias = Some(get_namespaced_task_name(
-                            namespace,
-                            &self.mac_alias.clone().unwrap(),
-                        ));
-                    }
+        if self.windows_alias.is_some() {
+            self.windows_alias = Some(update_name(&self.windows_alias.clone().unwrap()));
+        }
 
-                    if self.run_task.is_some() {
-                        let mut run_task = self.run_task.clone().unwrap();
+        if self.mac_alias.is_some() {
+            self.mac_alias = Some(update_name(&self.mac_alias.clone().unwrap()));
+        }
 
-                        run_task = match run_task {
-                            RunTaskInfo::Name(value) => {
-                                RunTaskInfo::Name(get_namespaced_task_name(namespace, &value))
+        if self.run_task.is_some() {
+            let mut run_task = self.run_task.clone().unwrap();
+
+            run_task = match run_task {
+                RunTaskInfo::Name(value) => RunTaskInfo::Name(update_name(&value)),
+                RunTaskInfo::Details(mut run_task_details) => {
+                    match run_task_details.name {
+                        RunTaskName::Single(ref name) => {
+                            run_task_details.name = RunTaskName::Single(update_name(name))
+                        }
+                        RunTaskName::Multiple(ref names) => {
+                            let mut updated_names = vec![];
+                            for name in names {
+                                updated_names.push(update_name(name));
                             }
-                            RunTaskInfo::Details(mut run_task_details) => {
-                                match run_task_details.name {
-                                    RunTaskName::Single(ref name) => {
-                                        run_task_details.name = RunTaskName::Single(
-                                            get_namespaced_task_name(namespace, name),
-                                        )
-                                    }
-                                    RunTaskName::Multiple(ref names) => {
-                                        let mut updated_names = vec![];
-                                        for name in names {
-                                            updated_names
-                                                .push(get_namespaced_task_name(namespace, name));
-                                        }
-
-                                        run_task_details.name =
-                                            RunTaskName::Multiple(updated_names);
-                                    }
-                                };
-
-                                RunTaskInfo::Details(run_task_details)
+
+                            run_task_details.name = RunTaskName::Multiple(updated_names);
+                        }
+                    };
+
+                    RunTaskInfo::Details(run_task_details)
+                }
+                RunTaskInfo::Routing(mut routing_info_vector) => {
+                    for mut routing_info in &mut routing_info_vector {
+                        match routing_info.name {
+                            RunTaskName::Single(ref name) => {
+                                routing_info.name = RunTaskName::Single(update_name(name))
                             }
-                            RunTaskInfo::Routing(mut routing_info_vector) => {
-                                for mut routing_info in &mut routing_info_vector {
-                                    match routing_info.name {
-                                        RunTaskName::Single(ref name) => {
-                                            routing_info.name = RunTaskName::Single(
-                                                get_namespaced_task_name(namespace, name),
-                                            )
-                                        }
-                                        RunTaskName::Multiple(ref names) => {
-                                            let mut updated_names = vec![];
-                                            for name in names {
-                                                updated_names.push(get_namespaced_task_name(
-                                                    namespace, name,
-                                                ));
-                                            }
-
-                                            routing_info.name =
-                                                RunTaskName::Multiple(updated_names);
-                                        }
-                                    };
+                            RunTaskName::Multiple(ref names) => {
+                                let mut updated_names = vec![];
+                                for name in names {
+                                    updated_names.push(update_name(name));
                                 }
 
-                                RunTaskInfo::Routing(routing_info_vector)
+                                routing_info.name = RunTaskName::Multiple(updated_names);
                             }
                         };
-
-                        self.run_task = Some(run_task);
                     }
 
-                    if self.dependencies.is_some() {
-                        let dependencies = self.dependencies.clone().unwrap();
-                        let mut modified_dependencies = vec![];
+                    RunTaskInfo::Routing(routing_info_vector)
+                }
+            };
 
-                        for task in &dependencies {
-                            modified_dependencies.push(get_namespaced_task_name(namespace, &task));
-                        }
+            self.run_task = Some(run_task);
+        }
 
-                        self.dependencies = Some(modified_dependencies);
-                    }
-                }
+        if self.dependencies.is_some() {
+            let dependencies = self.dependencies.clone().unwrap();
+            let mut modified_dependencies = vec![];
+
+            for task in &dependencies {
+                modified_dependencies.push(update_name(&task));
             }
-            None => (),
-        };
+
+            self.dependencies = Some(modified_dependencies);
+        }
     }
 
     /// Copies values from the task into self.
@@ -1088,6 +1264,12 @@ impl Task {
             self.private = None;
         }
 
+        if task.is_final.is_some() {
+            self.is_final = task.is_final.clone();
+        } else if override_values {
+            self.is_final = None;
+        }
+
         if task.deprecated.is_some() {
             self.deprecated = task.deprecated.clone();
         } else if override_values {
@@ -1100,6 +1282,41 @@ impl Task {
             self.extend = None;
         }
 
+        if task.override_core_task.is_some() {
+            self.override_core_task = task.override_core_task.clone();
+        } else if override_values {
+            self.override_core_task = None;
+        }
+
+        if task.min_version.is_some() {
+            self.min_version = task.min_version.clone();
+        } else if override_values {
+            self.min_version = None;
+        }
+
+        // tags are accumulated across all the task definitions unless the task is cleared
+        if let Some(ref tags) = task.tags {
+            self.tags = if override_values {
+                Some(tags.clone())
+            } else {
+                Some(append_unique_values(&self.tags, tags))
+            };
+        } else if override_values {
+            self.tags = None;
+        }
+
+        if task.source_file.is_some() {
+            self.source_file = task.source_file.clone();
+        }
+
+        if task.initial_makefile.is_some() {
+            self.initial_makefile = task.initial_makefile.clone();
+        }
+
+        if task.initial_makefile_dir.is_some() {
+            self.initial_makefile_dir = task.initial_makefile_dir.clone();
+        }
+
         if task.workspace.is_some() {
             self.workspace = task.workspace.clone();
         } else if override_values {
@@ -1148,6 +1365,12 @@ impl Task {
             self.env = None;
         }
 
+        if task.env_block.is_some() {
+            self.env_block = task.env_block.clone();
+        } else if override_values {
+            self.env_block = None;
+        }
+
         if task.cwd.is_some() {
             self.cwd = task.cwd.clone();
         } else if override_values {
@@ -1317,8 +1540,15 @@ impl Task {
                     category: self.category.clone(),
                     disabled: override_task.disabled.clone(),
                     private: override_task.private.clone(),
+                    is_final: self.is_final.clone(),
                     deprecated: override_task.deprecated.clone(),
                     extend: override_task.extend.clone(),
+                    override_core_task: self.override_core_task.clone(),
+                    min_version: self.min_version.clone(),
+                    tags: self.tags.clone(),
+                    source_file: self.source_file.clone(),
+                    initial_makefile: self.initial_makefile.clone(),
+                    initial_makefile_dir: self.initial_makefile_dir.clone(),
                     workspace: self.workspace.clone(),
                     watch: override_task.watch.clone(),
                     condition: override_task.condition.clone(),
@@ -1327,6 +1557,7 @@ impl Task {
                     force: override_task.force.clone(),
                     env_files: override_task.env_files.clone(),
                     env: override_task.env.clone(),
+                    env_block: self.env_block.clone(),
                     cwd: override_task.cwd.clone(),
                     alias: None,
                     linux_alias: None,
@@ -1461,7 +1692,7 @@ impl Task {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 /// Holds a single task configuration for a specific platform as an override of another task
 pub struct PlatformOverrideTask {
     /// if true, it should ignore all data in base task
@@ -1631,10 +1862,55 @@ impl PlatformOverrideTask {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 /// Extend with more fine tuning options
 pub struct ExtendOptions {
-    /// Path to another makefile
+    /// Path to another makefile (not required when extending a preset)
+    #[serde(default)]
     pub path: String,
+    /// Name of a built-in preset makefile to extend instead of the path
+    pub preset: Option<String>,
     /// Enable optional extend (default to false)
     pub optional: Option<bool>,
+    /// Only the listed tasks are imported from the extended makefile (default to all tasks)
+    pub tasks: Option<Vec<String>>,
+    /// If true, the dependencies of the listed tasks are imported as well (default to false)
+    pub include_dependencies: Option<bool>,
+    /// The makefile is only extended if one of the listed profiles is active (default to all profiles)
+    pub profiles: Option<Vec<String>>,
+    /// Overrides the skip_core_tasks config value of the extended makefile
+    pub skip_core_tasks: Option<bool>,
+    /// Defines whether the extended makefile is overridden by the current makefile (default) or overrides it
+    pub mode: Option<ExtendMode>,
+    /// Defines the directory from which the relative extend path is resolved (default to the current makefile directory)
+    pub relative_to: Option<ExtendRelativeTo>,
+    /// If false, the env of the extended makefile is not imported (default to true)
+    pub import_env: Option<bool>,
+    /// If false, the config section of the extended makefile is not imported (default to true)
+    pub import_config: Option<bool>,
+    /// If false, the env files of the extended makefile are not imported (default to true)
+    pub import_env_files: Option<bool>,
+    /// If false, the env scripts of the extended makefile are not imported (default to true)
+    pub import_env_scripts: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Defines how the extended makefile is merged with the current makefile
+pub enum ExtendMode {
+    /// The extended makefile is the base and the current makefile overrides it
+    Base,
+    /// The extended makefile is merged on top of the current makefile and overrides it
+    Overlay,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Defines the directory from which the relative extend path is resolved
+pub enum ExtendRelativeTo {
+    /// The directory of the makefile which defines the extend
+    File,
+    /// The directory of the workspace makefile (or the current working directory if not defined)
+    Workspace,
+    /// The current working directory
+    Cwd,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1656,6 +1932,8 @@ pub struct ModifyConfig {
     pub private: Option<bool>,
     /// If set to some value, all core tasks are modified to: <namespace>::<name> for example default::build
     pub namespace: Option<String>,
+    /// Renames specific core tasks (current name -> new name), references to them are updated as well
+    pub rename: Option<IndexMap<String, String>>,
 }
 
 impl ModifyConfig {
@@ -1663,6 +1941,12 @@ impl ModifyConfig {
     pub fn is_modifications_defined(self: &ModifyConfig) -> bool {
         if self.private.unwrap_or(false) {
             true
+        } else if self
+            .rename
+            .as_ref()
+            .map_or(false, |rename| !rename.is_empty())
+        {
+            true
         } else {
             match self.namespace {
                 Some(ref value) => value.len() > 0,
@@ -1697,6 +1981,8 @@ pub struct ConfigSection {
     pub additional_profiles: Option<Vec<String>>,
     /// Minimum cargo-make/makers version
     pub min_version: Option<String>,
+    /// Maximum cargo-make/makers version
+    pub max_version: Option<String>,
     /// The task.workspace default value
     pub default_to_workspace: Option<bool>,
     /// True to reduce console output for non CI execution
@@ -1713,6 +1999,53 @@ pub struct ConfigSection {
     pub windows_load_script: Option<Vec<String>>,
     /// acts like load_script if runtime OS is Mac (takes precedence over load_script)
     pub mac_load_script: Option<Vec<String>>,
+    /// If true, task env blocks are merged key by key with the extended task env instead of replacing it (default false)
+    pub merge_task_env: Option<bool>,
+    /// If true, all task dependencies and aliases must reference defined tasks (default false)
+    pub validate_dependencies: Option<bool>,
+    /// If true, the load script output is parsed as a makefile and merged into the current makefile (default false)
+    pub merge_load_script_output: Option<bool>,
+    /// If true, experimental tasks never override user defined tasks of the same name (default false)
+    pub additive_experimental_tasks: Option<bool>,
+    /// The order in which the env files and env scripts are applied (default FilesFirst)
+    pub env_setup_order: Option<EnvSetupOrder>,
+    /// Env keys (such as PATH) which are joined with the OS path separator instead of overridden when defined by multiple makefiles
+    pub path_env_keys: Option<Vec<String>>,
+    /// Glob patterns of task names which are removed after the core tasks are merged (added to the extended makefiles patterns)
+    pub skip_tasks: Option<Vec<String>>,
+    /// If true, identical env scripts (ignoring surrounding whitespace) defined by multiple makefiles are only invoked once (default false)
+    pub dedup_env_scripts: Option<bool>,
+    /// If true, the task initial makefile path and directory variables in the task command, args and script are expanded while loading the makefiles (default false)
+    pub expand_makefile_location: Option<bool>,
+    /// If true, the additional_profiles and path_env_keys lists are appended to the extended makefile lists instead of replacing them (default false)
+    pub merge_lists: Option<bool>,
+    /// If true, the merged task names are validated against the task name pattern and names which differ only by case are reported as conflicts (default false)
+    pub validate_task_names: Option<bool>,
+    /// The regular expression which validated task names must match (default ^[a-z0-9-]+(::[a-z0-9-]+)*$)
+    pub task_name_pattern: Option<String>,
+    /// If true, validated task names (and the task dependencies and aliases) are converted to lower case (default false)
+    pub fold_task_name_case: Option<bool>,
+    /// If true (and defined by the workspace makefile), the workspace makefile env and config override the member makefile values instead of being extended by them (default false)
+    pub workspace_env_override: Option<bool>,
+    /// If true, empty env values remove the env key defined in the extended makefiles instead of overriding it with an empty value (default false)
+    pub empty_env_is_unset: Option<bool>,
+    /// The profile to use in case no profile was provided (for example via the --profile cli argument)
+    pub default_profile: Option<String>,
+    /// A single env file (shorthand which is appended to the makefile env_files list)
+    pub env_file: Option<String>,
+    /// If true, env keys which change their value type (value, list or profile) between the merged makefiles fail the makefile loading (default false)
+    pub strict_env_types: Option<bool>,
+    /// The task to invoke by default (core task names are resolved via the modify_core_tasks namespace)
+    pub default_task: Option<String>,
+    /// The directory of the makefile which defined the env_file (relative env_file paths are resolved from it)
+    #[serde(skip)]
+    pub(crate) env_file_directory: Option<String>,
+    /// The makefile (or internal) which last set each of the config fields
+    #[serde(skip)]
+    field_sources: IndexMap<String, String>,
+    /// The makefile (or internal) which last defined each of the env keys
+    #[serde(skip)]
+    env_sources: IndexMap<String, String>,
 }
 
 impl ConfigSection {
@@ -1723,6 +2056,25 @@ impl ConfigSection {
 
     /// Apply modifications
     pub fn apply(self: &mut ConfigSection, modify_config: &ModifyConfig) {
+        if let Some(ref rename) = modify_config.rename {
+            for task_name in [
+                &mut self.init_task,
+                &mut self.end_task,
+                &mut self.on_error_task,
+            ]
+            .iter_mut()
+            {
+                let new_name = match task_name {
+                    Some(ref name) => rename.get(name).cloned(),
+                    None => None,
+                };
+
+                if new_name.is_some() {
+                    **task_name = new_name;
+                }
+            }
+        }
+
         match modify_config.namespace {
             Some(ref namespace) => {
                 if self.init_task.is_some() {
@@ -1776,14 +2128,28 @@ impl ConfigSection {
             self.on_error_task = extended.on_error_task.clone();
         }
 
-        if extended.additional_profiles.is_some() {
-            self.additional_profiles = extended.additional_profiles.clone();
+        // list fields are appended in case the extending config (or the base config) enables it
+        let merge_lists = extended.merge_lists.or(self.merge_lists).unwrap_or(false);
+
+        if let Some(ref extended_additional_profiles) = extended.additional_profiles {
+            self.additional_profiles = if merge_lists {
+                Some(append_unique_values(
+                    &self.additional_profiles,
+                    extended_additional_profiles,
+                ))
+            } else {
+                Some(extended_additional_profiles.clone())
+            };
         }
 
         if extended.min_version.is_some() {
             self.min_version = extended.min_version.clone();
         }
 
+        if extended.max_version.is_some() {
+            self.max_version = extended.max_version.clone();
+        }
+
         if extended.default_to_workspace.is_some() {
             self.default_to_workspace = extended.default_to_workspace.clone();
         }
@@ -1815,12 +2181,146 @@ impl ConfigSection {
         if extended.mac_load_script.is_some() {
             self.mac_load_script = extended.mac_load_script.clone();
         }
+
+        if extended.merge_task_env.is_some() {
+            self.merge_task_env = extended.merge_task_env.clone();
+        }
+
+        if extended.validate_dependencies.is_some() {
+            self.validate_dependencies = extended.validate_dependencies.clone();
+        }
+
+        if extended.merge_load_script_output.is_some() {
+            self.merge_load_script_output = extended.merge_load_script_output.clone();
+        }
+
+        if extended.additive_experimental_tasks.is_some() {
+            self.additive_experimental_tasks = extended.additive_experimental_tasks.clone();
+        }
+
+        if extended.env_setup_order.is_some() {
+            self.env_setup_order = extended.env_setup_order.clone();
+        }
+
+        if let Some(ref extended_path_env_keys) = extended.path_env_keys {
+            self.path_env_keys = if merge_lists {
+                Some(append_unique_values(
+                    &self.path_env_keys,
+                    extended_path_env_keys,
+                ))
+            } else {
+                Some(extended_path_env_keys.clone())
+            };
+        }
+
+        if extended.dedup_env_scripts.is_some() {
+            self.dedup_env_scripts = extended.dedup_env_scripts.clone();
+        }
+
+        if extended.merge_lists.is_some() {
+            self.merge_lists = extended.merge_lists.clone();
+        }
+
+        if extended.expand_makefile_location.is_some() {
+            self.expand_makefile_location = extended.expand_makefile_location.clone();
+        }
+
+        if extended.validate_task_names.is_some() {
+            self.validate_task_names = extended.validate_task_names.clone();
+        }
+
+        if extended.task_name_pattern.is_some() {
+            self.task_name_pattern = extended.task_name_pattern.clone();
+        }
+
+        if extended.fold_task_name_case.is_some() {
+            self.fold_task_name_case = extended.fold_task_name_case.clone();
+        }
+
+        if extended.workspace_env_override.is_some() {
+            self.workspace_env_override = extended.workspace_env_override.clone();
+        }
+
+        if extended.empty_env_is_unset.is_some() {
+            self.empty_env_is_unset = extended.empty_env_is_unset.clone();
+        }
+
+        if extended.default_profile.is_some() {
+            self.default_profile = extended.default_profile.clone();
+        }
+
+        if extended.env_file.is_some() {
+            self.env_file = extended.env_file.clone();
+            self.env_file_directory = extended.env_file_directory.clone();
+        }
+
+        if extended.strict_env_types.is_some() {
+            self.strict_env_types = extended.strict_env_types.clone();
+        }
+
+        if extended.default_task.is_some() {
+            self.default_task = extended.default_task.clone();
+        }
+
+        // skip patterns are accumulated across all makefiles
+        if let Some(ref extended_skip_tasks) = extended.skip_tasks {
+            self.skip_tasks = Some(append_unique_values(&self.skip_tasks, extended_skip_tasks));
+        }
+
+        // only the fields defined by the extended config have a source
+        for (field, source) in extended.field_sources.iter() {
+            self.field_sources
+                .insert(field.to_string(), source.to_string());
+        }
+
+        for (key, source) in extended.env_sources.iter() {
+            self.env_sources.insert(key.to_string(), source.to_string());
+        }
+    }
+
+    /// Returns the makefile which last set the provided config field (or internal for the
+    /// internal descriptors)
+    pub fn get_field_source(self: &ConfigSection, field: &str) -> Option<String> {
+        self.field_sources.get(field).cloned()
+    }
+
+    /// Returns the makefile (or internal) which last set each of the defined config fields
+    pub fn get_field_sources(self: &ConfigSection) -> &IndexMap<String, String> {
+        &self.field_sources
+    }
+
+    /// Sets the makefile which defined the provided config field
+    pub(crate) fn set_field_source(self: &mut ConfigSection, field: &str, source: &str) {
+        self.field_sources
+            .insert(field.to_string(), source.to_string());
+    }
+
+    /// Returns the makefile (or internal) which last defined the provided env key
+    pub fn get_env_source(self: &ConfigSection, key: &str) -> Option<String> {
+        self.env_sources.get(key).cloned()
+    }
+
+    /// Sets the makefile which defined the provided env key
+    pub(crate) fn set_env_source(self: &mut ConfigSection, key: &str, source: &str) {
+        self.env_sources.insert(key.to_string(), source.to_string());
+    }
+
+    /// Returns the env setup order (defaults to env files first)
+    pub fn get_env_setup_order(self: &ConfigSection) -> EnvSetupOrder {
+        self.env_setup_order.unwrap_or(EnvSetupOrder::FilesFirst)
     }
 
     /// Returns the load script based on the current platform
     pub fn get_load_script(self: &ConfigSection) -> Option<Vec<String>> {
-        let platform_name = get_platform_name();
+        self.get_load_script_for_platform(&get_platform_name())
+    }
 
+    /// Returns the load script for the provided platform, falling back to the platform
+    /// agnostic load script
+    fn get_load_script_for_platform(
+        self: &ConfigSection,
+        platform_name: &str,
+    ) -> Option<Vec<String>> {
         if platform_name == "windows" {
             if self.windows_load_script.is_some() {
                 self.windows_load_script.clone()
@@ -1853,14 +2353,28 @@ pub struct Config {
     /// The env vars to setup before running the flow
     pub env: IndexMap<String, EnvValue>,
     /// The env scripts to execute before running the flow
-    pub env_scripts: Vec<String>,
+    pub env_scripts: Vec<EnvScript>,
     /// All task definitions
     pub tasks: IndexMap<String, Task>,
 }
 
 impl Config {
-    /// Apply modifications
-    pub fn apply(self: &mut Config, modify_config: &ModifyConfig) {
+    /// Apply modifications.<br>
+    /// Returns an error in case a renamed task collides with another task.
+    pub fn apply(self: &mut Config, modify_config: &ModifyConfig) -> Result<(), DescriptorError> {
+        let rename = modify_config.rename.clone().unwrap_or(IndexMap::new());
+        for (task_name, new_name) in rename.iter() {
+            if self.tasks.contains_key(task_name)
+                && self.tasks.contains_key(new_name)
+                && !rename.contains_key(new_name)
+            {
+                return Err(DescriptorError::RenameConflict {
+                    task: task_name.to_string(),
+                    name: new_name.to_string(),
+                });
+            }
+        }
+
         self.config.apply(&modify_config);
 
         let namespace = match modify_config.namespace {
@@ -1871,15 +2385,27 @@ impl Config {
         let mut modified_tasks = IndexMap::<String, Task>::new();
 
         for (key, value) in self.tasks.iter() {
-            let namespaced_task = get_namespaced_task_name(namespace, &key);
+            let task_name = match rename.get(key) {
+                Some(new_name) => new_name,
+                None => key,
+            };
+            let namespaced_task = get_namespaced_task_name(namespace, task_name);
             let mut task = value.clone();
 
             task.apply(&modify_config);
 
+            if modified_tasks.contains_key(&namespaced_task) {
+                return Err(DescriptorError::RenameConflict {
+                    task: key.to_string(),
+                    name: task_name.to_string(),
+                });
+            }
             modified_tasks.insert(namespaced_task, task);
         }
 
         self.tasks = modified_tasks;
+
+        Ok(())
     }
 }
 
@@ -1895,7 +2421,9 @@ pub struct ExternalConfig {
     /// The env vars to setup before running the flow
     pub env: Option<IndexMap<String, EnvValue>>,
     /// The env scripts to execute before running the flow
-    pub env_scripts: Option<Vec<String>>,
+    pub env_scripts: Option<Vec<EnvScript>>,
+    /// Named env blocks which tasks can merge into their env via the task env_block attribute
+    pub env_blocks: Option<IndexMap<String, IndexMap<String, EnvValue>>>,
     /// All task definitions
     pub tasks: Option<IndexMap<String, Task>>,
 }
@@ -1907,6 +2435,421 @@ impl ExternalConfig {
     }
 }
 
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// The makefile section in which a merge event occurred
+pub enum MergeSection {
+    /// A task was overridden
+    Task,
+    /// An env key was overridden
+    Env,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+/// Holds a task or env key override which occurred while merging the makefiles
+pub struct MergeEvent {
+    /// The makefile section
+    pub section: MergeSection,
+    /// The task name or env key
+    pub key: String,
+    /// The makefile (or internal) which defined the overridden value (if known)
+    pub previous_source: Option<String>,
+    /// The makefile (or internal) which defined the overriding value (if known)
+    pub new_source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The outcome of comparing the makefile min_version with the running cargo-make version
+pub enum VersionCheck {
+    /// The makefile does not define a min_version or the running version is new enough
+    Ok,
+    /// The running version is older than the makefile min_version
+    TooOld {
+        /// The makefile min_version
+        required: String,
+        /// The running cargo-make version
+        running: String,
+    },
+    /// The makefile or its min_version could not be parsed (holds the parsing error)
+    Unparseable(String),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// Holds the makefiles loading options
+pub struct LoadOptions {
+    /// If true, the workspace makefiles (defined by the CARGO_MAKE_WORKSPACE_MAKEFILE env) are not merged into the loaded makefile
+    pub skip_workspace_makefile: bool,
+    /// If true, fails on makefile problems which are otherwise only reported as warnings
+    pub strict: bool,
+    /// If true, fails in case a cli env override key is not defined in the makefiles env
+    pub strict_env: bool,
+}
+
+impl LoadOptions {
+    /// Creates and returns a new instance.
+    pub fn new() -> LoadOptions {
+        Default::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Holds the loaded config and the information collected while loading the makefiles
+pub struct LoadOutput {
+    /// The fully merged config
+    pub config: Config,
+    /// The paths of all the loaded makefiles in their merge precedence order (extended makefiles first)
+    pub load_trace: Vec<String>,
+    /// The task and env overrides which occurred while merging the makefiles (later events override earlier events)
+    pub merge_events: Vec<MergeEvent>,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Holds the differences between two (fully merged) configs
+pub struct ConfigDiff {
+    /// Names of the tasks only defined in the new config
+    pub added_tasks: Vec<String>,
+    /// Names of the tasks only defined in the old config
+    pub removed_tasks: Vec<String>,
+    /// Names of the tasks defined in both configs with different values
+    pub changed_tasks: Vec<String>,
+    /// Env keys only defined in the new config
+    pub added_env: Vec<String>,
+    /// Env keys only defined in the old config
+    pub removed_env: Vec<String>,
+    /// Env keys defined in both configs with different values
+    pub changed_env: Vec<String>,
+    /// Names of the config section fields with different values
+    pub changed_config: Vec<String>,
+}
+
+impl ConfigDiff {
+    /// Creates and returns a new instance.
+    pub fn new() -> ConfigDiff {
+        Default::default()
+    }
+
+    /// Returns true if no differences were found
+    pub fn is_empty(self: &ConfigDiff) -> bool {
+        self.added_tasks.is_empty()
+            && self.removed_tasks.is_empty()
+            && self.changed_tasks.is_empty()
+            && self.added_env.is_empty()
+            && self.removed_env.is_empty()
+            && self.changed_env.is_empty()
+            && self.changed_config.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Holds the error found while loading the makefiles
+pub enum DescriptorError {
+    /// The makefile requires a newer cargo-make version
+    MinVersion {
+        /// The minimum cargo-make version required by the makefile
+        required: String,
+        /// The makefile which requires the minimum version (if known)
+        file: Option<String>,
+    },
+    /// The makefile does not support the current cargo-make version
+    MaxVersion {
+        /// The maximum cargo-make version supported by the makefile
+        maximum: String,
+    },
+    /// The makefile is not a valid toml makefile
+    Parse {
+        /// The makefile path
+        file: String,
+        /// The toml parsing error
+        source: toml::de::Error,
+    },
+    /// The makefile is not a valid yaml makefile
+    ParseYaml {
+        /// The makefile path
+        file: String,
+        /// The yaml parsing error message
+        message: String,
+    },
+    /// The makefile was not found
+    FileNotFound(PathBuf),
+    /// The makefile path points to a directory instead of a makefile
+    IsDirectory(PathBuf),
+    /// A required env file defined in the makefiles was not found (strict mode)
+    EnvFileNotFound(PathBuf),
+    /// The makefile extends itself (the value holds the entire extend chain)
+    CircularExtend(Vec<String>),
+    /// The extend glob pattern is invalid or did not match any makefile
+    ExtendPattern(String),
+    /// The remote makefile could not be loaded
+    Remote(String),
+    /// The makefile contains unknown keys (strict mode)
+    UnknownKeys {
+        /// The makefile path
+        file: String,
+        /// The unknown keys
+        keys: Vec<String>,
+    },
+    /// Task dependencies or aliases reference undefined tasks (task -> reference)
+    UndefinedDependencies(Vec<String>),
+    /// The extend path contains undefined environment variables
+    UnresolvedExtendPath(String),
+    /// The load script failed or its output is not a valid makefile
+    LoadScript(String),
+    /// A task listed in the extend options was not found in the extended makefile
+    ExtendTaskNotFound {
+        /// The extended makefile path
+        path: String,
+        /// The task name
+        task: String,
+    },
+    /// The min_version or max_version value is not a valid semver version
+    InvalidVersion(String),
+    /// The extend preset name is not one of the built-in presets
+    UnknownPreset(String),
+    /// A renamed core task collides with another task
+    RenameConflict {
+        /// The renamed task name
+        task: String,
+        /// The new task name
+        name: String,
+    },
+    /// The makefile does not define any tasks, env, config or extend (strict mode)
+    EmptyDescriptor(String),
+    /// The loaded config could not be serialized
+    Serialize(String),
+    /// The extend chain is deeper than the maximum extend depth
+    ExtendDepthLimit {
+        /// The makefile which exceeded the limit
+        file: String,
+        /// The maximum extend depth
+        limit: usize,
+    },
+    /// The makefile path could not be canonicalized (strict mode)
+    Canonicalize {
+        /// The makefile path
+        file: PathBuf,
+        /// The canonicalization error message
+        message: String,
+    },
+    /// The task name pattern is not a valid regular expression
+    InvalidTaskNamePattern(String),
+    /// The task name does not match the task name pattern
+    InvalidTaskName {
+        /// The task name
+        task: String,
+        /// The task name pattern
+        pattern: String,
+    },
+    /// Two task names are identical after normalization
+    TaskNameConflict {
+        /// The first task name
+        task: String,
+        /// The conflicting task name
+        other: String,
+    },
+    /// The makefile defines the same tasks more than once (strict mode)
+    DuplicateTaskKeys {
+        /// The makefile path
+        file: String,
+        /// The duplicate task names
+        keys: Vec<String>,
+    },
+    /// A cli env override key is not defined in the makefiles env (strict env mode)
+    UndefinedCliEnv(String),
+    /// A task references an env block which is not defined
+    EnvBlockNotFound {
+        /// The task name
+        task: String,
+        /// The env block name
+        block: String,
+    },
+    /// The makefile could not be read from stdin
+    Stdin(String),
+    /// An extending makefile overrides a final task (strict mode)
+    FinalTaskOverride(String),
+    /// An env key changes its value type between the merged makefiles (strict_env_types mode)
+    EnvTypeMismatch {
+        /// The env key (profile env keys are prefixed with the profile name)
+        key: String,
+        /// The value type defined by the extended makefile
+        base_type: String,
+        /// The value type defined by the extending makefile
+        extended_type: String,
+    },
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DescriptorError::MinVersion { required, file } => {
+                write!(
+                    formatter,
+                    "version: {} does not meet minimum required version: {}",
+                    env!("CARGO_PKG_VERSION"),
+                    required
+                )?;
+
+                match file {
+                    Some(file) => write!(formatter, " (required by: {})", file),
+                    None => Ok(()),
+                }
+            }
+            DescriptorError::MaxVersion { maximum } => write!(
+                formatter,
+                "version: {} is newer than the maximum supported version: {}",
+                env!("CARGO_PKG_VERSION"),
+                maximum
+            ),
+            DescriptorError::Parse { file, source } => {
+                let location = match source.line_col() {
+                    Some((line, column)) => {
+                        format!(" (line: {}, column: {})", line + 1, column + 1)
+                    }
+                    None => "".to_string(),
+                };
+
+                write!(
+                    formatter,
+                    "Unable to parse external descriptor: {}{}, {}",
+                    file, location, source
+                )
+            }
+            DescriptorError::ParseYaml { file, message } => write!(
+                formatter,
+                "Unable to parse external descriptor: {}, {}",
+                file, message
+            ),
+            DescriptorError::FileNotFound(file) => {
+                write!(formatter, "Descriptor file: {:#?} not found.", file)
+            }
+            DescriptorError::EnvFileNotFound(file) => {
+                write!(formatter, "Env file: {:#?} not found.", file)
+            }
+            DescriptorError::IsDirectory(directory) => write!(
+                formatter,
+                "Descriptor path: {:#?} is a directory, not a makefile.",
+                directory
+            ),
+            DescriptorError::CircularExtend(extend_chain) => write!(
+                formatter,
+                "circular extend detected: {}",
+                extend_chain.join(" -> ")
+            ),
+            DescriptorError::ExtendPattern(message) => write!(formatter, "{}", message),
+            DescriptorError::Remote(message) => write!(formatter, "{}", message),
+            DescriptorError::UnknownKeys { file, keys } => write!(
+                formatter,
+                "Unknown keys found in descriptor: {}, {}",
+                file,
+                keys.join(", ")
+            ),
+            DescriptorError::UndefinedDependencies(references) => write!(
+                formatter,
+                "Undefined task dependencies found: {}",
+                references.join(", ")
+            ),
+            DescriptorError::UnresolvedExtendPath(path) => write!(
+                formatter,
+                "Unable to resolve environment variables in extend path: {}",
+                path
+            ),
+            DescriptorError::LoadScript(message) => write!(formatter, "{}", message),
+            DescriptorError::ExtendTaskNotFound { path, task } => write!(
+                formatter,
+                "Task: {} not found in extended descriptor: {}",
+                task, path
+            ),
+            DescriptorError::InvalidVersion(message) => write!(formatter, "{}", message),
+            DescriptorError::UnknownPreset(name) => {
+                write!(formatter, "Unknown extend preset: {}", name)
+            }
+            DescriptorError::RenameConflict { task, name } => write!(
+                formatter,
+                "Unable to rename task: {} to: {}, a task with that name already exists.",
+                task, name
+            ),
+            DescriptorError::EmptyDescriptor(file) => write!(
+                formatter,
+                "Makefile: {} is empty, no tasks, env, config or extend defined.",
+                file
+            ),
+            DescriptorError::Serialize(message) => {
+                write!(formatter, "Unable to serialize config: {}", message)
+            }
+            DescriptorError::ExtendDepthLimit { file, limit } => write!(
+                formatter,
+                "extend depth limit: {} exceeded at file: {}",
+                limit, file
+            ),
+            DescriptorError::Canonicalize { file, message } => write!(
+                formatter,
+                "Unable to canonicalize descriptor path: {:#?}, {}",
+                file, message
+            ),
+            DescriptorError::InvalidTaskNamePattern(message) => {
+                write!(formatter, "Invalid task name pattern: {}", message)
+            }
+            DescriptorError::InvalidTaskName { task, pattern } => write!(
+                formatter,
+                "Task name: {} does not match the task name pattern: {}",
+                task, pattern
+            ),
+            DescriptorError::TaskNameConflict { task, other } => write!(
+                formatter,
+                "Task names: {} and {} conflict after normalization.",
+                task, other
+            ),
+            DescriptorError::DuplicateTaskKeys { file, keys } => write!(
+                formatter,
+                "Duplicate task keys found in descriptor: {}, {}",
+                file,
+                keys.join(", ")
+            ),
+            DescriptorError::UndefinedCliEnv(key) => write!(
+                formatter,
+                "Cli env override key: {} is not defined in the makefiles env",
+                key
+            ),
+            DescriptorError::EnvBlockNotFound { task, block } => write!(
+                formatter,
+                "Env block: {} referenced by task: {} not found",
+                block, task
+            ),
+            DescriptorError::Stdin(message) => {
+                write!(formatter, "Unable to read makefile from stdin: {}", message)
+            }
+            DescriptorError::FinalTaskOverride(task) => write!(
+                formatter,
+                "Task: {} is final and can not be overridden (strict mode is enabled)",
+                task
+            ),
+            DescriptorError::EnvTypeMismatch {
+                key,
+                base_type,
+                extended_type,
+            } => write!(
+                formatter,
+                "Env key: {} type changed from: {} to: {} (strict_env_types is enabled)",
+                key, base_type, extended_type
+            ),
+        }
+    }
+}
+
+impl Error for DescriptorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DescriptorError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<DescriptorError> for String {
+    fn from(error: DescriptorError) -> String {
+        error.to_string()
+    }
+}
+
 #[derive(Serialize, Debug)]
 /// Execution plan step to execute
 pub struct Step {
@@ -721,6 +721,99 @@ fn install_rustup_component_info_deserialize_missing_test_arg() {
     assert_eq!(info.test_arg, None);
 }
 
+#[test]
+fn env_script_deserialize_mixed() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        env_scripts = [
+            "echo first",
+            { script = "echo second", order = -1 },
+            { script = "echo third" },
+        ]
+        "#,
+    )
+    .unwrap();
+    let env_scripts = config.env_scripts.unwrap();
+
+    assert_eq!(env_scripts.len(), 3);
+    match env_scripts[0] {
+        EnvScript::Text(ref script) => assert_eq!(script, "echo first"),
+        _ => panic!("invalid env script type"),
+    };
+    assert_eq!(env_scripts[0].get_order(), 0);
+    assert_eq!(env_scripts[1].get_script(), "echo second");
+    assert_eq!(env_scripts[1].get_order(), -1);
+    assert_eq!(env_scripts[2].get_script(), "echo third");
+    assert_eq!(env_scripts[2].get_order(), 0);
+}
+
+#[test]
+fn env_script_deserialize_script_runner() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        env_scripts = [
+            "echo plain",
+            { script = "print('runner')", script_runner = "python3", script_runner_args = ["-u"] },
+        ]
+        "#,
+    )
+    .unwrap();
+    let env_scripts = config.env_scripts.unwrap();
+
+    assert_eq!(env_scripts.len(), 2);
+    assert_eq!(env_scripts[0].get_script(), "echo plain");
+    assert!(env_scripts[0].get_script_runner().is_none());
+    assert!(env_scripts[0].get_script_runner_args().is_none());
+    assert_eq!(env_scripts[1].get_script(), "print('runner')");
+    assert_eq!(env_scripts[1].get_script_runner().unwrap(), "python3");
+    assert_eq!(
+        env_scripts[1].get_script_runner_args().unwrap(),
+        vec!["-u".to_string()]
+    );
+}
+
+#[test]
+fn env_setup_order_deserialize() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        [config]
+        env_setup_order = "ScriptsFirst"
+        "#,
+    )
+    .unwrap();
+    let config_section = config.config.unwrap();
+
+    assert_eq!(
+        config_section.env_setup_order.unwrap(),
+        EnvSetupOrder::ScriptsFirst
+    );
+    assert_eq!(
+        config_section.get_env_setup_order(),
+        EnvSetupOrder::ScriptsFirst
+    );
+}
+
+#[test]
+fn env_setup_order_serialize() {
+    let mut config_section = ConfigSection::new();
+    config_section.env_setup_order = Some(EnvSetupOrder::ScriptsFirst);
+
+    let text = toml::to_string(&config_section).unwrap();
+    let parsed: ConfigSection = toml::from_str(&text).unwrap();
+
+    assert_eq!(parsed.env_setup_order.unwrap(), EnvSetupOrder::ScriptsFirst);
+}
+
+#[test]
+fn config_section_get_env_setup_order_default() {
+    let config_section = ConfigSection::new();
+
+    assert_eq!(
+        config_section.get_env_setup_order(),
+        EnvSetupOrder::FilesFirst
+    );
+}
+
 #[test]
 fn env_value_deserialize_string() {
     let config: ExternalConfig = toml::from_str(
@@ -778,6 +871,28 @@ fn env_value_deserialize_bool_false() {
     }
 }
 
+#[test]
+fn env_value_deserialize_number() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        [env]
+        PORT = 8080
+        OFFSET = -5
+        "#,
+    )
+    .unwrap();
+    let env = config.env.unwrap();
+
+    match env.get("PORT").unwrap() {
+        EnvValue::Number(value) => assert_eq!(*value, 8080),
+        _ => panic!("invalid env value type"),
+    };
+    match env.get("OFFSET").unwrap() {
+        EnvValue::Number(value) => assert_eq!(*value, -5),
+        _ => panic!("invalid env value type"),
+    };
+}
+
 #[test]
 fn env_value_deserialize_list_empty() {
     let config: ExternalConfig = toml::from_str(
@@ -908,6 +1023,71 @@ fn env_value_deserialize_conditional_env_value_with_condition() {
     }
 }
 
+#[test]
+fn env_value_deserialize_documented() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        [env]
+        key = { value = "value", description = "the key description" }
+        "#,
+    )
+    .unwrap();
+    let env = config.env.unwrap();
+
+    for (_, info) in &env {
+        match info {
+            EnvValue::Documented(value) => {
+                assert_eq!(value.value, "value");
+                assert_eq!(value.description, "the key description");
+
+                ()
+            }
+            _ => panic!("invalid env value type"),
+        };
+    }
+}
+
+#[test]
+fn env_value_deserialize_conditional_with_description() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        [env]
+        key = { value = "value", description = "the key description", condition = {} }
+        "#,
+    )
+    .unwrap();
+    let env = config.env.unwrap();
+
+    for (_, info) in &env {
+        match info {
+            EnvValue::Conditional(value) => {
+                assert_eq!(value.value, "value");
+                assert!(value.condition.is_some());
+
+                ()
+            }
+            _ => panic!("invalid env value type"),
+        };
+    }
+}
+
+#[test]
+fn env_value_get_description_documented() {
+    let env_value = EnvValue::Documented(EnvValueDocumented {
+        value: "value".to_string(),
+        description: "the key description".to_string(),
+    });
+
+    assert_eq!(env_value.get_description().unwrap(), "the key description");
+}
+
+#[test]
+fn env_value_get_description_not_documented() {
+    let env_value = EnvValue::Value("value".to_string());
+
+    assert!(env_value.get_description().is_none());
+}
+
 #[test]
 fn env_value_deserialize_profile() {
     let config: ExternalConfig = toml::from_str(
@@ -955,6 +1135,29 @@ fn env_value_deserialize_unset() {
     }
 }
 
+#[test]
+fn env_value_deserialize_append() {
+    let config: ExternalConfig = toml::from_str(
+        r#"
+        [env]
+        key = { append = ["1", "2"] }
+        "#,
+    )
+    .unwrap();
+    let env = config.env.unwrap();
+
+    for (_, info) in &env {
+        match info {
+            EnvValue::Append(value) => {
+                assert_eq!(value.append, vec!["1".to_string(), "2".to_string()]);
+
+                ()
+            }
+            _ => panic!("invalid env value type"),
+        };
+    }
+}
+
 #[test]
 fn task_new() {
     let task = Task::new();
@@ -1050,6 +1253,33 @@ fn task_should_ignore_errors_false_force_true() {
     assert!(!task.should_ignore_errors());
 }
 
+#[test]
+fn task_source_file_not_serialized() {
+    let mut task = Task::new();
+    task.set_source_file("./Makefile.toml");
+    assert_eq!(task.get_source_file().unwrap(), "./Makefile.toml");
+
+    let task_string = toml::to_string(&task).unwrap();
+    assert!(!task_string.contains("source_file"));
+
+    let task: Task = toml::from_str("source_file = \"./Other.toml\"").unwrap();
+    assert!(task.get_source_file().is_none());
+}
+
+#[test]
+fn task_extend_source_file() {
+    let mut base = Task::new();
+    base.set_source_file("./base.toml");
+
+    let mut extended = Task::new();
+    base.extend(&extended);
+    assert_eq!(base.get_source_file().unwrap(), "./base.toml");
+
+    extended.set_source_file("./extended.toml");
+    base.extend(&extended);
+    assert_eq!(base.get_source_file().unwrap(), "./extended.toml");
+}
+
 #[test]
 fn task_extend_both_have_misc_data() {
     let mut base = Task::new();
@@ -1072,6 +1302,14 @@ fn task_extend_both_have_misc_data() {
         private: Some(true),
         deprecated: Some(DeprecationInfo::Message("extended".to_string())),
         extend: None,
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: Some(true),
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: None,
         condition_script: None,
@@ -1145,6 +1383,7 @@ fn task_extend_both_have_misc_data() {
     assert_eq!(base.command.unwrap(), "test1");
     assert!(base.disabled.unwrap());
     assert!(base.private.unwrap());
+    assert!(base.is_final.unwrap());
     assert_eq!(
         base.deprecated.unwrap(),
         DeprecationInfo::Message("extended".to_string())
@@ -1170,6 +1409,14 @@ fn task_extend_extended_have_all_fields() {
         private: Some(true),
         deprecated: Some(DeprecationInfo::Boolean(true)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: None,
         condition_script: None,
@@ -1211,6 +1458,14 @@ fn task_extend_extended_have_all_fields() {
         private: Some(false),
         deprecated: Some(DeprecationInfo::Boolean(false)),
         extend: Some("extended".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: Some(false),
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(false)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -1437,6 +1692,7 @@ fn task_extend_extended_have_all_fields() {
     assert!(base.workspace.unwrap());
     assert!(base.disabled.unwrap());
     assert!(!base.private.unwrap());
+    assert!(!base.is_final.unwrap());
     assert_eq!(base.deprecated.unwrap(), DeprecationInfo::Boolean(false));
     assert_eq!(base.extend.unwrap(), "extended");
     assert_eq!(base.watch.unwrap(), TaskWatchOptions::Boolean(false));
@@ -1486,6 +1742,14 @@ fn task_extend_clear_with_no_data() {
         private: Some(false),
         deprecated: Some(DeprecationInfo::Boolean(true)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(false)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -1704,6 +1968,67 @@ fn task_extend_clear_with_no_data() {
     assert!(base.mac.is_none());
 }
 
+#[test]
+fn task_extend_env_block() {
+    let mut base = Task::new();
+    base.env_block = Some("base".to_string());
+    let mut extended = Task::new();
+    extended.env_block = Some("extended".to_string());
+
+    base.extend(&extended);
+
+    assert_eq!(base.env_block.unwrap(), "extended");
+}
+
+#[test]
+fn task_extend_env_block_clear() {
+    let mut base = Task::new();
+    base.env_block = Some("base".to_string());
+    let mut extended = Task::new();
+    extended.clear = Some(true);
+
+    base.extend(&extended);
+
+    assert!(base.env_block.is_none());
+}
+
+#[test]
+fn task_extend_tags_union() {
+    let mut base = Task::new();
+    base.tags = Some(vec!["ci".to_string(), "local".to_string()]);
+    let mut extended = Task::new();
+    extended.tags = Some(vec!["local".to_string(), "release".to_string()]);
+
+    base.extend(&extended);
+
+    assert_eq!(
+        base.tags.unwrap(),
+        vec!["ci".to_string(), "local".to_string(), "release".to_string()]
+    );
+}
+
+#[test]
+fn task_extend_tags_not_defined() {
+    let mut base = Task::new();
+    base.tags = Some(vec!["ci".to_string()]);
+
+    base.extend(&Task::new());
+
+    assert_eq!(base.tags.unwrap(), vec!["ci".to_string()]);
+}
+
+#[test]
+fn task_extend_tags_clear() {
+    let mut base = Task::new();
+    base.tags = Some(vec!["ci".to_string()]);
+    let mut extended = Task::new();
+    extended.clear = Some(true);
+
+    base.extend(&extended);
+
+    assert!(base.tags.is_none());
+}
+
 #[test]
 fn task_extend_clear_with_all_data() {
     let mut base = Task::new();
@@ -1721,6 +2046,14 @@ fn task_extend_clear_with_all_data() {
         private: Some(false),
         deprecated: Some(DeprecationInfo::Boolean(true)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(false)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -1986,6 +2319,14 @@ fn task_get_normalized_task_undefined() {
         private: Some(true),
         deprecated: None,
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: Some(true),
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: None,
         condition_script: None,
@@ -2060,6 +2401,7 @@ fn task_get_normalized_task_undefined() {
     assert!(!normalized_task.workspace.unwrap());
     assert!(!normalized_task.disabled.unwrap());
     assert!(normalized_task.private.unwrap());
+    assert!(normalized_task.is_final.unwrap());
     assert_eq!(normalized_task.extend.unwrap(), "base");
     assert_eq!(
         normalized_task.watch.unwrap(),
@@ -2107,6 +2449,14 @@ fn task_get_normalized_task_with_override_no_clear() {
         private: Some(true),
         deprecated: None,
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -2301,6 +2651,14 @@ fn task_get_normalized_task_with_override_clear_false() {
         private: Some(true),
         deprecated: Some(DeprecationInfo::Boolean(false)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -2493,6 +2851,14 @@ fn task_get_normalized_task_with_override_clear_false_partial_override() {
         private: Some(true),
         deprecated: Some(DeprecationInfo::Boolean(true)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -2649,6 +3015,14 @@ fn task_get_normalized_task_with_override_clear_true() {
         private: Some(true),
         deprecated: Some(DeprecationInfo::Boolean(true)),
         extend: Some("base".to_string()),
+        override_core_task: None,
+        min_version: None,
+        tags: None,
+        env_block: None,
+        is_final: None,
+        source_file: None,
+        initial_makefile: None,
+        initial_makefile_dir: None,
         watch: Some(TaskWatchOptions::Boolean(true)),
         condition: Some(TaskCondition {
             fail_message: None,
@@ -2985,12 +3359,14 @@ fn config_section_extend_all_values() {
     base.modify_core_tasks = Some(ModifyConfig {
         private: Some(true),
         namespace: Some("base".to_string()),
+        rename: None,
     });
     base.init_task = Some("base_init".to_string());
     base.end_task = Some("base_end".to_string());
     base.on_error_task = Some("base_err".to_string());
     base.additional_profiles = Some(vec!["b1".to_string(), "b2".to_string()]);
     base.min_version = Some("1.0.0".to_string());
+    base.max_version = Some("3.0.0".to_string());
     base.default_to_workspace = Some(true);
     base.reduce_output = Some(true);
     base.time_summary = Some(true);
@@ -2998,17 +3374,38 @@ fn config_section_extend_all_values() {
     base.linux_load_script = Some(vec!["linux".to_string(), "base_info".to_string()]);
     base.windows_load_script = Some(vec!["windows".to_string(), "base_info".to_string()]);
     base.mac_load_script = Some(vec!["mac".to_string(), "base_info".to_string()]);
+    base.merge_task_env = Some(true);
+    base.validate_dependencies = Some(true);
+    base.merge_load_script_output = Some(true);
+    base.additive_experimental_tasks = Some(true);
+    base.env_setup_order = Some(EnvSetupOrder::FilesFirst);
+    base.path_env_keys = Some(vec!["BASE_PATH".to_string()]);
+    base.skip_tasks = Some(vec!["ci-*".to_string()]);
+    base.dedup_env_scripts = Some(true);
+    base.expand_makefile_location = Some(true);
+    base.merge_lists = Some(true);
+    base.validate_task_names = Some(true);
+    base.task_name_pattern = Some("^[a-z]+$".to_string());
+    base.fold_task_name_case = Some(true);
+    base.workspace_env_override = Some(true);
+    base.empty_env_is_unset = Some(true);
+    base.default_profile = Some("base".to_string());
+    base.env_file = Some("base.env".to_string());
+    base.strict_env_types = Some(true);
+    base.default_task = Some("base".to_string());
 
     extended.skip_core_tasks = Some(false);
     extended.modify_core_tasks = Some(ModifyConfig {
         private: Some(false),
         namespace: Some("extended".to_string()),
+        rename: None,
     });
     extended.init_task = Some("extended_init".to_string());
     extended.end_task = Some("extended_end".to_string());
     extended.on_error_task = Some("extended_err".to_string());
     extended.additional_profiles = Some(vec!["e1".to_string(), "e2".to_string()]);
     extended.min_version = Some("2.0.0".to_string());
+    extended.max_version = Some("4.0.0".to_string());
     extended.default_to_workspace = Some(false);
     extended.reduce_output = Some(false);
     extended.time_summary = Some(false);
@@ -3016,6 +3413,25 @@ fn config_section_extend_all_values() {
     extended.linux_load_script = Some(vec!["extended_info".to_string()]);
     extended.windows_load_script = Some(vec!["extended_info".to_string()]);
     extended.mac_load_script = Some(vec!["extended_info".to_string()]);
+    extended.merge_task_env = Some(false);
+    extended.validate_dependencies = Some(false);
+    extended.merge_load_script_output = Some(false);
+    extended.additive_experimental_tasks = Some(false);
+    extended.env_setup_order = Some(EnvSetupOrder::ScriptsFirst);
+    extended.path_env_keys = Some(vec!["PATH".to_string(), "LIB".to_string()]);
+    extended.skip_tasks = Some(vec!["ci-*".to_string(), "workspace-*".to_string()]);
+    extended.dedup_env_scripts = Some(false);
+    extended.expand_makefile_location = Some(false);
+    extended.merge_lists = Some(false);
+    extended.validate_task_names = Some(false);
+    extended.task_name_pattern = Some("^[a-z_]+$".to_string());
+    extended.fold_task_name_case = Some(false);
+    extended.workspace_env_override = Some(false);
+    extended.empty_env_is_unset = Some(false);
+    extended.default_profile = Some("extended".to_string());
+    extended.env_file = Some("extended.env".to_string());
+    extended.strict_env_types = Some(false);
+    extended.default_task = Some("extended".to_string());
 
     base.extend(&mut extended);
 
@@ -3031,6 +3447,7 @@ fn config_section_extend_all_values() {
         vec!["e1".to_string(), "e2".to_string()]
     );
     assert_eq!(base.min_version.unwrap(), "2.0.0".to_string());
+    assert_eq!(base.max_version.unwrap(), "4.0.0".to_string());
     assert!(!base.default_to_workspace.unwrap());
     assert!(!base.reduce_output.unwrap());
     assert!(!base.time_summary.unwrap());
@@ -3038,6 +3455,28 @@ fn config_section_extend_all_values() {
     assert_eq!(base.linux_load_script.unwrap().len(), 1);
     assert_eq!(base.windows_load_script.unwrap().len(), 1);
     assert_eq!(base.mac_load_script.unwrap().len(), 1);
+    assert!(!base.merge_task_env.unwrap());
+    assert!(!base.validate_dependencies.unwrap());
+    assert!(!base.merge_load_script_output.unwrap());
+    assert!(!base.additive_experimental_tasks.unwrap());
+    assert_eq!(base.env_setup_order.unwrap(), EnvSetupOrder::ScriptsFirst);
+    assert_eq!(base.path_env_keys.unwrap().len(), 2);
+    assert_eq!(
+        base.skip_tasks.unwrap(),
+        vec!["ci-*".to_string(), "workspace-*".to_string()]
+    );
+    assert!(!base.dedup_env_scripts.unwrap());
+    assert!(!base.expand_makefile_location.unwrap());
+    assert!(!base.merge_lists.unwrap());
+    assert!(!base.validate_task_names.unwrap());
+    assert_eq!(base.task_name_pattern.unwrap(), "^[a-z_]+$".to_string());
+    assert!(!base.fold_task_name_case.unwrap());
+    assert!(!base.workspace_env_override.unwrap());
+    assert!(!base.empty_env_is_unset.unwrap());
+    assert_eq!(base.default_profile.unwrap(), "extended".to_string());
+    assert_eq!(base.env_file.unwrap(), "extended.env".to_string());
+    assert!(!base.strict_env_types.unwrap());
+    assert_eq!(base.default_task.unwrap(), "extended".to_string());
 }
 
 #[test]
@@ -3049,12 +3488,14 @@ fn config_section_extend_no_values() {
     base.modify_core_tasks = Some(ModifyConfig {
         private: Some(true),
         namespace: Some("base".to_string()),
+        rename: None,
     });
     base.init_task = Some("base_init".to_string());
     base.end_task = Some("base_end".to_string());
     base.on_error_task = Some("base_err".to_string());
     base.additional_profiles = Some(vec!["b1".to_string(), "b2".to_string()]);
     base.min_version = Some("1.0.0".to_string());
+    base.max_version = Some("3.0.0".to_string());
     base.default_to_workspace = Some(true);
     base.reduce_output = Some(true);
     base.time_summary = Some(true);
@@ -3062,6 +3503,25 @@ fn config_section_extend_no_values() {
     base.linux_load_script = Some(vec!["linux".to_string(), "base_info".to_string()]);
     base.windows_load_script = Some(vec!["windows".to_string(), "base_info".to_string()]);
     base.mac_load_script = Some(vec!["mac".to_string(), "base_info".to_string()]);
+    base.merge_task_env = Some(true);
+    base.validate_dependencies = Some(true);
+    base.merge_load_script_output = Some(true);
+    base.additive_experimental_tasks = Some(true);
+    base.env_setup_order = Some(EnvSetupOrder::FilesFirst);
+    base.path_env_keys = Some(vec!["BASE_PATH".to_string()]);
+    base.skip_tasks = Some(vec!["ci-*".to_string()]);
+    base.dedup_env_scripts = Some(true);
+    base.expand_makefile_location = Some(true);
+    base.merge_lists = Some(true);
+    base.validate_task_names = Some(true);
+    base.task_name_pattern = Some("^[a-z]+$".to_string());
+    base.fold_task_name_case = Some(true);
+    base.workspace_env_override = Some(true);
+    base.empty_env_is_unset = Some(true);
+    base.default_profile = Some("base".to_string());
+    base.env_file = Some("base.env".to_string());
+    base.strict_env_types = Some(true);
+    base.default_task = Some("base".to_string());
 
     base.extend(&mut extended);
 
@@ -3077,6 +3537,7 @@ fn config_section_extend_no_values() {
         vec!["b1".to_string(), "b2".to_string()]
     );
     assert_eq!(base.min_version.unwrap(), "1.0.0".to_string());
+    assert_eq!(base.max_version.unwrap(), "3.0.0".to_string());
     assert!(base.default_to_workspace.unwrap());
     assert!(base.reduce_output.unwrap());
     assert!(base.time_summary.unwrap());
@@ -3084,6 +3545,25 @@ fn config_section_extend_no_values() {
     assert_eq!(base.linux_load_script.unwrap().len(), 2);
     assert_eq!(base.windows_load_script.unwrap().len(), 2);
     assert_eq!(base.mac_load_script.unwrap().len(), 2);
+    assert!(base.merge_task_env.unwrap());
+    assert!(base.validate_dependencies.unwrap());
+    assert!(base.merge_load_script_output.unwrap());
+    assert!(base.additive_experimental_tasks.unwrap());
+    assert_eq!(base.env_setup_order.unwrap(), EnvSetupOrder::FilesFirst);
+    assert_eq!(base.path_env_keys.unwrap().len(), 1);
+    assert_eq!(base.skip_tasks.unwrap(), vec!["ci-*".to_string()]);
+    assert!(base.dedup_env_scripts.unwrap());
+    assert!(base.expand_makefile_location.unwrap());
+    assert!(base.merge_lists.unwrap());
+    assert!(base.validate_task_names.unwrap());
+    assert_eq!(base.task_name_pattern.unwrap(), "^[a-z]+$".to_string());
+    assert!(base.fold_task_name_case.unwrap());
+    assert!(base.workspace_env_override.unwrap());
+    assert!(base.empty_env_is_unset.unwrap());
+    assert_eq!(base.default_profile.unwrap(), "base".to_string());
+    assert_eq!(base.env_file.unwrap(), "base.env".to_string());
+    assert!(base.strict_env_types.unwrap());
+    assert_eq!(base.default_task.unwrap(), "base".to_string());
 }
 
 #[test]
@@ -3095,12 +3575,14 @@ fn config_section_extend_some_values() {
     base.modify_core_tasks = Some(ModifyConfig {
         private: Some(true),
         namespace: Some("base".to_string()),
+        rename: None,
     });
     base.init_task = Some("base_init".to_string());
     base.end_task = Some("base_end".to_string());
     base.on_error_task = Some("base_err".to_string());
     base.additional_profiles = Some(vec!["b1".to_string(), "b2".to_string()]);
     base.min_version = Some("1.0.0".to_string());
+    base.max_version = Some("3.0.0".to_string());
     base.default_to_workspace = Some(true);
     base.reduce_output = Some(true);
     base.time_summary = Some(true);
@@ -3108,6 +3590,25 @@ fn config_section_extend_some_values() {
     base.linux_load_script = Some(vec!["linux".to_string(), "base_info".to_string()]);
     base.windows_load_script = Some(vec!["windows".to_string(), "base_info".to_string()]);
     base.mac_load_script = Some(vec!["mac".to_string(), "base_info".to_string()]);
+    base.merge_task_env = Some(true);
+    base.validate_dependencies = Some(true);
+    base.merge_load_script_output = Some(true);
+    base.additive_experimental_tasks = Some(true);
+    base.env_setup_order = Some(EnvSetupOrder::FilesFirst);
+    base.path_env_keys = Some(vec!["BASE_PATH".to_string()]);
+    base.skip_tasks = Some(vec!["ci-*".to_string()]);
+    base.dedup_env_scripts = Some(true);
+    base.expand_makefile_location = Some(true);
+    base.merge_lists = Some(true);
+    base.validate_task_names = Some(true);
+    base.task_name_pattern = Some("^[a-z]+$".to_string());
+    base.fold_task_name_case = Some(true);
+    base.workspace_env_override = Some(true);
+    base.empty_env_is_unset = Some(true);
+    base.default_profile = Some("base".to_string());
+    base.env_file = Some("base.env".to_string());
+    base.strict_env_types = Some(true);
+    base.default_task = Some("base".to_string());
 
     extended.skip_core_tasks = Some(false);
     extended.init_task = Some("extended_init".to_string());
@@ -3126,6 +3627,7 @@ fn config_section_extend_some_values() {
         vec!["b1".to_string(), "b2".to_string()]
     );
     assert_eq!(base.min_version.unwrap(), "1.0.0".to_string());
+    assert_eq!(base.max_version.unwrap(), "3.0.0".to_string());
     assert!(base.default_to_workspace.unwrap());
     assert!(base.reduce_output.unwrap());
     assert!(base.time_summary.unwrap());
@@ -3133,6 +3635,116 @@ fn config_section_extend_some_values() {
     assert_eq!(base.linux_load_script.unwrap().len(), 2);
     assert_eq!(base.windows_load_script.unwrap().len(), 2);
     assert_eq!(base.mac_load_script.unwrap().len(), 2);
+    assert_eq!(base.skip_tasks.unwrap(), vec!["ci-*".to_string()]);
+    assert!(base.dedup_env_scripts.unwrap());
+    assert!(base.expand_makefile_location.unwrap());
+    assert!(base.merge_lists.unwrap());
+    assert!(base.validate_task_names.unwrap());
+    assert_eq!(base.task_name_pattern.unwrap(), "^[a-z]+$".to_string());
+    assert!(base.fold_task_name_case.unwrap());
+    assert!(base.workspace_env_override.unwrap());
+    assert!(base.empty_env_is_unset.unwrap());
+    assert_eq!(base.default_profile.unwrap(), "base".to_string());
+    assert_eq!(base.env_file.unwrap(), "base.env".to_string());
+    assert!(base.strict_env_types.unwrap());
+    assert_eq!(base.default_task.unwrap(), "base".to_string());
+}
+
+#[test]
+fn config_section_extend_lists_replaced() {
+    let mut base = ConfigSection::new();
+    let mut extended = ConfigSection::new();
+
+    base.additional_profiles = Some(vec!["b1".to_string(), "shared".to_string()]);
+    base.path_env_keys = Some(vec!["PATH".to_string()]);
+    extended.additional_profiles = Some(vec!["shared".to_string(), "e1".to_string()]);
+    extended.path_env_keys = Some(vec!["LIB".to_string()]);
+
+    base.extend(&mut extended);
+
+    assert_eq!(
+        base.additional_profiles.unwrap(),
+        vec!["shared".to_string(), "e1".to_string()]
+    );
+    assert_eq!(base.path_env_keys.unwrap(), vec!["LIB".to_string()]);
+}
+
+#[test]
+fn config_section_extend_lists_appended() {
+    let mut base = ConfigSection::new();
+    let mut extended = ConfigSection::new();
+
+    base.additional_profiles = Some(vec!["b1".to_string(), "shared".to_string()]);
+    base.path_env_keys = Some(vec!["PATH".to_string()]);
+    extended.merge_lists = Some(true);
+    extended.additional_profiles = Some(vec!["shared".to_string(), "e1".to_string()]);
+    extended.path_env_keys = Some(vec!["LIB".to_string()]);
+    extended.init_task = Some("extended_init".to_string());
+
+    base.extend(&mut extended);
+
+    assert_eq!(
+        base.additional_profiles.unwrap(),
+        vec!["b1".to_string(), "shared".to_string(), "e1".to_string()]
+    );
+    assert_eq!(
+        base.path_env_keys.unwrap(),
+        vec!["PATH".to_string(), "LIB".to_string()]
+    );
+    assert_eq!(base.init_task.unwrap(), "extended_init");
+}
+
+#[test]
+fn config_section_extend_lists_appended_by_base() {
+    let mut base = ConfigSection::new();
+    let mut extended = ConfigSection::new();
+
+    base.merge_lists = Some(true);
+    base.additional_profiles = Some(vec!["b1".to_string()]);
+    extended.additional_profiles = Some(vec!["e1".to_string()]);
+
+    base.extend(&mut extended);
+
+    assert_eq!(
+        base.additional_profiles.unwrap(),
+        vec!["b1".to_string(), "e1".to_string()]
+    );
+    assert!(base.merge_lists.unwrap());
+}
+
+#[test]
+fn config_section_extend_field_sources() {
+    let mut base = ConfigSection::new();
+    let mut extended = ConfigSection::new();
+
+    base.init_task = Some("base_init".to_string());
+    base.set_field_source("init_task", "base.toml");
+    base.end_task = Some("base_end".to_string());
+    base.set_field_source("end_task", "base.toml");
+    extended.end_task = Some("extended_end".to_string());
+    extended.set_field_source("end_task", "extended.toml");
+
+    base.extend(&mut extended);
+
+    assert_eq!(base.get_field_source("init_task").unwrap(), "base.toml");
+    assert_eq!(base.get_field_source("end_task").unwrap(), "extended.toml");
+    assert!(base.get_field_source("on_error_task").is_none());
+}
+
+#[test]
+fn config_section_extend_env_sources() {
+    let mut base = ConfigSection::new();
+    let mut extended = ConfigSection::new();
+
+    base.set_env_source("BASE", "base.toml");
+    base.set_env_source("SHARED", "base.toml");
+    extended.set_env_source("SHARED", "extended.toml");
+
+    base.extend(&mut extended);
+
+    assert_eq!(base.get_env_source("BASE").unwrap(), "base.toml");
+    assert_eq!(base.get_env_source("SHARED").unwrap(), "extended.toml");
+    assert!(base.get_env_source("OTHER").is_none());
 }
 
 #[test]
@@ -3179,8 +3791,54 @@ fn config_section_get_get_load_script_all_defined() {
     assert_eq!(script[0], get_platform_name());
 }
 
-#[test]
-fn workspace_new() {
+fn create_platform_load_script_config() -> ConfigSection {
+    let mut config = ConfigSection::new();
+    config.load_script = Some(vec!["base".to_string()]);
+    config.linux_load_script = Some(vec!["linux".to_string()]);
+    config.windows_load_script = Some(vec!["windows".to_string()]);
+    config.mac_load_script = Some(vec!["mac".to_string()]);
+
+    config
+}
+
+#[test]
+fn config_section_get_load_script_for_platform_linux() {
+    let config = create_platform_load_script_config();
+
+    let load_script = config.get_load_script_for_platform("linux");
+    assert_eq!(load_script.unwrap(), vec!["linux".to_string()]);
+}
+
+#[test]
+fn config_section_get_load_script_for_platform_windows() {
+    let config = create_platform_load_script_config();
+
+    let load_script = config.get_load_script_for_platform("windows");
+    assert_eq!(load_script.unwrap(), vec!["windows".to_string()]);
+}
+
+#[test]
+fn config_section_get_load_script_for_platform_mac() {
+    let config = create_platform_load_script_config();
+
+    let load_script = config.get_load_script_for_platform("mac");
+    assert_eq!(load_script.unwrap(), vec!["mac".to_string()]);
+}
+
+#[test]
+fn config_section_get_load_script_for_platform_fallback() {
+    let mut config = ConfigSection::new();
+    config.load_script = Some(vec!["base".to_string()]);
+    config.linux_load_script = Some(vec!["linux".to_string()]);
+
+    for platform_name in &["windows", "mac"] {
+        let load_script = config.get_load_script_for_platform(platform_name);
+        assert_eq!(load_script.unwrap(), vec!["base".to_string()]);
+    }
+}
+
+#[test]
+fn workspace_new() {
     let workspace = Workspace::new();
 
     assert!(workspace.members.is_none());
@@ -3200,11 +3858,26 @@ fn get_namespaced_task_name_with_value() {
     assert_eq!(output, "prefix::my_task");
 }
 
+#[test]
+fn get_namespaced_task_name_already_namespaced() {
+    let output = get_namespaced_task_name("prefix", "prefix::my_task");
+
+    assert_eq!(output, "prefix::my_task");
+}
+
+#[test]
+fn get_namespaced_task_name_other_namespace() {
+    let output = get_namespaced_task_name("prefix", "other::my_task");
+
+    assert_eq!(output, "prefix::other::my_task");
+}
+
 #[test]
 fn task_apply_task_empty_modify_empty() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.apply(&modify_config);
@@ -3223,6 +3896,7 @@ fn task_apply_task_empty_modify_private() {
     let modify_config = ModifyConfig {
         private: Some(true),
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.apply(&modify_config);
@@ -3241,6 +3915,7 @@ fn task_apply_task_empty_modify_not_private() {
     let modify_config = ModifyConfig {
         private: Some(false),
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.apply(&modify_config);
@@ -3259,6 +3934,7 @@ fn task_apply_modify_empty() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.private = Some(true);
@@ -3278,6 +3954,7 @@ fn task_apply_modify_private() {
     let modify_config = ModifyConfig {
         private: Some(true),
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.private = Some(false);
@@ -3297,6 +3974,7 @@ fn task_apply_modify_not_private() {
     let modify_config = ModifyConfig {
         private: Some(false),
         namespace: None,
+        rename: None,
     };
     let mut task = Task::new();
     task.private = Some(true);
@@ -3316,6 +3994,7 @@ fn task_apply_task_empty_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
     let mut task = Task::new();
     task.apply(&modify_config);
@@ -3334,6 +4013,7 @@ fn task_apply_no_run_task_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3362,6 +4042,7 @@ fn task_apply_run_task_name_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3387,6 +4068,7 @@ fn task_apply_run_task_details_single_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3420,6 +4102,7 @@ fn task_apply_run_task_details_multiple_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3456,6 +4139,7 @@ fn task_apply_run_task_routing_info_single_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3491,6 +4175,7 @@ fn task_apply_run_task_routing_info_multiple_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
 
     let mut task = Task::new();
@@ -3529,6 +4214,7 @@ fn config_section_apply_config_empty_modify_empty() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: None,
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.apply(&modify_config);
@@ -3543,6 +4229,7 @@ fn config_section_apply_config_empty_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("default".to_string()),
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.apply(&modify_config);
@@ -3557,6 +4244,7 @@ fn config_section_apply_config_with_values_modify_empty() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: None,
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.init_task = Some("init".to_string());
@@ -3574,6 +4262,7 @@ fn config_section_apply_config_with_values_modify_namespace() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: Some("config_ns".to_string()),
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.init_task = Some("init".to_string());
@@ -3591,6 +4280,7 @@ fn config_apply_modify_empty() {
     let modify_config = ModifyConfig {
         private: None,
         namespace: None,
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.init_task = Some("init".to_string());
@@ -3605,7 +4295,7 @@ fn config_apply_modify_empty() {
         env_scripts: vec![],
         tasks,
     };
-    config.apply(&modify_config);
+    config.apply(&modify_config).unwrap();
 
     assert_eq!(config.config.init_task.unwrap(), "init");
     assert_eq!(config.env.len(), 0);
@@ -3618,6 +4308,7 @@ fn config_apply_modify_all() {
     let modify_config = ModifyConfig {
         private: Some(true),
         namespace: Some("all".to_string()),
+        rename: None,
     };
     let mut config_section = ConfigSection::new();
     config_section.init_task = Some("init".to_string());
@@ -3632,7 +4323,7 @@ fn config_apply_modify_all() {
         env_scripts: vec![],
         tasks,
     };
-    config.apply(&modify_config);
+    config.apply(&modify_config).unwrap();
 
     assert_eq!(config.config.init_task.unwrap(), "all::init");
     assert_eq!(config.env.len(), 0);
@@ -3640,6 +4331,192 @@ fn config_apply_modify_all() {
     assert!(config.tasks.get("all::test").unwrap().private.unwrap());
 }
 
+#[test]
+fn config_apply_modify_twice() {
+    let modify_config = ModifyConfig {
+        private: Some(true),
+        namespace: Some("all".to_string()),
+        rename: None,
+    };
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    let mut tasks = IndexMap::new();
+    let mut task = Task::new();
+    task.alias = Some("other".to_string());
+    task.dependencies = Some(vec!["other".to_string()]);
+    tasks.insert("test".to_string(), task);
+    tasks.insert("other".to_string(), Task::new());
+    let mut config = Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks,
+    };
+    config.apply(&modify_config).unwrap();
+    config.apply(&modify_config).unwrap();
+
+    assert_eq!(config.config.init_task.unwrap(), "all::init");
+    assert_eq!(config.tasks.len(), 2);
+    assert!(config.tasks.contains_key("all::test"));
+    assert!(config.tasks.contains_key("all::other"));
+    let task = config.tasks.get("all::test").unwrap();
+    assert_eq!(task.alias.clone().unwrap(), "all::other");
+    assert_eq!(
+        task.dependencies.clone().unwrap(),
+        vec!["all::other".to_string()]
+    );
+}
+
+fn create_rename_test_config() -> Config {
+    let mut config_section = ConfigSection::new();
+    config_section.init_task = Some("init".to_string());
+    config_section.end_task = Some("end".to_string());
+    let mut tasks = IndexMap::new();
+    tasks.insert("init".to_string(), Task::new());
+    tasks.insert("end".to_string(), Task::new());
+    tasks.insert("build".to_string(), Task::new());
+    let mut task = Task::new();
+    task.dependencies = Some(vec!["init".to_string(), "build".to_string()]);
+    task.alias = Some("build".to_string());
+    tasks.insert("flow".to_string(), task);
+
+    Config {
+        config: config_section,
+        env_files: vec![],
+        env: IndexMap::new(),
+        env_scripts: vec![],
+        tasks,
+    }
+}
+
+fn create_rename_modify_config(
+    renames: Vec<(&str, &str)>,
+    namespace: Option<&str>,
+) -> ModifyConfig {
+    let mut rename = IndexMap::new();
+    for (task_name, new_name) in renames {
+        rename.insert(task_name.to_string(), new_name.to_string());
+    }
+
+    ModifyConfig {
+        private: None,
+        namespace: namespace.map(|value| value.to_string()),
+        rename: Some(rename),
+    }
+}
+
+#[test]
+fn modify_config_is_modifications_defined_rename() {
+    let modify_config = create_rename_modify_config(vec![("build", "core-build")], None);
+    assert!(modify_config.is_modifications_defined());
+
+    let modify_config = create_rename_modify_config(vec![], None);
+    assert!(!modify_config.is_modifications_defined());
+}
+
+#[test]
+fn config_apply_modify_rename() {
+    let modify_config = create_rename_modify_config(vec![("build", "core-build")], None);
+    let mut config = create_rename_test_config();
+
+    config.apply(&modify_config).unwrap();
+
+    assert_eq!(config.tasks.len(), 4);
+    assert!(config.tasks.contains_key("core-build"));
+    assert!(!config.tasks.contains_key("build"));
+    assert!(config.tasks.contains_key("init"));
+    let task = config.tasks.get("flow").unwrap();
+    assert_eq!(
+        task.dependencies.clone().unwrap(),
+        vec!["init".to_string(), "core-build".to_string()]
+    );
+    assert_eq!(task.alias.clone().unwrap(), "core-build");
+    assert_eq!(config.config.init_task.unwrap(), "init");
+}
+
+#[test]
+fn config_apply_modify_rename_config_tasks() {
+    let modify_config = create_rename_modify_config(vec![("init", "core-init")], None);
+    let mut config = create_rename_test_config();
+
+    config.apply(&modify_config).unwrap();
+
+    assert!(config.tasks.contains_key("core-init"));
+    assert_eq!(config.config.init_task.unwrap(), "core-init");
+    assert_eq!(config.config.end_task.unwrap(), "end");
+}
+
+#[test]
+fn config_apply_modify_rename_with_namespace() {
+    let modify_config = create_rename_modify_config(vec![("build", "core-build")], Some("ns"));
+    let mut config = create_rename_test_config();
+
+    config.apply(&modify_config).unwrap();
+
+    assert!(config.tasks.contains_key("ns::core-build"));
+    assert!(config.tasks.contains_key("ns::init"));
+    let task = config.tasks.get("ns::flow").unwrap();
+    assert_eq!(
+        task.dependencies.clone().unwrap(),
+        vec!["ns::init".to_string(), "ns::core-build".to_string()]
+    );
+}
+
+#[test]
+fn config_apply_modify_rename_swap() {
+    let modify_config =
+        create_rename_modify_config(vec![("build", "init"), ("init", "build")], None);
+    let mut config = create_rename_test_config();
+
+    config.apply(&modify_config).unwrap();
+
+    let task = config.tasks.get("flow").unwrap();
+    assert_eq!(
+        task.dependencies.clone().unwrap(),
+        vec!["build".to_string(), "init".to_string()]
+    );
+}
+
+#[test]
+fn config_apply_modify_rename_conflict() {
+    let modify_config = create_rename_modify_config(vec![("build", "end")], None);
+    let mut config = create_rename_test_config();
+
+    let result = config.apply(&modify_config);
+
+    match result.err().unwrap() {
+        DescriptorError::RenameConflict { task, name } => {
+            assert_eq!(task, "build");
+            assert_eq!(name, "end");
+        }
+        _ => panic!("wrong error type"),
+    };
+}
+
+#[test]
+fn config_apply_modify_rename_same_target() {
+    let modify_config =
+        create_rename_modify_config(vec![("build", "target"), ("end", "target")], None);
+    let mut config = create_rename_test_config();
+
+    let result = config.apply(&modify_config);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn descriptor_error_display_rename_conflict() {
+    let error = DescriptorError::RenameConflict {
+        task: "build".to_string(),
+        name: "end".to_string(),
+    };
+
+    let message = error.to_string();
+    assert!(message.contains("build"));
+    assert!(message.contains("end"));
+}
+
 #[test]
 fn deprecation_info_partial_eq_same_bool_true() {
     let value1 = DeprecationInfo::Boolean(true);
@@ -3687,3 +4564,296 @@ fn deprecation_info_partial_eq_diff_type() {
 
     assert!(value1 != value2);
 }
+
+#[test]
+fn descriptor_error_display_min_version() {
+    let error = DescriptorError::MinVersion {
+        required: "999.999.999".to_string(),
+        file: None,
+    };
+
+    let message = error.to_string();
+    assert!(message.contains("does not meet minimum required version: 999.999.999"));
+    assert!(!message.contains("required by"));
+}
+
+#[test]
+fn descriptor_error_display_min_version_with_file() {
+    let error = DescriptorError::MinVersion {
+        required: "999.999.999".to_string(),
+        file: Some("Makefile.toml".to_string()),
+    };
+
+    let message = error.to_string();
+    assert!(message.contains(
+        "does not meet minimum required version: 999.999.999 (required by: Makefile.toml)"
+    ));
+}
+
+#[test]
+fn descriptor_error_display_max_version() {
+    let error = DescriptorError::MaxVersion {
+        maximum: "0.0.1".to_string(),
+    };
+
+    let message = error.to_string();
+    assert!(message.contains("is newer than the maximum supported version: 0.0.1"));
+}
+
+#[test]
+fn descriptor_error_display_parse() {
+    let source = toml::from_str::<ExternalConfig>("[tasks.bad").unwrap_err();
+    let error = DescriptorError::Parse {
+        file: "./Makefile.toml".to_string(),
+        source,
+    };
+
+    let message = error.to_string();
+    assert!(message.starts_with("Unable to parse external descriptor: ./Makefile.toml (line: 1"));
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn descriptor_error_display_parse_yaml() {
+    let error = DescriptorError::ParseYaml {
+        file: "./Makefile.yaml".to_string(),
+        message: "bad yaml".to_string(),
+    };
+
+    let message = error.to_string();
+    assert!(message.contains("./Makefile.yaml"));
+    assert!(message.contains("bad yaml"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_file_not_found() {
+    let error = DescriptorError::FileNotFound(PathBuf::from("./Makefile.toml"));
+
+    assert!(error.to_string().contains("Makefile.toml"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_is_directory() {
+    let error = DescriptorError::IsDirectory(PathBuf::from("./tasks"));
+
+    let message = error.to_string();
+    assert!(message.contains("tasks"));
+    assert!(message.contains("is a directory"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_env_file_not_found() {
+    let error = DescriptorError::EnvFileNotFound(PathBuf::from("./missing.env"));
+
+    let message = error.to_string();
+    assert!(message.contains("missing.env"));
+    assert!(message.contains("not found"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_empty_descriptor() {
+    let error = DescriptorError::EmptyDescriptor("./Makefile.toml".to_string());
+
+    let message = error.to_string();
+    assert!(message.contains("Makefile.toml"));
+    assert!(message.contains("is empty"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_serialize() {
+    let error = DescriptorError::Serialize("test error".to_string());
+
+    let message = error.to_string();
+    assert!(message.contains("Unable to serialize config"));
+    assert!(message.contains("test error"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_extend_depth_limit() {
+    let error = DescriptorError::ExtendDepthLimit {
+        file: "deep.toml".to_string(),
+        limit: 50,
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "extend depth limit: 50 exceeded at file: deep.toml"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_canonicalize() {
+    let error = DescriptorError::Canonicalize {
+        file: PathBuf::from("./missing.toml"),
+        message: "test error".to_string(),
+    };
+
+    let message = error.to_string();
+    assert!(message.contains("Unable to canonicalize descriptor path"));
+    assert!(message.contains("missing.toml"));
+    assert!(message.contains("test error"));
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_invalid_task_name_pattern() {
+    let error = DescriptorError::InvalidTaskNamePattern("test error".to_string());
+
+    assert_eq!(error.to_string(), "Invalid task name pattern: test error");
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_invalid_task_name() {
+    let error = DescriptorError::InvalidTaskName {
+        task: "Build".to_string(),
+        pattern: "^[a-z0-9-]+$".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Task name: Build does not match the task name pattern: ^[a-z0-9-]+$"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_task_name_conflict() {
+    let error = DescriptorError::TaskNameConflict {
+        task: "build".to_string(),
+        other: "Build".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Task names: build and Build conflict after normalization."
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_duplicate_task_keys() {
+    let error = DescriptorError::DuplicateTaskKeys {
+        file: "Makefile.toml".to_string(),
+        keys: vec!["build".to_string(), "test".to_string()],
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Duplicate task keys found in descriptor: Makefile.toml, build, test"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_undefined_cli_env() {
+    let error = DescriptorError::UndefinedCliEnv("RUSTFLGS".to_string());
+
+    assert_eq!(
+        error.to_string(),
+        "Cli env override key: RUSTFLGS is not defined in the makefiles env"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_env_block_not_found() {
+    let error = DescriptorError::EnvBlockNotFound {
+        task: "test".to_string(),
+        block: "database".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Env block: database referenced by task: test not found"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_stdin() {
+    let error = DescriptorError::Stdin("test".to_string());
+
+    assert_eq!(
+        error.to_string(),
+        "Unable to read makefile from stdin: test"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_final_task_override() {
+    let error = DescriptorError::FinalTaskOverride("test".to_string());
+
+    assert_eq!(
+        error.to_string(),
+        "Task: test is final and can not be overridden (strict mode is enabled)"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_env_type_mismatch() {
+    let error = DescriptorError::EnvTypeMismatch {
+        key: "TEST".to_string(),
+        base_type: "list".to_string(),
+        extended_type: "value".to_string(),
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Env key: TEST type changed from: list to: value (strict_env_types is enabled)"
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn descriptor_error_display_circular_extend() {
+    let error = DescriptorError::CircularExtend(vec![
+        "a.toml".to_string(),
+        "b.toml".to_string(),
+        "a.toml".to_string(),
+    ]);
+
+    assert_eq!(
+        error.to_string(),
+        "circular extend detected: a.toml -> b.toml -> a.toml"
+    );
+}
+
+#[test]
+fn descriptor_error_display_unknown_keys() {
+    let error = DescriptorError::UnknownKeys {
+        file: "./Makefile.toml".to_string(),
+        keys: vec!["a".to_string(), "b".to_string()],
+    };
+
+    assert_eq!(
+        error.to_string(),
+        "Unknown keys found in descriptor: ./Makefile.toml, a, b"
+    );
+}
+
+#[test]
+fn descriptor_error_into_string() {
+    let error = DescriptorError::Remote("remote error".to_string());
+
+    let message: String = error.into();
+    assert_eq!(message, "remote error");
+}
+
+#[test]
+fn load_options_new() {
+    let load_options = LoadOptions::new();
+
+    assert!(!load_options.skip_workspace_makefile);
+    assert!(!load_options.strict);
+    assert!(!load_options.strict_env);
+}
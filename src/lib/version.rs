@@ -93,10 +93,40 @@ pub(crate) fn is_newer(old_string: &str, new_string: &str, default_result: bool)
     }
 }
 
-pub(crate) fn is_newer_found(version_string: &str) -> bool {
+fn parse_version(version_string: &str) -> Result<Version, String> {
+    match Version::parse(version_string) {
+        Ok(version) => Ok(version),
+        Err(error) => Err(format!(
+            "Unable to parse version: {} error: {}",
+            version_string, error
+        )),
+    }
+}
+
+/// Returns true if the new version has a higher semver precedence than the old version.<br>
+/// Pre-release versions have lower precedence than the release version and build metadata is ignored.
+pub(crate) fn is_newer_semver(old_string: &str, new_string: &str) -> Result<bool, String> {
+    let old_version = parse_version(old_string)?;
+    let new_version = parse_version(new_string)?;
+
+    Ok(new_version > old_version)
+}
+
+/// Returns the running cargo-make version
+pub(crate) fn get_running_version() -> String {
+    VERSION.to_string()
+}
+
+pub(crate) fn is_newer_found(version_string: &str) -> Result<bool, String> {
+    debug!("Checking Version: {}", &version_string);
+
+    is_newer_semver(&VERSION, &version_string)
+}
+
+pub(crate) fn is_older_found(version_string: &str) -> Result<bool, String> {
     debug!("Checking Version: {}", &version_string);
 
-    is_newer(&VERSION, &version_string, false)
+    is_newer_semver(&version_string, &VERSION)
 }
 
 fn print_notification(latest_string: &str) {
@@ -188,7 +218,7 @@ pub(crate) fn check() {
 
     match latest {
         Some(value) => {
-            if is_newer_found(&value) {
+            if is_newer_found(&value).unwrap_or(false) {
                 print_notification(&value);
             }
         }
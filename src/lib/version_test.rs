@@ -27,10 +27,15 @@ fn get_version_from_output_found() {
     assert_eq!(version.unwrap(), "1.2.3");
 }
 
+#[test]
+fn get_running_version_current() {
+    assert_eq!(get_running_version(), env!("CARGO_PKG_VERSION"));
+}
+
 #[test]
 fn is_newer_found_same() {
     let current = env!("CARGO_PKG_VERSION");
-    let newer = is_newer_found(current);
+    let newer = is_newer_found(current).unwrap();
 
     assert!(!newer);
 }
@@ -45,7 +50,7 @@ fn is_newer_found_newer_major() {
         + "."
         + &version.patch.to_string();
 
-    let newer = is_newer_found(&version_string);
+    let newer = is_newer_found(&version_string).unwrap();
 
     assert!(newer);
 }
@@ -60,7 +65,7 @@ fn is_newer_found_newer_minor() {
         + "."
         + &version.patch.to_string();
 
-    let newer = is_newer_found(&version_string);
+    let newer = is_newer_found(&version_string).unwrap();
 
     assert!(newer);
 }
@@ -75,7 +80,7 @@ fn is_newer_found_newer_patch() {
         + "."
         + &(version.patch + 1).to_string();
 
-    let newer = is_newer_found(&version_string);
+    let newer = is_newer_found(&version_string).unwrap();
 
     assert!(newer);
 }
@@ -90,9 +95,10 @@ fn is_newer_found_older_major_newer_minor() {
         + "."
         + &version.patch.to_string();
 
+    // for major version 0 the older version is not a valid semver
     let newer = is_newer_found(&version_string);
 
-    assert!(!newer);
+    assert!(!newer.unwrap_or(false));
 }
 
 #[test]
@@ -105,9 +111,10 @@ fn is_newer_found_older_major_newer_patch() {
         + "."
         + &(version.patch + 1).to_string();
 
+    // for major version 0 the older version is not a valid semver
     let newer = is_newer_found(&version_string);
 
-    assert!(!newer);
+    assert!(!newer.unwrap_or(false));
 }
 
 #[test]
@@ -120,11 +127,89 @@ fn is_newer_found_older_minor_newer_patch() {
         + "."
         + &(version.patch + 1).to_string();
 
-    let newer = is_newer_found(&version_string);
+    let newer = is_newer_found(&version_string).unwrap();
+
+    assert!(!newer);
+}
+
+#[test]
+fn is_older_found_same() {
+    let current = env!("CARGO_PKG_VERSION");
+    let older = is_older_found(current).unwrap();
+
+    assert!(!older);
+}
+
+#[test]
+fn is_older_found_older_major() {
+    let older = is_older_found("0.0.1").unwrap();
+
+    assert!(older);
+}
+
+#[test]
+fn is_older_found_newer_minor() {
+    let current = env!("CARGO_PKG_VERSION");
+    let version = Version::parse(current).unwrap();
+    let version_string = version.major.to_string()
+        + "."
+        + &(version.minor + 1).to_string()
+        + "."
+        + &version.patch.to_string();
+
+    let older = is_older_found(&version_string).unwrap();
+
+    assert!(!older);
+}
+
+#[test]
+fn is_older_found_invalid() {
+    let older = is_older_found("bad");
+
+    assert!(older.is_err());
+}
+
+#[test]
+fn is_newer_found_invalid() {
+    let newer = is_newer_found("bad");
+
+    assert!(newer.is_err());
+}
+
+#[test]
+fn is_newer_semver_release_newer_than_prerelease() {
+    let newer = is_newer_semver("1.2.0-beta.1", "1.2.0").unwrap();
+
+    assert!(newer);
+}
+
+#[test]
+fn is_newer_semver_prerelease_older_than_release() {
+    let newer = is_newer_semver("1.2.0", "1.2.0-beta.1").unwrap();
 
     assert!(!newer);
 }
 
+#[test]
+fn is_newer_semver_prerelease_order() {
+    assert!(is_newer_semver("1.2.0-alpha", "1.2.0-beta.1").unwrap());
+    assert!(is_newer_semver("1.2.0-beta.1", "1.2.0-beta.2").unwrap());
+    assert!(!is_newer_semver("1.2.0-beta.2", "1.2.0-beta.1").unwrap());
+}
+
+#[test]
+fn is_newer_semver_build_metadata_ignored() {
+    assert!(!is_newer_semver("1.2.0+build1", "1.2.0+build2").unwrap());
+    assert!(!is_newer_semver("1.2.0+build2", "1.2.0").unwrap());
+}
+
+#[test]
+fn is_newer_semver_invalid() {
+    let error = is_newer_semver("1.2.0", "1.2").unwrap_err();
+
+    assert!(error.contains("1.2"));
+}
+
 #[test]
 fn check_full() {
     check();